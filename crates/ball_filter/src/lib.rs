@@ -9,6 +9,7 @@ use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
 
 mod hypothesis;
+pub mod trajectory;
 
 pub use hypothesis::{BallHypothesis, BallMode};
 use types::multivariate_normal_distribution::MultivariateNormalDistribution;
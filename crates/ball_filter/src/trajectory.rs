@@ -0,0 +1,228 @@
+use std::time::Duration;
+
+use coordinate_systems::Ground;
+use geometry::circle::Circle;
+use linear_algebra::{Point2, Vector2};
+use types::{
+    ball_trajectory::{BallTrajectory, PredictedBallState},
+    parameters::BallTrajectoryParameters,
+};
+
+/// Speed and deceleration of a rolling ball observed at one point in time
+#[derive(Clone, Copy, Debug)]
+pub struct RollingObservation {
+    pub speed: f32,
+    pub deceleration: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RollingFriction {
+    pub rolling_deceleration: f32,
+    pub velocity_drag: f32,
+}
+
+/// Integrates the ball motion with rolling friction and bounces off `colliders` until the ball
+/// rests or the prediction horizon ends. Colliders already overlapping the ball at the start, e.g.
+/// the robot which just kicked it, are ignored.
+pub fn predict_trajectory(
+    position: Point2<Ground>,
+    velocity: Vector2<Ground>,
+    ball_radius: f32,
+    colliders: &[Circle<Ground>],
+    parameters: &BallTrajectoryParameters,
+) -> BallTrajectory {
+    let time_step = parameters.time_step.as_secs_f32();
+    let mut state = PredictedBallState {
+        time: Duration::ZERO,
+        position,
+        velocity,
+    };
+    let mut states = vec![state];
+    let colliders: Vec<_> = colliders
+        .iter()
+        .filter(|collider| (position - collider.center).norm() >= collider.radius + ball_radius)
+        .collect();
+
+    while state.velocity.norm() > parameters.resting_speed
+        && state.time < parameters.prediction_horizon
+        && time_step > 0.0
+    {
+        let speed = state.velocity.norm();
+        let deceleration = parameters.rolling_deceleration + parameters.velocity_drag * speed;
+        let next_speed = (speed - deceleration * time_step).max(0.0);
+        let next_velocity = state.velocity * (next_speed / speed);
+
+        state.position += (state.velocity + next_velocity) * 0.5 * time_step;
+        state.velocity = next_velocity;
+        state.time += parameters.time_step;
+
+        for collider in &colliders {
+            bounce_off(&mut state, collider, ball_radius, parameters.restitution);
+        }
+        states.push(state);
+    }
+
+    let time_to_rest = (state.velocity.norm() <= parameters.resting_speed).then_some(state.time);
+    BallTrajectory {
+        states,
+        rest_position: state.position,
+        time_to_rest,
+    }
+}
+
+fn bounce_off(
+    state: &mut PredictedBallState,
+    collider: &Circle<Ground>,
+    ball_radius: f32,
+    restitution: f32,
+) {
+    let minimum_distance = collider.radius + ball_radius;
+    let offset = state.position - collider.center;
+    if offset.norm() >= minimum_distance {
+        return;
+    }
+    let Some(normal) = offset.try_normalize(f32::EPSILON) else {
+        return;
+    };
+    let normal_speed = state.velocity.dot(&normal);
+    if normal_speed < 0.0 {
+        state.velocity -= normal * (1.0 + restitution) * normal_speed;
+    }
+    state.position = collider.center + normal * minimum_distance;
+}
+
+/// Least squares fit of `deceleration = rolling_deceleration + velocity_drag * speed`, `None` if
+/// the observations do not cover different speeds
+pub fn fit_rolling_friction(observations: &[RollingObservation]) -> Option<RollingFriction> {
+    let count = observations.len() as f32;
+    let speed_sum: f32 = observations
+        .iter()
+        .map(|observation| observation.speed)
+        .sum();
+    let deceleration_sum: f32 = observations
+        .iter()
+        .map(|observation| observation.deceleration)
+        .sum();
+    let squared_speed_sum: f32 = observations
+        .iter()
+        .map(|observation| observation.speed.powi(2))
+        .sum();
+    let product_sum: f32 = observations
+        .iter()
+        .map(|observation| observation.speed * observation.deceleration)
+        .sum();
+
+    let determinant = count * squared_speed_sum - speed_sum.powi(2);
+    if determinant.abs() < f32::EPSILON {
+        return None;
+    }
+    let velocity_drag = (count * product_sum - speed_sum * deceleration_sum) / determinant;
+    let rolling_deceleration = (deceleration_sum - velocity_drag * speed_sum) / count;
+    Some(RollingFriction {
+        rolling_deceleration,
+        velocity_drag,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use linear_algebra::{point, vector};
+
+    use super::*;
+
+    fn parameters() -> BallTrajectoryParameters {
+        BallTrajectoryParameters {
+            rolling_deceleration: 0.3,
+            velocity_drag: 0.2,
+            restitution: 0.5,
+            time_step: Duration::from_millis(10),
+            prediction_horizon: Duration::from_secs(10),
+            resting_speed: 0.01,
+        }
+    }
+
+    #[test]
+    fn ball_rests_after_rolling_straight() {
+        let trajectory = predict_trajectory(
+            Point2::origin(),
+            vector![1.0, 0.0],
+            0.05,
+            &[],
+            &parameters(),
+        );
+
+        // v' = -(a + b v) integrates to a distance of v0 / b - a / b² ln(1 + b v0 / a)
+        let expected_distance = 1.0 / 0.2 - 0.3 / 0.04 * (1.0_f32 + 0.2 / 0.3).ln();
+        assert!((trajectory.rest_position.x() - expected_distance).abs() < 0.02);
+        assert!(trajectory.rest_position.y().abs() < f32::EPSILON);
+        assert!(trajectory.time_to_rest.is_some());
+    }
+
+    #[test]
+    fn ball_bounces_back_from_goal_post() {
+        let post = Circle {
+            center: point![1.0, 0.0],
+            radius: 0.05,
+        };
+        let trajectory = predict_trajectory(
+            Point2::origin(),
+            vector![2.0, 0.0],
+            0.05,
+            &[post],
+            &parameters(),
+        );
+
+        assert!(trajectory
+            .states
+            .iter()
+            .all(|state| state.position.x() <= 0.9 + 1e-4));
+        assert!(trajectory.rest_position.x() < 0.9);
+        assert!(trajectory.states.last().unwrap().velocity.x() <= 0.0);
+    }
+
+    #[test]
+    fn ball_leaves_robot_it_starts_in() {
+        let kicking_robot = Circle {
+            center: point![-0.1, 0.0],
+            radius: 0.3,
+        };
+        let trajectory = predict_trajectory(
+            Point2::origin(),
+            vector![-1.0, 0.0],
+            0.05,
+            &[kicking_robot],
+            &parameters(),
+        );
+
+        assert!(trajectory.rest_position.x() < -1.0);
+        assert!(trajectory
+            .states
+            .windows(2)
+            .all(|states| states[1].position.x() <= states[0].position.x()));
+    }
+
+    #[test]
+    fn friction_is_recovered_from_observations() {
+        let observations: Vec<_> = (1..20)
+            .map(|index| {
+                let speed = index as f32 * 0.1;
+                RollingObservation {
+                    speed,
+                    deceleration: 0.4 + 0.25 * speed,
+                }
+            })
+            .collect();
+
+        let friction = fit_rolling_friction(&observations).unwrap();
+
+        assert!((friction.rolling_deceleration - 0.4).abs() < 1e-3);
+        assert!((friction.velocity_drag - 0.25).abs() < 1e-3);
+        assert_eq!(
+            fit_rolling_friction(&[RollingObservation {
+                speed: 1.0,
+                deceleration: 0.5
+            }]),
+            None
+        );
+    }
+}
@@ -1,56 +1,11 @@
 use color_eyre::eyre::{Result, WrapErr};
 
 use code_generation::{generate, write_to_file::WriteToFile, ExecutionMode};
-use source_analyzer::{
-    cyclers::{CyclerKind, Cyclers},
-    manifest::{CyclerManifest, FrameworkManifest},
-    pretty::to_string_pretty,
-    structs::Structs,
-};
+use hulk_manifest::collect_hulk_cyclers;
+use source_analyzer::{pretty::to_string_pretty, structs::Structs};
 
 fn main() -> Result<()> {
-    let manifest = FrameworkManifest {
-        cyclers: vec![
-            CyclerManifest {
-                name: "Control",
-                kind: CyclerKind::RealTime,
-                instances: vec![""],
-                setup_nodes: vec!["crate::fake_data"],
-                nodes: vec![
-                    "control::active_vision",
-                    "control::ball_state_composer",
-                    "control::behavior::node",
-                    "control::dribble_path_planner",
-                    "control::filtered_game_controller_state_timer",
-                    "control::game_controller_state_filter",
-                    "control::kick_selector",
-                    "control::motion::look_around",
-                    "control::motion::motion_selector",
-                    "control::penalty_shot_direction_estimation",
-                    "control::primary_state_filter",
-                    "control::referee_position_provider",
-                    "control::role_assignment",
-                    "control::rule_obstacle_composer",
-                    "control::search_suggestor",
-                    "control::team_ball_receiver",
-                    "control::time_to_reach_kick_position",
-                    "control::world_state_composer",
-                ],
-                execution_time_warning_threshold: None,
-            },
-            CyclerManifest {
-                name: "SplNetwork",
-                kind: CyclerKind::Perception,
-                instances: vec![""],
-                setup_nodes: vec!["spl_network::message_receiver"],
-                nodes: vec!["spl_network::message_filter"],
-                execution_time_warning_threshold: None,
-            },
-        ],
-    };
-    let root = "../../crates/";
-
-    let cyclers = Cyclers::try_from_manifest(manifest, root)?;
+    let cyclers = collect_hulk_cyclers("../../crates/", Some("behavior_simulator"))?;
     for path in cyclers.watch_paths() {
         println!("cargo:rerun-if-changed={}", path.display());
     }
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+use coordinate_systems::Field;
+use linear_algebra::{distance, point, Point2, Vector2};
+use scenario::scenario;
+use spl_network_messages::{GameState, PlayerNumber};
+
+use bevyhavior_simulator::{
+    ball::BallResource,
+    game_controller::GameControllerCommand,
+    robot::Robot,
+    time::{Ticks, TicksTime},
+};
+use types::ball_position::SimulatorBallState;
+
+/// The ball is moved out of sight of all robots to this position
+const HIDDEN_BALL_POSITION: Point2<Field> = point![3.5, 2.5];
+const HIDE_BALL_TICK: u32 = 1500;
+const MAXIMUM_SEARCH_TICKS: u32 = 5000;
+
+#[scenario]
+fn coordinated_ball_search(app: &mut App) {
+    app.add_systems(Startup, startup);
+    app.add_systems(Update, update);
+}
+
+fn startup(
+    mut commands: Commands,
+    mut game_controller_commands: EventWriter<GameControllerCommand>,
+    mut ball: ResMut<BallResource>,
+) {
+    for number in [
+        PlayerNumber::One,
+        PlayerNumber::Two,
+        PlayerNumber::Three,
+        PlayerNumber::Four,
+        PlayerNumber::Five,
+        PlayerNumber::Six,
+        PlayerNumber::Seven,
+    ] {
+        commands.spawn(Robot::new(number));
+    }
+    ball.state = Some(SimulatorBallState {
+        position: point![-1.0, 0.0],
+        velocity: Vector2::zeros(),
+    });
+    game_controller_commands.send(GameControllerCommand::SetGameState(GameState::Playing));
+}
+
+fn update(
+    time: Res<Time<Ticks>>,
+    mut ball: ResMut<BallResource>,
+    robots: Query<&Robot>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if time.ticks() == HIDE_BALL_TICK {
+        ball.state = Some(SimulatorBallState {
+            position: HIDDEN_BALL_POSITION,
+            velocity: Vector2::zeros(),
+        });
+    }
+    if time.ticks() <= HIDE_BALL_TICK {
+        return;
+    }
+
+    let is_found = robots.iter().any(|robot| {
+        robot
+            .database
+            .main_outputs
+            .ball_position
+            .is_some_and(|ball_position| {
+                distance(
+                    robot.ground_to_field() * ball_position.position,
+                    HIDDEN_BALL_POSITION,
+                ) < 0.5
+            })
+    });
+    let search_ticks = time.ticks() - HIDE_BALL_TICK;
+    if is_found {
+        println!(
+            "Ball found after {:.1}s",
+            (search_ticks * 12) as f32 / 1000.0
+        );
+        println!("Done");
+        exit.send(AppExit::Success);
+    }
+    if search_ticks >= MAXIMUM_SEARCH_TICKS {
+        println!("Ball was not found within {MAXIMUM_SEARCH_TICKS} ticks");
+        exit.send(AppExit::from_code(1));
+    }
+}
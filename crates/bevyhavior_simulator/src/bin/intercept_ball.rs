@@ -72,13 +72,18 @@ fn update(
         let ball_in_ground = field_to_ground * ball.position;
         let velocity_in_ground = field_to_ground * ball.velocity;
 
-        if ball_in_ground.coords().norm() < 0.2
+        let ball_hits_robot = ball_in_ground.coords().norm() < 0.2
             && ball_in_ground
                 .coords()
                 .normalize()
                 .dot(&velocity_in_ground.normalize())
-                < -0.3
-        {
+                < -0.3;
+        let ball_hits_keeper_motion = robot.keeper_motion_coverage().is_some_and(|covered_range| {
+            ball_in_ground.x().abs() < 0.15
+                && covered_range.contains(&ball_in_ground.y())
+                && velocity_in_ground.x() < 0.0
+        });
+        if ball_hits_robot || ball_hits_keeper_motion {
             ball.velocity = Vector::zeros();
         }
     }
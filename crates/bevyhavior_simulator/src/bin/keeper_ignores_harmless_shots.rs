@@ -0,0 +1,108 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use linear_algebra::{point, vector, Isometry2, Vector2};
+use scenario::scenario;
+use spl_network_messages::{GameState, PlayerNumber};
+use types::{ball_position::SimulatorBallState, motion_command::MotionCommand};
+
+use bevyhavior_simulator::{
+    ball::BallResource,
+    game_controller::{GameController, GameControllerCommand},
+    robot::Robot,
+    time::{Ticks, TicksTime},
+};
+
+/// Origin and target along the y axis and speed of each shot, either missing the goal or too
+/// slow to reach it
+const SHOTS: [(f32, f32, f32); 5] = [
+    (0.0, 1.3, 3.0),
+    (0.0, -1.3, 3.0),
+    (0.5, 1.8, 3.0),
+    (-0.5, -1.8, 3.0),
+    (0.0, 0.0, 0.8),
+];
+
+#[scenario]
+fn keeper_ignores_harmless_shots(app: &mut App) {
+    app.add_systems(Startup, startup);
+    app.add_systems(Update, update);
+}
+
+#[derive(SystemParam)]
+struct State<'s> {
+    shots: Local<'s, usize>,
+    last_shot_end: Local<'s, u32>,
+}
+
+fn startup(
+    mut commands: Commands,
+    mut game_controller_commands: EventWriter<GameControllerCommand>,
+    mut ball: ResMut<BallResource>,
+) {
+    commands.spawn(Robot::new(PlayerNumber::One));
+    let mut striker = Robot::new(PlayerNumber::Seven);
+    *striker.ground_to_field_mut() = Isometry2::from_parts(vector![-1.5, 0.0], 0.0);
+    commands.spawn(striker);
+    game_controller_commands.send(GameControllerCommand::SetGameState(GameState::Playing));
+    ball.friction_coefficient = 0.995;
+}
+
+fn update(
+    game_controller: ResMut<GameController>,
+    time: ResMut<Time<Ticks>>,
+    mut ball: ResMut<BallResource>,
+    mut exit: EventWriter<AppExit>,
+    mut robots: Query<&mut Robot>,
+    mut state: State,
+) {
+    if game_controller.state.opponent_team.score > 0 {
+        println!("Shot {} unexpectedly hit the goal", *state.shots);
+        exit.send(AppExit::from_code(1));
+        return;
+    }
+    if time.ticks() >= 10_000 {
+        println!("Only {} of {} shots were taken", *state.shots, SHOTS.len());
+        exit.send(AppExit::from_code(1));
+        return;
+    }
+    let Some(mut keeper) = robots
+        .iter_mut()
+        .find(|robot| robot.parameters.player_number == PlayerNumber::One)
+    else {
+        return;
+    };
+
+    if let MotionCommand::KeeperMotion { .. } | MotionCommand::Jump { .. } =
+        keeper.database.main_outputs.motion_command
+    {
+        println!("Keeper dove for harmless shot {}", *state.shots);
+        exit.send(AppExit::from_code(1));
+        return;
+    }
+
+    let shot_is_over = ball.state.map_or(true, |ball| {
+        ball.velocity.norm() < 0.05 || ball.position.x() < -4.4 || ball.position.y().abs() > 2.5
+    });
+    if shot_is_over && *state.last_shot_end == 0 {
+        *state.last_shot_end = time.ticks();
+        ball.state = Some(SimulatorBallState {
+            position: point![-2.0, 0.0],
+            velocity: Vector2::zeros(),
+        });
+    }
+    if shot_is_over && time.ticks() - *state.last_shot_end > 100 {
+        let Some(&(origin, target, speed)) = SHOTS.get(*state.shots) else {
+            println!("Done");
+            exit.send(AppExit::Success);
+            return;
+        };
+        *keeper.ground_to_field_mut() = Isometry2::from_parts(vector![-4.3, 0.0], 0.0);
+        let position = point![-2.0, origin];
+        ball.state = Some(SimulatorBallState {
+            position,
+            velocity: (point![-4.5, target] - position).normalize() * speed,
+        });
+        *state.shots += 1;
+        *state.last_shot_end = 0;
+    }
+}
@@ -0,0 +1,142 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use linear_algebra::{point, vector, Isometry2, Vector2};
+use scenario::scenario;
+use spl_network_messages::{GameState, PlayerNumber};
+use types::{
+    ball_position::SimulatorBallState,
+    motion_command::{JumpDirection, MotionCommand},
+};
+
+use bevyhavior_simulator::{
+    ball::BallResource,
+    game_controller::{GameController, GameControllerCommand},
+    robot::Robot,
+    time::{Ticks, TicksTime},
+};
+
+/// Origin and target of each shot along the y axis
+const SHOTS: [(f32, f32); 7] = [
+    (0.0, 0.0),
+    (0.0, 0.45),
+    (0.0, -0.45),
+    (0.5, 0.2),
+    (-0.5, -0.2),
+    (0.3, -0.5),
+    (-0.3, 0.5),
+];
+
+#[scenario]
+fn keeper_saves_shots(app: &mut App) {
+    app.add_systems(Startup, startup);
+    app.add_systems(Update, update);
+}
+
+#[derive(SystemParam)]
+struct State<'s> {
+    shots: Local<'s, usize>,
+    last_shot_end: Local<'s, u32>,
+    used_dive_directions: Local<'s, Vec<JumpDirection>>,
+}
+
+fn startup(
+    mut commands: Commands,
+    mut game_controller_commands: EventWriter<GameControllerCommand>,
+    mut ball: ResMut<BallResource>,
+) {
+    commands.spawn(Robot::new(PlayerNumber::One));
+    let mut striker = Robot::new(PlayerNumber::Seven);
+    *striker.ground_to_field_mut() = Isometry2::from_parts(vector![-1.5, 0.0], 0.0);
+    commands.spawn(striker);
+    game_controller_commands.send(GameControllerCommand::SetGameState(GameState::Playing));
+    ball.friction_coefficient = 0.995;
+}
+
+fn update(
+    game_controller: ResMut<GameController>,
+    time: ResMut<Time<Ticks>>,
+    mut ball: ResMut<BallResource>,
+    mut exit: EventWriter<AppExit>,
+    mut robots: Query<&mut Robot>,
+    mut state: State,
+) {
+    if game_controller.state.opponent_team.score > 0 {
+        println!("Failed to save shot {}", *state.shots);
+        exit.send(AppExit::from_code(1));
+        return;
+    }
+    if time.ticks() >= 10_000 {
+        println!("Only {} of {} shots were taken", *state.shots, SHOTS.len());
+        exit.send(AppExit::from_code(1));
+        return;
+    }
+    let Some(mut keeper) = robots
+        .iter_mut()
+        .find(|robot| robot.parameters.player_number == PlayerNumber::One)
+    else {
+        return;
+    };
+
+    let shot_is_over = ball.state.map_or(true, |ball| {
+        ball.velocity.norm() < 0.05 || ball.velocity.x() > 0.0 || ball.position.x() < -4.4
+    });
+    if shot_is_over && *state.last_shot_end == 0 {
+        *state.last_shot_end = time.ticks();
+        ball.state = Some(SimulatorBallState {
+            position: point![-2.0, 0.0],
+            velocity: Vector2::zeros(),
+        });
+    }
+    if shot_is_over && time.ticks() - *state.last_shot_end > 100 {
+        let Some(&(origin, target)) = SHOTS.get(*state.shots) else {
+            let all_dives_were_used = [
+                JumpDirection::Center,
+                JumpDirection::Left,
+                JumpDirection::Right,
+            ]
+            .iter()
+            .all(|direction| state.used_dive_directions.contains(direction));
+            if all_dives_were_used {
+                println!("Done");
+                exit.send(AppExit::Success);
+            } else {
+                println!(
+                    "Keeper did not use all dives: {:?}",
+                    *state.used_dive_directions
+                );
+                exit.send(AppExit::from_code(1));
+            }
+            return;
+        };
+        *keeper.ground_to_field_mut() = Isometry2::from_parts(vector![-4.3, 0.0], 0.0);
+        let position = point![-2.0, origin];
+        ball.state = Some(SimulatorBallState {
+            position,
+            velocity: (point![-4.5, target] - position).normalize() * 3.0,
+        });
+        *state.shots += 1;
+        *state.last_shot_end = 0;
+    }
+
+    // basic collision physics of the keeper motions
+    if let Some(ball) = ball.state.as_mut() {
+        let field_to_ground = keeper.ground_to_field().inverse();
+        let ball_in_ground = field_to_ground * ball.position;
+        let velocity_in_ground = field_to_ground * ball.velocity;
+        if let Some(covered_range) = keeper.keeper_motion_coverage() {
+            if let MotionCommand::KeeperMotion { direction } | MotionCommand::Jump { direction } =
+                keeper.database.main_outputs.motion_command
+            {
+                if !state.used_dive_directions.contains(&direction) {
+                    state.used_dive_directions.push(direction);
+                }
+            }
+            if ball_in_ground.x().abs() < 0.15
+                && covered_range.contains(&ball_in_ground.y())
+                && velocity_in_ground.x() < 0.0
+            {
+                ball.velocity = vector![1.0, 0.0];
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use linear_algebra::{point, vector, Isometry2, Point2};
+use linear_algebra::{point, vector, Isometry2, Point2, Vector2};
 use scenario::scenario;
 use spl_network_messages::{GameState, PlayerNumber};
 use types::{
@@ -55,6 +55,7 @@ fn update(
             ],
         radius_at_foot_height: 0.5,
         radius_at_hip_height: 0.5,
+        velocity: Vector2::zeros(),
     }];
 
     if game_controller.state.hulks_team.score > 0 {
@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+
+use linear_algebra::point;
+use scenario::scenario;
+use spl_network_messages::{GameState, PlayerNumber};
+
+use bevyhavior_simulator::{
+    game_controller::{GameController, GameControllerCommand},
+    opponent::{Opponent, OpponentPolicy},
+    robot::Robot,
+    time::{Ticks, TicksTime},
+};
+use types::motion_command::MotionCommand;
+
+#[scenario]
+fn pass_to_teammate(app: &mut App) {
+    app.add_systems(Startup, startup);
+    app.add_systems(Update, update);
+}
+
+fn startup(
+    mut commands: Commands,
+    mut game_controller_commands: EventWriter<GameControllerCommand>,
+) {
+    for number in [
+        PlayerNumber::One,
+        PlayerNumber::Two,
+        PlayerNumber::Three,
+        PlayerNumber::Four,
+        PlayerNumber::Five,
+        PlayerNumber::Six,
+        PlayerNumber::Seven,
+    ] {
+        commands.spawn(Robot::new(number));
+    }
+    for lateral_offset in [-0.3, 0.0, 0.3] {
+        commands.spawn(Opponent::new(
+            point![3.0, lateral_offset],
+            OpponentPolicy::WallDefense { lateral_offset },
+        ));
+    }
+    game_controller_commands.send(GameControllerCommand::SetGameState(GameState::Ready));
+}
+
+fn update(
+    game_controller: ResMut<GameController>,
+    time: Res<Time<Ticks>>,
+    mut exit: EventWriter<AppExit>,
+    robots: Query<&Robot>,
+) {
+    let is_passing = robots.iter().any(|robot| {
+        let is_kicking = matches!(
+            robot.database.main_outputs.motion_command,
+            MotionCommand::InWalkKick { .. }
+        );
+        let is_pass = robot
+            .database
+            .main_outputs
+            .kick_decisions
+            .as_ref()
+            .and_then(|decisions| decisions.first())
+            .is_some_and(|decision| decision.receiver.is_some());
+        is_kicking && is_pass
+    });
+    if is_passing {
+        println!("Done");
+        exit.send(AppExit::Success);
+    }
+    if game_controller.state.hulks_team.score > 0 {
+        println!("Scored without passing");
+        exit.send(AppExit::from_code(1));
+    }
+    if time.ticks() >= 15_000 {
+        println!("No pass was played :(");
+        exit.send(AppExit::from_code(1));
+    }
+}
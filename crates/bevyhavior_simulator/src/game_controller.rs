@@ -3,7 +3,7 @@ use std::time::{Duration, SystemTime};
 use bevy::prelude::*;
 
 use spl_network_messages::{
-    GamePhase, GameState, Penalty, PlayerNumber, SubState, Team, TeamColor, TeamState,
+    GamePhase, GameState, Half, Penalty, PlayerNumber, SubState, Team, TeamColor, TeamState,
 };
 use types::{game_controller_state::GameControllerState, players::Players};
 
@@ -160,6 +160,7 @@ impl Default for GameController {
             state: GameControllerState {
                 game_state: GameState::Initial,
                 game_phase: GamePhase::Normal,
+                half: Half::First,
                 remaining_time_in_half: Duration::ZERO,
                 kicking_team: Some(Team::Hulks),
                 last_game_state_change: SystemTime::UNIX_EPOCH,
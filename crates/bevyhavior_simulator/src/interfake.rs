@@ -12,6 +12,7 @@ use hardware::{NetworkInterface, RecordingInterface, SpeakerInterface, TimeInter
 use types::{
     audio::SpeakerRequest,
     messages::{IncomingMessage, OutgoingMessage},
+    recording_event::RecordingEvent,
 };
 
 use crate::{cyclers::control::Database, HardwareInterface};
@@ -53,6 +54,12 @@ impl RecordingInterface for Interfake {
     }
 
     fn set_whether_to_record(&self, _enable: bool) {}
+
+    fn trigger_recording_event(&self, _event: RecordingEvent) {}
+
+    fn take_recording_events(&self) -> Vec<RecordingEvent> {
+        Vec::new()
+    }
 }
 
 impl TimeInterface for Interfake {
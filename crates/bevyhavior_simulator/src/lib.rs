@@ -9,6 +9,7 @@ pub mod fake_data;
 pub mod field_dimensions;
 pub mod game_controller;
 pub mod interfake;
+pub mod opponent;
 pub mod recorder;
 pub mod robot;
 pub mod scenario;
@@ -0,0 +1,281 @@
+use std::{f32::consts::PI, time::Duration};
+
+use bevy::{
+    ecs::{
+        component::Component,
+        system::{Query, Res, ResMut},
+    },
+    time::Time,
+};
+
+use coordinate_systems::{Field, Ground, Head};
+use linear_algebra::{point, vector, Isometry2, Orientation2, Point2, Rotation2, Vector2};
+use spl_network_messages::{GameState, PlayerNumber};
+use types::obstacles::Obstacle;
+
+use crate::{
+    ball::BallResource, field_dimensions::SimulatorFieldDimensions,
+    game_controller::GameController, robot::Robot,
+};
+
+/// Scripted behavior of an opponent robot
+#[derive(Clone, Copy, Debug)]
+pub enum OpponentPolicy {
+    /// Walks behind the ball and kicks it towards the HULKs goal
+    ChaseBall,
+    /// Stays between the marked HULKs robot and the opponent goal
+    ManMark {
+        player_number: PlayerNumber,
+        distance: f32,
+    },
+    /// Blocks the line between ball and opponent goal at the edge of the penalty area, shifted
+    /// sideways to form a wall with other defenders
+    WallDefense { lateral_offset: f32 },
+}
+
+pub struct OpponentParameters {
+    pub walk_speed: f32,
+    pub turn_speed: f32,
+    pub radius: f32,
+    pub kick_range: f32,
+    pub kick_strength: f32,
+    pub kick_cooldown: Duration,
+}
+
+impl Default for OpponentParameters {
+    fn default() -> Self {
+        Self {
+            walk_speed: 0.1,
+            turn_speed: 1.0,
+            radius: 0.2,
+            kick_range: 0.3,
+            kick_strength: 1.5,
+            kick_cooldown: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Opponent robot which is not controlled by the HULKs code but follows an [`OpponentPolicy`]
+#[derive(Component)]
+pub struct Opponent {
+    pub ground_to_field: Isometry2<Ground, Field>,
+    pub home_position: Point2<Field>,
+    pub velocity: Vector2<Field>,
+    pub policy: OpponentPolicy,
+    pub parameters: OpponentParameters,
+    pub last_kick_time: Option<Duration>,
+}
+
+impl Opponent {
+    /// Creates an opponent at its home position facing the HULKs goal
+    pub fn new(home_position: Point2<Field>, policy: OpponentPolicy) -> Self {
+        Self {
+            ground_to_field: Isometry2::from_parts(home_position.coords(), PI),
+            home_position,
+            velocity: Vector2::zeros(),
+            policy,
+            parameters: OpponentParameters::default(),
+            last_kick_time: None,
+        }
+    }
+
+    pub fn position(&self) -> Point2<Field> {
+        self.ground_to_field.as_pose().position()
+    }
+
+    fn target(
+        &self,
+        ball: Option<Point2<Field>>,
+        robots: &Query<&Robot>,
+        field_dimensions: &SimulatorFieldDimensions,
+    ) -> Option<Point2<Field>> {
+        let opponent_goal = point![field_dimensions.length / 2.0, 0.0];
+        let hulks_goal = point![-field_dimensions.length / 2.0, 0.0];
+        match self.policy {
+            OpponentPolicy::ChaseBall => {
+                let ball = ball?;
+                let behind_ball = (ball - hulks_goal)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or(vector![1.0, 0.0]);
+                Some(ball + behind_ball * self.parameters.kick_range * 0.8)
+            }
+            OpponentPolicy::ManMark {
+                player_number,
+                distance,
+            } => {
+                let marked_robot = robots
+                    .iter()
+                    .find(|robot| robot.parameters.player_number == player_number)?;
+                let marked_position = marked_robot.ground_to_field().as_pose().position();
+                let to_goal = opponent_goal - marked_position;
+                Some(marked_position + to_goal.cap_magnitude(distance))
+            }
+            OpponentPolicy::WallDefense { lateral_offset } => {
+                let ball = ball?;
+                let to_ball = (ball - opponent_goal)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or(vector![-1.0, 0.0]);
+                let sideways = Rotation2::<Field, Field>::new(PI / 2.0) * to_ball;
+                Some(
+                    opponent_goal
+                        + to_ball * field_dimensions.penalty_area_length
+                        + sideways * lateral_offset,
+                )
+            }
+        }
+    }
+}
+
+pub fn move_opponents(
+    mut opponents: Query<&mut Opponent>,
+    robots: Query<&Robot>,
+    mut ball: ResMut<BallResource>,
+    field_dimensions: Res<SimulatorFieldDimensions>,
+    game_controller: Res<GameController>,
+    time: Res<Time>,
+) {
+    let hulks_goal = point![-field_dimensions.length / 2.0, 0.0];
+    let ball_position = ball.state.as_ref().map(|ball| ball.position);
+
+    for mut opponent in &mut opponents {
+        let target = match game_controller.state.game_state {
+            GameState::Playing => opponent.target(ball_position, &robots, &field_dimensions),
+            GameState::Ready => Some(opponent.home_position),
+            _ => None,
+        };
+        let position = opponent.position();
+        let target = target.unwrap_or(position);
+        let step =
+            (target - position).cap_magnitude(opponent.parameters.walk_speed * time.delta_secs());
+
+        let look_at = match game_controller.state.game_state {
+            GameState::Playing => ball_position.unwrap_or(hulks_goal),
+            _ => hulks_goal,
+        };
+        let current_orientation = opponent.ground_to_field.orientation();
+        let desired_orientation = Orientation2::from_vector(look_at - (position + step));
+        let maximum_turn = opponent.parameters.turn_speed * time.delta_secs();
+        let turn = current_orientation
+            .rotation_to(desired_orientation)
+            .angle()
+            .clamp(-maximum_turn, maximum_turn);
+        opponent.ground_to_field = Isometry2::from_parts(
+            (position + step).coords(),
+            current_orientation.angle() + turn,
+        );
+        opponent.velocity = if time.delta_secs() > 0.0 {
+            step / time.delta_secs()
+        } else {
+            Vector2::zeros()
+        };
+
+        if game_controller.state.game_state != GameState::Playing {
+            continue;
+        }
+        let Some(ball) = ball.state.as_mut() else {
+            continue;
+        };
+        let in_range =
+            (ball.position - opponent.position()).norm() < opponent.parameters.kick_range;
+        let previous_kick_finished = !opponent.last_kick_time.is_some_and(|last_kick_time| {
+            time.elapsed() - last_kick_time <= opponent.parameters.kick_cooldown
+        });
+        if in_range && previous_kick_finished {
+            ball.velocity = (hulks_goal - ball.position)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or(vector![-1.0, 0.0])
+                * opponent.parameters.kick_strength;
+            opponent.last_kick_time = Some(time.elapsed());
+        }
+    }
+}
+
+/// Bounces the ball off opponents and pushes HULKs robots out of them
+pub fn collide_with_opponents(
+    opponents: Query<&Opponent>,
+    mut robots: Query<&mut Robot>,
+    mut ball: ResMut<BallResource>,
+) {
+    for opponent in &opponents {
+        let opponent_position = opponent.position();
+        let radius = opponent.parameters.radius;
+
+        if let Some(ball) = ball.state.as_mut() {
+            let offset = ball.position - opponent_position;
+            if let Some(normal) = offset.try_normalize(f32::EPSILON) {
+                if offset.norm() < radius && ball.velocity.dot(&normal) < 0.0 {
+                    ball.velocity =
+                        (ball.velocity - normal * 2.0 * ball.velocity.dot(&normal)) * 0.5;
+                    ball.position = opponent_position + normal * radius;
+                }
+            }
+        }
+
+        for mut robot in &mut robots {
+            let old_ground_to_field = robot.ground_to_field();
+            let offset = old_ground_to_field.as_pose().position() - opponent_position;
+            let minimum_distance = 2.0 * radius;
+            let Some(normal) = offset.try_normalize(f32::EPSILON) else {
+                continue;
+            };
+            if offset.norm() >= minimum_distance {
+                continue;
+            }
+            let new_ground_to_field = Isometry2::from_parts(
+                (opponent_position + normal * minimum_distance).coords(),
+                old_ground_to_field.orientation().angle(),
+            );
+            if let Some(ball) = robot.database.main_outputs.ball_position.as_mut() {
+                let old_ground_to_new_ground = new_ground_to_field.inverse() * old_ground_to_field;
+                ball.position = old_ground_to_new_ground * ball.position;
+                ball.velocity = old_ground_to_new_ground * ball.velocity;
+            }
+            *robot.ground_to_field_mut() = new_ground_to_field;
+        }
+    }
+}
+
+/// Adds the opponents each HULKs robot currently sees to its obstacles, replacing the opponents
+/// observed in the last tick but keeping all other obstacles
+pub fn observe_opponents(opponents: Query<&Opponent>, mut robots: Query<&mut Robot>) {
+    if opponents.is_empty() {
+        return;
+    }
+
+    for mut robot in &mut robots {
+        let field_to_ground = robot.ground_to_field().inverse();
+        let head_to_ground = Rotation2::<Head, Ground>::new(
+            robot.database.main_outputs.sensor_data.positions.head.yaw,
+        );
+        let field_of_view = robot.field_of_view();
+        let view_range = robot.simulator_parameters.obstacle_view_range;
+
+        let observed_opponents: Vec<_> = opponents
+            .iter()
+            .filter_map(|opponent| {
+                let opponent_in_ground = field_to_ground * opponent.position();
+                let opponent_in_head: Point2<Head> = head_to_ground.inverse() * opponent_in_ground;
+                let angle_to_opponent = opponent_in_head.coords().angle(&Vector2::x_axis());
+                let is_visible = angle_to_opponent.abs() < field_of_view / 2.0
+                    && opponent_in_head.coords().norm() < view_range;
+                is_visible.then(|| Obstacle {
+                    velocity: field_to_ground * opponent.velocity,
+                    ..Obstacle::robot(
+                        opponent_in_ground,
+                        opponent.parameters.radius,
+                        opponent.parameters.radius,
+                    )
+                })
+            })
+            .collect();
+
+        let robot = &mut *robot;
+        let obstacles = &mut robot.database.main_outputs.obstacles;
+        let retained_obstacles = obstacles
+            .len()
+            .saturating_sub(robot.observed_opponent_count);
+        obstacles.truncate(retained_obstacles);
+        robot.observed_opponent_count = observed_opponents.len();
+        obstacles.extend(observed_opponents);
+    }
+}
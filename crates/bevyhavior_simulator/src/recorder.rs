@@ -14,14 +14,19 @@ use tokio::{
 };
 use tokio_util::sync::CancellationToken;
 
+use coordinate_systems::Field;
+use linear_algebra::Pose2;
 use types::{ball_position::SimulatorBallState, players::Players};
 
-use crate::{ball::BallResource, cyclers::control::Database, robot::Robot, server};
+use crate::{
+    ball::BallResource, cyclers::control::Database, opponent::Opponent, robot::Robot, server,
+};
 
 pub struct Frame {
     pub timestamp: SystemTime,
     pub ball: Option<SimulatorBallState>,
     pub robots: Players<Option<Database>>,
+    pub opponents: Vec<Pose2<Field>>,
 }
 
 #[derive(Resource)]
@@ -33,6 +38,7 @@ pub struct Recording {
 
 pub fn frame_recorder(
     robots: Query<&Robot>,
+    opponents: Query<&Opponent>,
     ball: Res<BallResource>,
     recording: ResMut<Recording>,
     time: Res<Time>,
@@ -46,6 +52,10 @@ pub fn frame_recorder(
         .send(Frame {
             timestamp: UNIX_EPOCH + time.elapsed(),
             robots: players,
+            opponents: opponents
+                .iter()
+                .map(|opponent| opponent.ground_to_field.as_pose())
+                .collect(),
             ball: ball.state,
         })
         .expect("failed to send frame to server");
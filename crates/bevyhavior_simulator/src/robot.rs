@@ -1,6 +1,7 @@
 use std::{
     convert::Into,
     mem::take,
+    ops::Range,
     sync::{mpsc, Arc},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -22,6 +23,7 @@ use framework::{future_queue, Producer, RecordingTrigger};
 use geometry::{direction::Rotate90Degrees, line_segment::LineSegment};
 use hula_types::hardware::Ids;
 use linear_algebra::{vector, Isometry2, Orientation2, Point2, Rotation2, Vector2};
+use nalgebra::Matrix3;
 use parameters::directory::deserialize;
 use projection::camera_matrix::CameraMatrix;
 use spl_network_messages::{HulkMessage, PlayerNumber};
@@ -29,7 +31,7 @@ use types::{
     ball_position::BallPosition,
     filtered_whistle::FilteredWhistle,
     messages::{IncomingMessage, OutgoingMessage},
-    motion_command::{HeadMotion, KickVariant, MotionCommand, OrientationMode},
+    motion_command::{HeadMotion, JumpDirection, KickVariant, MotionCommand, OrientationMode},
     motion_selection::MotionSafeExits,
     planned_path::PathSegment,
     support_foot::Side,
@@ -52,6 +54,8 @@ pub struct Robot {
     pub last_kick_time: Duration,
     pub ball_last_seen: Option<SystemTime>,
     pub simulator_parameters: SimulatedRobotParameters,
+    /// Number of obstacles at the end of `obstacles` which are opponents observed in the last tick
+    pub observed_opponent_count: usize,
 
     pub cycler: Cycler<Interfake>,
     control_receiver: Receiver<(SystemTime, Database)>,
@@ -112,6 +116,7 @@ impl Robot {
         database.main_outputs.has_ground_contact = true;
         database.main_outputs.buttons.is_chest_button_pressed_once = true;
         database.main_outputs.is_localization_converged = true;
+        database.main_outputs.pose_covariance = Some(Matrix3::zeros());
 
         subscriptions_sender
             .borrow_mut()
@@ -120,6 +125,7 @@ impl Robot {
         let simulator_parameters = SimulatedRobotParameters {
             ball_view_range: 3.0,
             ball_timeout_factor: 0.1,
+            obstacle_view_range: 3.0,
         };
 
         Ok(Self {
@@ -129,6 +135,7 @@ impl Robot {
             last_kick_time: Duration::default(),
             ball_last_seen: None,
             simulator_parameters,
+            observed_opponent_count: 0,
 
             cycler,
             control_receiver,
@@ -145,7 +152,12 @@ impl Robot {
             self.spl_network_sender
                 .finalize(crate::structs::spl_network::MainOutputs {
                     filtered_message: source_is_other.then(|| message.clone()),
+                    teammate_ball_search_hotspot: payload
+                        .ball_search_hotspot()
+                        .filter(|_| source_is_other)
+                        .map(|hotspot| hotspot.position()),
                     message,
+                    degraded_nodes: Vec::new(),
                 });
         }
         buffered_watch::Sender::<_>::borrow_mut(
@@ -193,6 +205,26 @@ impl Robot {
     pub fn whistle_mut(&mut self) -> &mut FilteredWhistle {
         &mut self.database.main_outputs.filtered_whistle
     }
+
+    /// Lateral range in ground coordinates in which the current keeper motion blocks the ball,
+    /// fixed here instead of taken from the estimator's reach to not grade it against itself
+    pub fn keeper_motion_coverage(&self) -> Option<Range<f32>> {
+        match self.database.main_outputs.motion_command {
+            MotionCommand::KeeperMotion {
+                direction: JumpDirection::Center,
+            } => Some(-0.25..0.25),
+            MotionCommand::Jump {
+                direction: JumpDirection::Center,
+            } => Some(-0.35..0.35),
+            MotionCommand::KeeperMotion {
+                direction: JumpDirection::Left,
+            } => Some(0.0..0.6),
+            MotionCommand::KeeperMotion {
+                direction: JumpDirection::Right,
+            } => Some(-0.6..0.0),
+            _ => None,
+        }
+    }
 }
 
 pub fn to_player_number(value: usize) -> Result<PlayerNumber, String> {
@@ -427,4 +459,5 @@ pub fn cycle_robots(
 pub struct SimulatedRobotParameters {
     pub ball_view_range: f32,
     pub ball_timeout_factor: f32,
+    pub obstacle_view_range: f32,
 }
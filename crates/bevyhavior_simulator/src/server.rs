@@ -6,7 +6,9 @@ use serde::{Deserialize, Serialize};
 use tokio::{net::ToSocketAddrs, select, sync::mpsc::UnboundedReceiver};
 use tokio_util::sync::CancellationToken;
 
+use coordinate_systems::Field;
 use hula_types::hardware::Ids;
+use linear_algebra::Pose2;
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use types::{
     ball_position::SimulatorBallState, field_dimensions::FieldDimensions, players::Players,
@@ -26,6 +28,7 @@ struct MainOutputs {
     frame_count: usize,
     ball: Option<SimulatorBallState>,
     databases: Players<Option<Database>>,
+    opponents: Vec<Pose2<Field>>,
 }
 
 #[derive(Clone, Default, Serialize, PathSerialize, PathIntrospect)]
@@ -73,6 +76,7 @@ async fn timeline_server(
                 outputs.main_outputs.frame_count = frames.len();
                 outputs.main_outputs.ball.clone_from(&frame.ball);
                 outputs.main_outputs.databases = frame.robots.clone();
+                outputs.main_outputs.opponents.clone_from(&frame.opponents);
                 *time = frame.timestamp;
             }
             {
@@ -22,6 +22,7 @@ use crate::{
     ball::{move_ball, BallResource},
     field_dimensions::SimulatorFieldDimensions,
     game_controller::{game_controller_plugin, GameController},
+    opponent::{collide_with_opponents, move_opponents, observe_opponents},
     recorder::Recording,
     robot::{cycle_robots, move_robots, Messages},
     server::Parameters,
@@ -61,9 +62,15 @@ impl Plugin for SimulatorPlugin {
         .insert_resource(Time::<()>::default())
         .insert_resource(Time::<Ticks>::default())
         .add_systems(First, update_time)
+        .add_systems(
+            Update,
+            observe_opponents.before(cycle_robots).after(autoref),
+        )
         .add_systems(Update, cycle_robots.before(move_robots).after(autoref))
         .add_systems(Update, move_robots)
-        .add_systems(Update, move_ball.after(move_robots));
+        .add_systems(Update, move_opponents.after(move_robots))
+        .add_systems(Update, move_ball.after(move_opponents))
+        .add_systems(Update, collide_with_opponents.after(move_ball));
 
         if self.use_recording {
             app.add_plugins(crate::recorder::recording_plugin);
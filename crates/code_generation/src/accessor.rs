@@ -350,6 +350,8 @@ mod tests {
             setup_nodes: vec![],
             cycle_nodes: vec![],
             execution_time_warning_threshold: None,
+            parallel_node_threads: None,
+            supervision_policies: Default::default(),
         };
 
         for (path, reference_type, expected_token_stream) in cases {
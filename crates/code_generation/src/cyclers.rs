@@ -6,7 +6,8 @@ use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 use source_analyzer::{
     contexts::Field,
-    cyclers::{Cycler, CyclerKind, Cyclers},
+    cyclers::{Cycler, CyclerKind, Cyclers, DEGRADED_NODES_OUTPUT},
+    manifest::SupervisionPolicy,
     node::Node,
     path::Path,
 };
@@ -29,10 +30,24 @@ pub fn generate_cyclers(cyclers: &Cyclers, mode: CyclerMode) -> TokenStream {
                 },
             }
         });
+        let timestamp_arms = cyclers.instances().map(|(_cycler, instance)| {
+            let instance_name = format_ident!("{}", instance);
+            quote! {
+                RecordingFrame::#instance_name { timestamp, .. } => *timestamp,
+            }
+        });
         quote! {
             pub enum RecordingFrame {
                 #(#recording_frame_variants)*
             }
+
+            impl RecordingFrame {
+                pub fn timestamp(&self) -> std::time::SystemTime {
+                    match self {
+                        #(#timestamp_arms)*
+                    }
+                }
+            }
         }
     } else {
         Default::default()
@@ -100,6 +115,7 @@ fn generate_database_struct(cycler: &Cycler) -> TokenStream {
             pub main_outputs: MainOutputs,
             pub additional_outputs: AdditionalOutputs,
             pub cycle_timings: crate::structs::#cycler_name::CycleTimings,
+            pub supervised_nodes: crate::structs::#cycler_name::SupervisedNodes,
         }
     }
 }
@@ -117,6 +133,11 @@ fn generate_struct(cycler: &Cycler, cyclers: &Cyclers, mode: CyclerMode) -> Toke
         }
     };
     let node_fields = generate_node_fields(cycler);
+    let node_thread_pool_field = cycler.parallel_node_threads.map(|_| {
+        quote! {
+            node_thread_pool: framework::NodeThreadPool,
+        }
+    });
     let recording_fields = if mode == CyclerMode::Run {
         quote! {
             recording_sender: std::sync::mpsc::SyncSender<crate::cyclers::RecordingFrame>,
@@ -134,9 +155,12 @@ fn generate_struct(cycler: &Cycler, cyclers: &Cyclers, mode: CyclerMode) -> Toke
             own_subscribed_outputs_receiver: buffered_watch::Receiver<std::collections::HashSet<String>>,
             parameters_receiver: buffered_watch::Receiver<(std::time::SystemTime, crate::structs::Parameters)>,
             pub cycler_state: crate::structs::#module_name::CyclerState,
+            supervised_nodes: crate::structs::#module_name::SupervisedNodes,
+            last_main_outputs: MainOutputs,
             #realtime_inputs
             #input_output_fields
             #node_fields
+            #node_thread_pool_field
             #recording_fields
         }
     }
@@ -241,6 +265,16 @@ fn generate_new_method(cycler: &Cycler, cyclers: &Cyclers, mode: CyclerMode) ->
     } else {
         Default::default()
     };
+    let node_thread_pool_initializer = cycler
+        .parallel_node_threads
+        .map(|number_of_threads| {
+            quote! {
+                let node_thread_pool = framework::NodeThreadPool::new(&format!("{instance:?}"), #number_of_threads)?;
+            }
+        });
+    let node_thread_pool_identifier = cycler
+        .parallel_node_threads
+        .map(|_| quote! { node_thread_pool, });
     let recording_initializer_fields = if mode == CyclerMode::Run {
         quote! {
             recording_sender,
@@ -265,6 +299,7 @@ fn generate_new_method(cycler: &Cycler, cyclers: &Cyclers, mode: CyclerMode) ->
             let mut cycler_state = crate::structs::#cycler_module_name::CyclerState::default();
             #node_initializers
             drop(parameters_guard);
+            #node_thread_pool_initializer
             Ok(Self {
                 instance,
                 hardware_interface,
@@ -272,8 +307,11 @@ fn generate_new_method(cycler: &Cycler, cyclers: &Cyclers, mode: CyclerMode) ->
                 own_subscribed_outputs_receiver,
                 parameters_receiver,
                 cycler_state,
+                supervised_nodes: Default::default(),
+                last_main_outputs: Default::default(),
                 #input_output_identifiers
                 #(#node_identifiers,)*
+                #node_thread_pool_identifier
                 #recording_initializer_fields
             })
         }
@@ -421,6 +459,9 @@ fn generate_start_method(cycler_kind: CyclerKind) -> TokenStream {
                     #scheduler_tokens
                     while !keep_running.is_cancelled() {
                         if let Err(error) = self.cycle() {
+                            self.hardware_interface.trigger_recording_event(
+                                types::recording_event::RecordingEvent::NodeError,
+                            );
                             keep_running.cancel();
                             return Err(error).wrap_err_with(|| {
                                 format!("failed to execute cycle of cycler `{:?}`", self.instance)
@@ -449,10 +490,21 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers, mode: CyclerMode) -
         .setup_nodes
         .iter()
         .map(|node| generate_node_execution(node, cycler, NodeType::Setup, mode));
-    let cycle_node_executions = cycler
-        .cycle_nodes
-        .iter()
-        .map(|node| generate_node_execution(node, cycler, NodeType::Cycle, mode));
+    let cycle_node_executions: Vec<_> = match cycler.parallel_node_threads {
+        Some(_) => cycler
+            .cycle_node_stages()
+            .into_iter()
+            .map(|stage| match stage.as_slice() {
+                [node] => generate_node_execution(node, cycler, NodeType::Cycle, mode),
+                nodes => generate_parallel_stage_execution(nodes, cycler, mode),
+            })
+            .collect(),
+        None => cycler
+            .cycle_nodes
+            .iter()
+            .map(|node| generate_node_execution(node, cycler, NodeType::Cycle, mode))
+            .collect(),
+    };
     let cross_input_fields = get_cross_input_fields(cycler);
     let cross_inputs = match mode {
         CyclerMode::Run => generate_cross_inputs_recording(cycler, cross_input_fields),
@@ -592,6 +644,7 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers, mode: CyclerMode) -
                 #cross_inputs
                 #(#cycle_node_executions)*
             }
+            own_database.supervised_nodes = self.supervised_nodes.clone();
 
             #after_remaining_nodes
 
@@ -904,12 +957,8 @@ fn generate_execute_node_and_write_main_outputs(
     mode: CyclerMode,
 ) -> TokenStream {
     let are_required_inputs_some = generate_required_input_condition(node, cycler, mode);
-    let node_name = &node.name;
-    let node_member = format_ident!("{}", node.name.to_case(Case::Snake));
-    let node_module = &node.module;
-    let context_initializers = generate_context_initializers(node, cycler, mode);
-    let cycle_error_message = format!("failed to execute cycle of `{}`", node.name);
-    let write_main_outputs = generate_write_main_outputs(node);
+    let execute_node = generate_execute_node(node, cycler, mode);
+    let write_node_results = generate_write_node_results(node, cycler);
     let write_main_outputs_from_defaults = generate_write_main_outputs_from_defaults(node);
 
     quote! {
@@ -917,18 +966,9 @@ fn generate_execute_node_and_write_main_outputs(
             #[allow(clippy::needless_else)]
             if #are_required_inputs_some {
                 let node_cycle_start = std::time::SystemTime::now();
-                let main_outputs = {
-                    let _task = ittapi::Task::begin(&itt_domain, #node_name);
-                    self.#node_member.cycle(
-                        #node_module::CycleContext::new(
-                            #context_initializers
-                        ),
-                    )
-                    .wrap_err(#cycle_error_message)?
-                };
+                let main_outputs = #execute_node;
                 let node_cycle_duration = node_cycle_start.elapsed().expect("time ran backwards");
-                own_database.cycle_timings.#node_member = node_cycle_duration;
-                #write_main_outputs
+                #write_node_results
             }
             else {
                 #write_main_outputs_from_defaults
@@ -937,6 +977,152 @@ fn generate_execute_node_and_write_main_outputs(
     }
 }
 
+fn generate_parallel_stage_execution(
+    nodes: &[&Node],
+    cycler: &Cycler,
+    mode: CyclerMode,
+) -> TokenStream {
+    let result_identifiers: Vec<_> = nodes
+        .iter()
+        .map(|node| format_ident!("{}_result", node.name.to_case(Case::Snake)))
+        .collect();
+    let prepare_node_states = nodes.iter().map(|node| match mode {
+        CyclerMode::Run => generate_record_node_state(node),
+        CyclerMode::Replay => generate_restore_node_state(node),
+    });
+    let mut executions = nodes.iter().zip(result_identifiers.iter()).map(
+        |(node, result_identifier)| {
+            let are_required_inputs_some = generate_required_input_condition(node, cycler, mode);
+            let execute_node = generate_execute_node(node, cycler, mode);
+            quote! {
+                #result_identifier = Some(if #are_required_inputs_some {
+                    let node_cycle_start = std::time::SystemTime::now();
+                    let main_outputs = #execute_node;
+                    Some((main_outputs, node_cycle_start.elapsed().expect("time ran backwards")))
+                } else {
+                    None
+                });
+            }
+        },
+    );
+    let first_execution = executions.next();
+    let writes = nodes
+        .iter()
+        .zip(result_identifiers.iter())
+        .map(|(node, result_identifier)| {
+            let write_node_results = generate_write_node_results(node, cycler);
+            let write_main_outputs_from_defaults = generate_write_main_outputs_from_defaults(node);
+            quote! {
+                match #result_identifier.flatten() {
+                    Some((main_outputs, node_cycle_duration)) => {
+                        #write_node_results
+                    }
+                    None => {
+                        #write_main_outputs_from_defaults
+                    }
+                }
+            }
+        });
+
+    quote! {
+        {
+            #(let mut #result_identifiers = None;)*
+            #(#prepare_node_states)*
+            // the guard itself cannot be shared between threads
+            let own_subscribed_outputs: &std::collections::HashSet<String> = &own_subscribed_outputs;
+            self.node_thread_pool.scope(|scope| {
+                #(scope.spawn(|_| { #executions });)*
+                #first_execution
+            });
+            #(#writes)*
+        }
+    }
+}
+
+fn generate_execute_node(node: &Node, cycler: &Cycler, mode: CyclerMode) -> TokenStream {
+    let node_name = &node.name;
+    let node_member = format_ident!("{}", node.name.to_case(Case::Snake));
+    let node_module = &node.module;
+    let context_initializers = generate_context_initializers(node, cycler, mode);
+    let cycle_error_message = format!("failed to execute cycle of `{}`", node.name);
+
+    quote! {
+        {
+            let _task = ittapi::Task::begin(&itt_domain, #node_name);
+            self.#node_member.cycle(
+                #node_module::CycleContext::new(
+                    #context_initializers
+                ),
+            )
+            .wrap_err(#cycle_error_message)
+        }
+    }
+}
+
+fn generate_write_node_results(node: &Node, cycler: &Cycler) -> TokenStream {
+    let node_member = format_ident!("{}", node.name.to_case(Case::Snake));
+    let write_main_outputs = generate_write_main_outputs(node);
+    let write_main_outputs = match cycler.supervision_policy(node) {
+        SupervisionPolicy::Abort => quote! {
+            let main_outputs = main_outputs?;
+            #write_main_outputs
+        },
+        SupervisionPolicy::ReuseLastOutputs => {
+            let store_last_main_outputs = generate_store_last_main_outputs(node);
+            generate_supervised_write_main_outputs(
+                node,
+                quote! {
+                    #write_main_outputs
+                    #store_last_main_outputs
+                },
+                generate_write_main_outputs_from_last(node),
+            )
+        }
+        SupervisionPolicy::UseDefaults => generate_supervised_write_main_outputs(
+            node,
+            write_main_outputs,
+            generate_write_main_outputs_from_defaults(node),
+        ),
+    };
+
+    quote! {
+        own_database.cycle_timings.#node_member = node_cycle_duration;
+        #write_main_outputs
+    }
+}
+
+fn generate_supervised_write_main_outputs(
+    node: &Node,
+    write_main_outputs: TokenStream,
+    write_fallback_main_outputs: TokenStream,
+) -> TokenStream {
+    let node_name = node.name.to_case(Case::Snake);
+    let node_member = format_ident!("{}", node_name);
+    let degraded_nodes_output = format_ident!("{}", DEGRADED_NODES_OUTPUT);
+    quote! {
+        match main_outputs {
+            Ok(main_outputs) => {
+                self.supervised_nodes.#node_member.record_success();
+                #write_main_outputs
+            }
+            Err(error) => {
+                own_database.main_outputs.#degraded_nodes_output.push(#node_name.to_string());
+                self.hardware_interface.trigger_recording_event(
+                    types::recording_event::RecordingEvent::NodeError,
+                );
+                if self.supervised_nodes.#node_member.record_error(&error) {
+                    log::error!("{error:?}");
+                    self.hardware_interface
+                        .write_to_speakers(types::audio::SpeakerRequest::PlaySound {
+                            sound: types::audio::Sound::Ouch,
+                        });
+                }
+                #write_fallback_main_outputs
+            }
+        }
+    }
+}
+
 fn generate_record_main_outputs(node: &Node) -> TokenStream {
     node.contexts
         .main_outputs
@@ -1422,3 +1608,131 @@ fn generate_write_main_outputs_from_defaults(node: &Node) -> TokenStream {
         })
         .collect()
 }
+
+fn generate_write_main_outputs_from_last(node: &Node) -> TokenStream {
+    node.contexts
+        .main_outputs
+        .iter()
+        .filter_map(|field| match field {
+            Field::MainOutput { name, .. } => Some(quote! {
+                own_database.main_outputs.#name = self.last_main_outputs.#name.clone();
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn generate_store_last_main_outputs(node: &Node) -> TokenStream {
+    node.contexts
+        .main_outputs
+        .iter()
+        .filter_map(|field| match field {
+            Field::MainOutput { name, .. } => Some(quote! {
+                self.last_main_outputs.#name = own_database.main_outputs.#name.clone();
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use source_analyzer::contexts::Contexts;
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn cycler_with_led_status(policy: SupervisionPolicy) -> Cycler {
+        let led_status = Node {
+            name: "LedStatus".to_string(),
+            module: parse_quote!(control::led_status),
+            file_path: "control/src/led_status.rs".into(),
+            contexts: Contexts {
+                creation_context: vec![],
+                cycle_context: vec![],
+                main_outputs: vec![Field::MainOutput {
+                    data_type: parse_quote!(Leds),
+                    name: format_ident!("leds"),
+                }],
+            },
+        };
+        Cycler {
+            name: "Control".to_string(),
+            kind: CyclerKind::RealTime,
+            instances: vec!["Control".to_string()],
+            setup_nodes: vec![],
+            cycle_nodes: vec![led_status],
+            execution_time_warning_threshold: None,
+            parallel_node_threads: None,
+            supervision_policies: BTreeMap::from([("LedStatus".to_string(), policy)]),
+        }
+    }
+
+    fn generate_write_led_status_results(policy: SupervisionPolicy) -> String {
+        let cycler = cycler_with_led_status(policy);
+        generate_write_node_results(&cycler.cycle_nodes[0], &cycler).to_string()
+    }
+
+    #[test]
+    fn errors_of_unsupervised_nodes_are_propagated() {
+        let generated = generate_write_led_status_results(SupervisionPolicy::Abort);
+
+        assert!(generated.contains("let main_outputs = main_outputs ?"));
+        assert!(!generated.contains("record_error"));
+        assert!(!generated.contains("degraded_nodes"));
+    }
+
+    #[test]
+    fn failed_supervised_nodes_write_fallback_outputs_and_are_listed_as_degraded() {
+        let use_defaults = generate_write_led_status_results(SupervisionPolicy::UseDefaults);
+        assert!(
+            use_defaults.contains("self . supervised_nodes . led_status . record_error (& error)")
+        );
+        assert!(use_defaults.contains(
+            "own_database . main_outputs . degraded_nodes . push (\"led_status\" . to_string ())"
+        ));
+        assert!(use_defaults.contains("own_database . main_outputs . leds = Default :: default ()"));
+
+        let reuse_last_outputs =
+            generate_write_led_status_results(SupervisionPolicy::ReuseLastOutputs);
+        assert!(reuse_last_outputs.contains(
+            "self . last_main_outputs . leds = own_database . main_outputs . leds . clone ()"
+        ));
+        assert!(reuse_last_outputs.contains(
+            "own_database . main_outputs . leds = self . last_main_outputs . leds . clone ()"
+        ));
+    }
+
+    #[test]
+    fn main_outputs_of_parallel_stage_are_written_after_all_nodes_finished() {
+        let mut cycler = cycler_with_led_status(SupervisionPolicy::Abort);
+        let mut sonar_filter = cycler.cycle_nodes[0].clone();
+        sonar_filter.name = "SonarFilter".to_string();
+        sonar_filter.module = parse_quote!(control::sonar_filter);
+        sonar_filter.contexts.main_outputs = vec![Field::MainOutput {
+            data_type: parse_quote!(SonarValues),
+            name: format_ident!("sonar_values"),
+        }];
+        cycler.cycle_nodes.push(sonar_filter);
+        cycler.parallel_node_threads = Some(2);
+
+        let stages = cycler.cycle_node_stages();
+        assert_eq!(stages.len(), 1);
+        let generated =
+            generate_parallel_stage_execution(&stages[0], &cycler, CyclerMode::Run).to_string();
+
+        let spawn = generated.find("scope . spawn").unwrap();
+        let leds_write = generated
+            .find("own_database . main_outputs . leds = main_outputs . leds . value")
+            .unwrap();
+        let sonar_values_write = generated
+            .find(
+                "own_database . main_outputs . sonar_values = main_outputs . sonar_values . value",
+            )
+            .unwrap();
+        assert!(spawn < leds_write);
+        assert!(leds_write < sonar_values_write);
+    }
+}
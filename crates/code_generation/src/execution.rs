@@ -1,7 +1,13 @@
+use std::fmt::Write;
+
 use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
-use source_analyzer::cyclers::{CyclerKind, Cyclers};
+use quote::{format_ident, quote, ToTokens};
+use source_analyzer::{
+    contexts::Field,
+    cyclers::{Cycler, CyclerKind, Cyclers},
+    pretty::ToWriterPretty,
+};
 
 use crate::{accessor::ReferenceKind, CyclerMode};
 
@@ -25,6 +31,8 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
             hardware_ids: hula_types::hardware::Ids,
             keep_running: tokio_util::sync::CancellationToken,
             recording_intervals: std::collections::HashMap<String, usize>,
+            recording_compression: framework::Compression,
+            event_recording: Option<framework::EventRecordingParameters>,
         ) -> color_eyre::Result<()>
         {
             use color_eyre::eyre::WrapErr;
@@ -51,7 +59,7 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
             let (parameters_sender, parameters_receiver) =
                 buffered_watch::channel((std::time::SystemTime::now(), initial_parameters));
 
-            let (recording_sender, recording_receiver) = std::sync::mpsc::sync_channel(420);
+            let (recording_sender, recording_receiver) = std::sync::mpsc::sync_channel::<crate::cyclers::RecordingFrame>(420);
             let recording_thread = #recording_thread;
 
             #construct_cyclers
@@ -393,10 +401,12 @@ fn generate_future_queues(cyclers: &Cyclers) -> TokenStream {
 }
 
 fn generate_recording_thread(cyclers: &Cyclers) -> TokenStream {
-    let file_creations = cyclers.instances().map(|(_cycler, instance)| {
+    let file_creations = cyclers.instances().map(|(cycler, instance)| {
         let instance_name_snake_case = format_ident!("{}", instance.to_case(Case::Snake));
         let recording_file_name = format!("{instance}.bincode");
         let error_message_file = format!("failed to create recording file for {instance}");
+        let error_message_header = format!("failed to write recording header for {instance}");
+        let schema_hash = schema_hash(cycler);
 
         quote! {
             let recording_file_path = log_path.as_ref().join(#recording_file_name);
@@ -405,7 +415,17 @@ fn generate_recording_thread(cyclers: &Cyclers) -> TokenStream {
                     .expect("recording file path has no parent directory")
             ).wrap_err("failed to create logs folder")?;
 
-            let mut #instance_name_snake_case = std::io::BufWriter::new(std::fs::File::create(recording_file_path).wrap_err(#error_message_file)?); // TODO: possible optimization: buffer size
+            let mut #instance_name_snake_case = framework::RecordingWriter::new(
+                std::io::BufWriter::new(std::fs::File::create(recording_file_path).wrap_err(#error_message_file)?), // TODO: possible optimization: buffer size
+                &framework::RecordingHeader {
+                    cycler_instance: #instance.to_string(),
+                    git_commit: option_env!("HULK_GIT_COMMIT").map(str::to_string),
+                    body_id: hardware_ids.body_id.clone(),
+                    head_id: hardware_ids.head_id.clone(),
+                    schema_hash: #schema_hash,
+                    compression: recording_compression,
+                },
+            ).wrap_err(#error_message_header)?;
         }
     });
     let frame_writes = cyclers.instances().map(|(_cycler, instance)| {
@@ -414,25 +434,28 @@ fn generate_recording_thread(cyclers: &Cyclers) -> TokenStream {
         let error_message = format!("failed to write into recording file for {instance}");
         quote! {
             crate::cyclers::RecordingFrame::#instance_name { timestamp, duration, data } => {
-                let mut recording_header = Vec::new();
-                bincode::serialize_into(&mut recording_header, &timestamp).wrap_err("failed to serialize timestamp")?;
-                bincode::serialize_into(&mut recording_header, &duration).wrap_err("failed to serialize duration")?;
-                bincode::serialize_into(&mut recording_header, &data.len()).wrap_err("failed to serialize data length")?;
-                #instance_name_snake_case.write_all(recording_header.as_slice()).wrap_err(#error_message)?;
-                #instance_name_snake_case.write_all(data.as_slice()).wrap_err(#error_message)?;
+                #instance_name_snake_case.write_frame(timestamp, duration, &data).wrap_err(#error_message)?;
             },
         }
     });
+    let file_finishes = cyclers.instances().map(|(_cycler, instance)| {
+        let instance_name_snake_case = format_ident!("{}", instance.to_case(Case::Snake));
+        let error_message = format!("failed to finish recording file for {instance}");
+        quote! {
+            #instance_name_snake_case.finish().wrap_err(#error_message)?;
+        }
+    });
 
     quote! {
         {
             let keep_running = keep_running.clone();
             let mut parameters_receiver = parameters_receiver.clone();
+            let hardware_ids = hardware_ids.clone();
+            let hardware_interface = hardware_interface.clone();
             std::thread::Builder::new()
                 .name("Recording".to_string())
                 .spawn(move || -> color_eyre::Result<()> {
                     let result = (|| {
-                        use std::io::Write;
                         {
                             let (_, parameters) = &*parameters_receiver.borrow_and_mark_as_seen();
                             std::fs::write(
@@ -441,11 +464,37 @@ fn generate_recording_thread(cyclers: &Cyclers) -> TokenStream {
                             )?;
                         }
                         #(#file_creations)*
-                        for recording_frame in recording_receiver {
+                        let mut write_frame = |recording_frame| -> color_eyre::Result<()> {
                             match recording_frame {
                                 #(#frame_writes)*
                             }
+                            Ok(())
+                        };
+                        let mut event_recording_buffer = event_recording.map(framework::EventRecordingBuffer::new);
+                        for recording_frame in recording_receiver {
+                            let recording_frames = match &mut event_recording_buffer {
+                                Some(buffer) => {
+                                    let events = hardware_interface.take_recording_events();
+                                    for event in &events {
+                                        log::info!("recording around {event:?}");
+                                    }
+                                    buffer.push(recording_frame.timestamp(), recording_frame, !events.is_empty())
+                                }
+                                None => vec![recording_frame],
+                            };
+                            for recording_frame in recording_frames {
+                                write_frame(recording_frame)?;
+                            }
+                        }
+                        // events may be triggered while shutting down, e.g. by failing nodes
+                        if let Some(buffer) = &mut event_recording_buffer {
+                            if !hardware_interface.take_recording_events().is_empty() {
+                                for recording_frame in buffer.flush() {
+                                    write_frame(recording_frame)?;
+                                }
+                            }
                         }
+                        #(#file_finishes)*
                         Ok(())
                     })();
 
@@ -479,10 +528,20 @@ fn generate_cycler_constructors(cyclers: &Cyclers, mode: CyclerMode) -> TokenStr
         };
         let recording_index = if mode == CyclerMode::Replay {
             let recording_file_name = format!("{instance}.bincode");
+            let schema_hash = schema_hash(cycler);
             quote! {
                 let #cycler_index_identifier = framework::RecordingIndex::read_from(
                     recordings_file_path.as_ref().join(#recording_file_name)
                 ).wrap_err("failed to read recording index")?;
+                if let Some(header) = #cycler_index_identifier.header() {
+                    if header.schema_hash != #schema_hash {
+                        log::warn!(
+                            "{} was recorded with different nodes (commit {:?}), replaying it will probably fail",
+                            #recording_file_name,
+                            header.git_commit,
+                        );
+                    }
+                }
             }
         } else {
             Default::default()
@@ -651,3 +710,48 @@ fn generate_cycler_replays(cyclers: &Cyclers) -> TokenStream {
         })
         .collect()
 }
+
+/// Hashes the nodes and their contexts, which determine the layout of recorded frames.
+///
+/// The hash is stored in recording headers, therefore it is computed over a textual description of
+/// the contexts with a hash function that does not change between Rust releases.
+fn schema_hash(cycler: &Cycler) -> u64 {
+    let mut schema = String::new();
+    for node in cycler.setup_nodes.iter().chain(&cycler.cycle_nodes) {
+        writeln!(schema, "{}", node.name).expect("failed to write to string");
+        for field in node
+            .contexts
+            .creation_context
+            .iter()
+            .chain(&node.contexts.cycle_context)
+            .chain(&node.contexts.main_outputs)
+        {
+            field
+                .to_writer_pretty(&mut schema)
+                .expect("failed to write to string");
+            if let Some(data_type) = field_data_type(field) {
+                write!(schema, " {}", data_type.to_token_stream())
+                    .expect("failed to write to string");
+            }
+            schema.push('\n');
+        }
+    }
+    let hash = blake3::hash(schema.as_bytes());
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&hash.as_bytes()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn field_data_type(field: &Field) -> Option<&syn::Type> {
+    match field {
+        Field::AdditionalOutput { data_type, .. }
+        | Field::CyclerState { data_type, .. }
+        | Field::HistoricInput { data_type, .. }
+        | Field::Input { data_type, .. }
+        | Field::MainOutput { data_type, .. }
+        | Field::Parameter { data_type, .. }
+        | Field::PerceptionInput { data_type, .. }
+        | Field::RequiredInput { data_type, .. } => Some(data_type),
+        Field::HardwareInterface { .. } => None,
+    }
+}
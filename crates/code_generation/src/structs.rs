@@ -57,6 +57,11 @@ pub fn generate_structs(structs: &Structs) -> TokenStream {
                 format_ident!("CycleTimings"),
                 &derives,
             );
+            let supervised_nodes = hierarchy_to_token_stream(
+                &cycler_structs.supervised_nodes,
+                format_ident!("SupervisedNodes"),
+                &derives,
+            );
 
             quote! {
                 pub mod #cycler_module_identifier {
@@ -64,6 +69,7 @@ pub fn generate_structs(structs: &Structs) -> TokenStream {
                     #additional_outputs
                     #cycler_state
                     #cycle_times
+                    #supervised_nodes
                 }
             }
         });
@@ -29,7 +29,7 @@ use tokio_tungstenite::{
 
 use crate::{
     client::protocol::Protocol,
-    messages::{Path, Paths, TextOrBinary},
+    messages::{Path, Paths, SubscriptionRate, TextOrBinary, UpdateMode},
     send_or_log::SendOrLogExt,
 };
 
@@ -53,6 +53,9 @@ pub struct SubscriptionHandle<T> {
 pub type JsonSubscriptionHandle = SubscriptionHandle<Value>;
 pub type BinarySubscriptionHandle = SubscriptionHandle<Vec<u8>>;
 
+/// Subscriptions of the same path with different rates or update modes are served separately
+type SubscriptionKey = (Path, SubscriptionRate, UpdateMode);
+
 #[derive(Debug)]
 enum Event {
     Connect,
@@ -68,10 +71,13 @@ enum Event {
     },
     SubscribeText {
         path: Path,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
         return_sender: oneshot::Sender<JsonSubscriptionHandle>,
     },
     SubscribeBinary {
         path: Path,
+        rate: SubscriptionRate,
         return_sender: oneshot::Sender<BinarySubscriptionHandle>,
     },
     Write {
@@ -155,11 +161,18 @@ impl ClientHandle {
         return_receiver.await.unwrap()
     }
 
-    pub async fn subscribe_text(&self, path: impl Into<Path>) -> JsonSubscriptionHandle {
+    pub async fn subscribe_text(
+        &self,
+        path: impl Into<Path>,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
+    ) -> JsonSubscriptionHandle {
         let (return_sender, return_receiver) = oneshot::channel();
         self.sender
             .send(Event::SubscribeText {
                 path: path.into(),
+                rate,
+                update_mode,
                 return_sender,
             })
             .await
@@ -167,11 +180,16 @@ impl ClientHandle {
         return_receiver.await.unwrap()
     }
 
-    pub async fn subscribe_binary(&self, path: impl Into<Path>) -> BinarySubscriptionHandle {
+    pub async fn subscribe_binary(
+        &self,
+        path: impl Into<Path>,
+        rate: SubscriptionRate,
+    ) -> BinarySubscriptionHandle {
         let (return_sender, return_receiver) = oneshot::channel();
         self.sender
             .send(Event::SubscribeBinary {
                 path: path.into(),
+                rate,
                 return_sender,
             })
             .await
@@ -223,10 +241,10 @@ pub struct Client {
     connection_state: State,
     peer_address: String,
     paths_sender: watch::Sender<PathsEvent>,
-    text_subscriptions: HashMap<Path, Subscription<Value>>,
-    text_unsubscriptions: JoinSet<Path>,
-    binary_subscriptions: HashMap<Path, Subscription<Vec<u8>>>,
-    binary_unsubscriptions: JoinSet<Path>,
+    text_subscriptions: HashMap<SubscriptionKey, Subscription<Value>>,
+    text_unsubscriptions: JoinSet<SubscriptionKey>,
+    binary_subscriptions: HashMap<SubscriptionKey, Subscription<Vec<u8>>>,
+    binary_unsubscriptions: JoinSet<SubscriptionKey>,
 }
 
 impl Client {
@@ -267,13 +285,13 @@ impl Client {
                                 None => break,
                             }
                         }
-                        Some(path) = self.text_unsubscriptions.join_next() => {
-                            let path = path.unwrap();
-                            self.text_subscriptions.remove(&path);
+                        Some(key) = self.text_unsubscriptions.join_next() => {
+                            let key = key.unwrap();
+                            self.text_subscriptions.remove(&key);
                         }
-                        Some(path) = self.binary_unsubscriptions.join_next() => {
-                            let path = path.unwrap();
-                            self.binary_subscriptions.remove(&path);
+                        Some(key) = self.binary_unsubscriptions.join_next() => {
+                            let key = key.unwrap();
+                            self.binary_subscriptions.remove(&key);
                         }
                     }
                 }
@@ -291,13 +309,13 @@ impl Client {
                             let socket = maybe_socket.unwrap();
                             self.handle_successful_connection(socket);
                         }
-                        Some(path) = self.text_unsubscriptions.join_next() => {
-                            let path = path.unwrap();
-                            self.text_subscriptions.remove(&path);
+                        Some(key) = self.text_unsubscriptions.join_next() => {
+                            let key = key.unwrap();
+                            self.text_subscriptions.remove(&key);
                         }
-                        Some(path) = self.binary_unsubscriptions.join_next() => {
-                            let path = path.unwrap();
-                            self.binary_subscriptions.remove(&path);
+                        Some(key) = self.binary_unsubscriptions.join_next() => {
+                            let key = key.unwrap();
+                            self.binary_subscriptions.remove(&key);
                         }
                     }
                 }
@@ -317,13 +335,13 @@ impl Client {
                                 ongoing_connection: spawn(try_connect(self.peer_address.clone()))
                             };
                         }
-                        Some(path) = self.text_unsubscriptions.join_next() => {
-                            let path = path.unwrap();
-                            self.text_subscriptions.remove(&path);
+                        Some(key) = self.text_unsubscriptions.join_next() => {
+                            let key = key.unwrap();
+                            self.text_subscriptions.remove(&key);
                         }
-                        Some(path) = self.binary_unsubscriptions.join_next() => {
-                            let path = path.unwrap();
-                            self.binary_subscriptions.remove(&path);
+                        Some(key) = self.binary_unsubscriptions.join_next() => {
+                            let key = key.unwrap();
+                            self.binary_subscriptions.remove(&key);
                         }
                     }
                 }
@@ -414,16 +432,19 @@ impl Client {
             }
             Event::SubscribeText {
                 path,
+                rate,
+                update_mode,
                 return_sender,
             } => {
-                let handle = self.subscribe_text(path).await;
+                let handle = self.subscribe_text(path, rate, update_mode).await;
                 let _ = return_sender.send(handle);
             }
             Event::SubscribeBinary {
                 path,
+                rate,
                 return_sender,
             } => {
-                let handle = self.subscribe_binary(path).await;
+                let handle = self.subscribe_binary(path, rate).await;
                 let _ = return_sender.send(handle);
             }
             Event::Write {
@@ -476,13 +497,16 @@ impl Client {
             });
         }
 
-        for (path, subscription) in &mut self.text_subscriptions {
+        for ((path, rate, update_mode), subscription) in &mut self.text_subscriptions {
             let handle = handle.clone();
             let path = path.clone();
+            let rate = *rate;
+            let update_mode = *update_mode;
             let update_sender = subscription.sender.clone();
             let (unsubscribe_sender, unsubscribe_receiver) = oneshot::channel();
             spawn(async move {
-                if let Ok(protocol_receiver) = handle.subscribe_text(path).await {
+                if let Ok(protocol_receiver) = handle.subscribe_text(path, rate, update_mode).await
+                {
                     spawn(serve_subscription(
                         protocol_receiver,
                         update_sender,
@@ -493,13 +517,14 @@ impl Client {
             subscription.protocol_unsubscribe = Some(unsubscribe_receiver);
         }
 
-        for (path, subscription) in &mut self.binary_subscriptions {
+        for ((path, rate, _), subscription) in &mut self.binary_subscriptions {
             let handle = handle.clone();
             let path = path.clone();
+            let rate = *rate;
             let update_sender = subscription.sender.clone();
             let (unsubscribe_sender, unsubscribe_receiver) = oneshot::channel();
             spawn(async move {
-                if let Ok(protocol_receiver) = handle.subscribe_binary(path).await {
+                if let Ok(protocol_receiver) = handle.subscribe_binary(path, rate).await {
                     spawn(serve_subscription(
                         protocol_receiver,
                         update_sender,
@@ -511,8 +536,16 @@ impl Client {
         }
     }
 
-    async fn subscribe_text(&mut self, path: Path) -> SubscriptionHandle<Value> {
-        match self.text_subscriptions.entry(path.clone()) {
+    async fn subscribe_text(
+        &mut self,
+        path: Path,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
+    ) -> SubscriptionHandle<Value> {
+        match self
+            .text_subscriptions
+            .entry((path.clone(), rate, update_mode))
+        {
             Occupied(mut entry) => {
                 let subscription = entry.get();
                 match subscription.drop.upgrade() {
@@ -546,7 +579,7 @@ impl Client {
                         } = &self.connection_state
                         {
                             protocol_handle
-                                .subscribe_text(path.clone())
+                                .subscribe_text(path.clone(), rate, update_mode)
                                 .await
                                 .map_or_else(
                                     |_| None,
@@ -569,8 +602,10 @@ impl Client {
                             drop: drop_sender.downgrade(),
                             protocol_unsubscribe: unsubscribe_receiver,
                         };
-                        self.text_unsubscriptions
-                            .spawn(wait_for_unsubscription(drop_receiver, path));
+                        self.text_unsubscriptions.spawn(wait_for_unsubscription(
+                            drop_receiver,
+                            (path, rate, update_mode),
+                        ));
                         entry.insert(subscription);
                         SubscriptionHandle {
                             receiver: update_receiver,
@@ -587,7 +622,7 @@ impl Client {
                 } = &self.connection_state
                 {
                     protocol_handle
-                        .subscribe_text(path.clone())
+                        .subscribe_text(path.clone(), rate, update_mode)
                         .await
                         .map_or_else(
                             |_| None,
@@ -609,8 +644,10 @@ impl Client {
                     drop: drop_sender.downgrade(),
                     protocol_unsubscribe: unsubscribe_receiver,
                 };
-                self.text_unsubscriptions
-                    .spawn(wait_for_unsubscription(drop_receiver, path));
+                self.text_unsubscriptions.spawn(wait_for_unsubscription(
+                    drop_receiver,
+                    (path, rate, update_mode),
+                ));
                 entry.insert(subscription);
                 SubscriptionHandle {
                     receiver: update_receiver,
@@ -620,8 +657,15 @@ impl Client {
         }
     }
 
-    async fn subscribe_binary(&mut self, path: Path) -> SubscriptionHandle<Vec<u8>> {
-        match self.binary_subscriptions.entry(path.clone()) {
+    async fn subscribe_binary(
+        &mut self,
+        path: Path,
+        rate: SubscriptionRate,
+    ) -> SubscriptionHandle<Vec<u8>> {
+        match self
+            .binary_subscriptions
+            .entry((path.clone(), rate, UpdateMode::Full))
+        {
             Occupied(mut entry) => {
                 let subscription = entry.get();
                 match subscription.drop.upgrade() {
@@ -655,7 +699,7 @@ impl Client {
                         } = &self.connection_state
                         {
                             protocol_handle
-                                .subscribe_binary(path.clone())
+                                .subscribe_binary(path.clone(), rate)
                                 .await
                                 .map_or_else(
                                     |_| None,
@@ -678,8 +722,10 @@ impl Client {
                             drop: drop_sender.downgrade(),
                             protocol_unsubscribe: unsubscribe_receiver,
                         };
-                        self.binary_unsubscriptions
-                            .spawn(wait_for_unsubscription(drop_receiver, path));
+                        self.binary_unsubscriptions.spawn(wait_for_unsubscription(
+                            drop_receiver,
+                            (path, rate, UpdateMode::Full),
+                        ));
                         entry.insert(subscription);
                         SubscriptionHandle {
                             receiver: update_receiver,
@@ -696,7 +742,7 @@ impl Client {
                 } = &self.connection_state
                 {
                     protocol_handle
-                        .subscribe_binary(path.clone())
+                        .subscribe_binary(path.clone(), rate)
                         .await
                         .map_or_else(
                             |_| None,
@@ -718,8 +764,10 @@ impl Client {
                     drop: drop_sender.downgrade(),
                     protocol_unsubscribe: unsubscribe_receiver,
                 };
-                self.binary_unsubscriptions
-                    .spawn(wait_for_unsubscription(drop_receiver, path));
+                self.binary_unsubscriptions.spawn(wait_for_unsubscription(
+                    drop_receiver,
+                    (path, rate, UpdateMode::Full),
+                ));
                 entry.insert(subscription);
                 SubscriptionHandle {
                     receiver: update_receiver,
@@ -769,7 +817,10 @@ async fn serve_subscription<T>(
     }
 }
 
-async fn wait_for_unsubscription(mut drop_receiver: mpsc::Receiver<()>, path: Path) -> Path {
+async fn wait_for_unsubscription(
+    mut drop_receiver: mpsc::Receiver<()>,
+    key: SubscriptionKey,
+) -> SubscriptionKey {
     while drop_receiver.recv().await.is_some() {}
-    path
+    key
 }
@@ -20,8 +20,10 @@ use tokio_tungstenite::{
 };
 
 use crate::{
+    json_patch,
     messages::{
-        Format, Path, Paths, Request, RequestId, RequestKind, Response, ResponseKind, TextOrBinary,
+        Format, Path, Paths, Request, RequestId, RequestKind, Response, ResponseKind,
+        SubscriptionRate, TextOrBinary, UpdateMode,
     },
     send_or_log::SendOrLogExt,
 };
@@ -37,6 +39,10 @@ pub enum Error {
         expected: &'static str,
         response: String,
     },
+    #[error("received delta update without previous value")]
+    MissingDeltaBase,
+    #[error("failed to apply delta update")]
+    Delta(#[source] json_patch::Error),
 }
 
 #[derive(Debug)]
@@ -70,6 +76,44 @@ impl From<Response> for SubscriptionEvent<Value> {
     }
 }
 
+/// Reconstructs the values of a text subscription with delta updates
+#[derive(Default)]
+struct DeltaDecoder {
+    value: Option<Value>,
+}
+
+impl DeltaDecoder {
+    fn decode(&mut self, response: Response) -> SubscriptionEvent<Value> {
+        let Ok(ResponseKind::Delta { timestamp, patch }) = response.kind else {
+            let event: SubscriptionEvent<Value> = response.into();
+            self.value = match &event {
+                SubscriptionEvent::Successful { value, .. }
+                | SubscriptionEvent::Update { value, .. } => Some(value.clone()),
+                SubscriptionEvent::Failure { .. } => None,
+            };
+            return event;
+        };
+        let Some(value) = &mut self.value else {
+            return SubscriptionEvent::Failure {
+                error: Error::MissingDeltaBase,
+            };
+        };
+        match json_patch::apply(value, &patch) {
+            Ok(()) => SubscriptionEvent::Update {
+                timestamp,
+                value: value.clone(),
+            },
+            Err(error) => {
+                // wait for the next keyframe
+                self.value = None;
+                SubscriptionEvent::Failure {
+                    error: Error::Delta(error),
+                }
+            }
+        }
+    }
+}
+
 impl From<Response> for SubscriptionEvent<Vec<u8>> {
     fn from(response: Response) -> Self {
         match response.kind {
@@ -108,10 +152,13 @@ enum Event {
     },
     SubscribeText {
         path: Path,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
         return_sender: oneshot::Sender<mpsc::Receiver<SubscriptionEvent<Value>>>,
     },
     SubscribeBinary {
         path: Path,
+        rate: SubscriptionRate,
         return_sender: oneshot::Sender<mpsc::Receiver<SubscriptionEvent<Vec<u8>>>>,
     },
     Write {
@@ -160,12 +207,16 @@ impl ProtocolHandle {
     pub async fn subscribe_text(
         &self,
         path: Path,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
     ) -> Result<mpsc::Receiver<SubscriptionEvent<Value>>, Error> {
         let (return_sender, return_receiver) = oneshot::channel();
         let _ = self
             .sender
             .send(Event::SubscribeText {
                 path,
+                rate,
+                update_mode,
                 return_sender,
             })
             .await;
@@ -175,12 +226,14 @@ impl ProtocolHandle {
     pub async fn subscribe_binary(
         &self,
         path: Path,
+        rate: SubscriptionRate,
     ) -> Result<mpsc::Receiver<SubscriptionEvent<Vec<u8>>>, Error> {
         let (return_sender, return_receiver) = oneshot::channel();
         let _ = self
             .sender
             .send(Event::SubscribeBinary {
                 path,
+                rate,
                 return_sender,
             })
             .await;
@@ -346,16 +399,19 @@ impl Protocol {
             }
             Event::SubscribeText {
                 path,
+                rate,
+                update_mode,
                 return_sender,
             } => {
-                let update_receiver = self.subscribe_text(path).await?;
+                let update_receiver = self.subscribe_text(path, rate, update_mode).await?;
                 let _ = return_sender.send(update_receiver);
             }
             Event::SubscribeBinary {
                 path,
+                rate,
                 return_sender,
             } => {
-                let update_receiver = self.subscribe_binary(path).await?;
+                let update_receiver = self.subscribe_binary(path, rate).await?;
                 let _ = return_sender.send(update_receiver);
             }
             Event::Write {
@@ -422,13 +478,20 @@ impl Protocol {
         &mut self,
         path: Path,
         format: Format,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
     ) -> Result<(mpsc::Receiver<Response>, RequestId), ClosingError> {
         let (response_sender, response_receiver) = mpsc::channel(1);
         let id = self.next_request_id;
         self.next_request_id += 1;
         let request = Request {
             id,
-            kind: RequestKind::Subscribe { path, format },
+            kind: RequestKind::Subscribe {
+                path,
+                format,
+                rate,
+                update_mode,
+            },
         };
         let message = Message::Text(
             serde_json::to_string(&request)
@@ -443,22 +506,48 @@ impl Protocol {
     async fn subscribe_text(
         &mut self,
         path: Path,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
     ) -> Result<mpsc::Receiver<SubscriptionEvent<Value>>, ClosingError> {
-        let (response_receiver, id) = self.subscribe(path, Format::Text).await?;
+        let (response_receiver, id) = self
+            .subscribe(path, Format::Text, rate, update_mode)
+            .await?;
         let (update_sender, update_receiver) = mpsc::channel(1);
-        self.subscription_tasks
-            .spawn(serve_subscription(response_receiver, update_sender, id));
+        match update_mode {
+            UpdateMode::Full => self.subscription_tasks.spawn(serve_subscription(
+                response_receiver,
+                update_sender,
+                SubscriptionEvent::from,
+                id,
+            )),
+            UpdateMode::Delta { .. } => {
+                let mut delta_decoder = DeltaDecoder::default();
+                self.subscription_tasks.spawn(serve_subscription(
+                    response_receiver,
+                    update_sender,
+                    move |response| delta_decoder.decode(response),
+                    id,
+                ))
+            }
+        };
         Ok(update_receiver)
     }
 
     async fn subscribe_binary(
         &mut self,
         path: Path,
+        rate: SubscriptionRate,
     ) -> Result<mpsc::Receiver<SubscriptionEvent<Vec<u8>>>, ClosingError> {
-        let (response_receiver, id) = self.subscribe(path, Format::Binary).await?;
+        let (response_receiver, id) = self
+            .subscribe(path, Format::Binary, rate, UpdateMode::Full)
+            .await?;
         let (update_sender, update_receiver) = mpsc::channel(1);
-        self.subscription_tasks
-            .spawn(serve_subscription(response_receiver, update_sender, id));
+        self.subscription_tasks.spawn(serve_subscription(
+            response_receiver,
+            update_sender,
+            SubscriptionEvent::from,
+            id,
+        ));
         Ok(update_receiver)
     }
 
@@ -546,9 +635,10 @@ async fn wait_for_read_binary_response(
     };
 }
 
-async fn serve_subscription(
+async fn serve_subscription<T>(
     mut response_receiver: mpsc::Receiver<Response>,
-    update_sender: mpsc::Sender<impl From<Response>>,
+    update_sender: mpsc::Sender<T>,
+    mut decode: impl FnMut(Response) -> T,
     id: RequestId,
 ) -> RequestId {
     loop {
@@ -556,7 +646,7 @@ async fn serve_subscription(
             maybe_response = response_receiver.recv() => {
                 match maybe_response {
                     Some(response) => {
-                        let _ = update_sender.send(response.into()).await;
+                        let _ = update_sender.send(decode(response)).await;
                     },
                     None => break,
                 }
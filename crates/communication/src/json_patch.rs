@@ -0,0 +1,238 @@
+//! Differences between JSON values in the style of JSON Patch (RFC 6902)
+//!
+//! Only the `add`, `remove`, and `replace` operations are used. Paths are JSON Pointers
+//! (RFC 6901), e.g. `/pose_hypotheses/0/score`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+pub type Patch = Vec<PatchOperation>;
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    #[error("invalid pointer `{0}`")]
+    InvalidPointer(String),
+    #[error("no value at `{0}`")]
+    NoSuchValue(String),
+}
+
+/// Computes the operations turning `old` into `new`
+pub fn diff(old: &Value, new: &Value) -> Patch {
+    let mut patch = Vec::new();
+    diff_at(old, new, &mut String::new(), &mut patch);
+    patch
+}
+
+fn diff_at(old: &Value, new: &Value, path: &mut String, patch: &mut Patch) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                patch.push(PatchOperation::Remove {
+                    path: format!("{path}/{}", escape(key)),
+                });
+            }
+            for (key, new_value) in new {
+                let length = path.len();
+                path.push('/');
+                path.push_str(&escape(key));
+                match old.get(key) {
+                    Some(old_value) => diff_at(old_value, new_value, path, patch),
+                    None => patch.push(PatchOperation::Add {
+                        path: path.clone(),
+                        value: new_value.clone(),
+                    }),
+                }
+                path.truncate(length);
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for (index, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+                let length = path.len();
+                path.push_str(&format!("/{index}"));
+                diff_at(old_value, new_value, path, patch);
+                path.truncate(length);
+            }
+            // removing from the back keeps the indices of the remaining elements valid
+            for index in (new.len()..old.len()).rev() {
+                patch.push(PatchOperation::Remove {
+                    path: format!("{path}/{index}"),
+                });
+            }
+            for (index, value) in new.iter().enumerate().skip(old.len()) {
+                patch.push(PatchOperation::Add {
+                    path: format!("{path}/{index}"),
+                    value: value.clone(),
+                });
+            }
+        }
+        (old, new) if old != new => patch.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Applies the operations of `patch` in order, `value` may be partially patched on error
+pub fn apply(value: &mut Value, patch: &[PatchOperation]) -> Result<(), Error> {
+    for operation in patch {
+        match operation {
+            PatchOperation::Add { path, value: new } => {
+                let Some((parent, key)) = split_parent(path)? else {
+                    *value = new.clone();
+                    continue;
+                };
+                match pointer_mut(value, path, &parent)? {
+                    Value::Object(object) => {
+                        object.insert(key, new.clone());
+                    }
+                    Value::Array(array) => {
+                        let index = match key.as_str() {
+                            "-" => array.len(),
+                            index => parse_index(index, path)?,
+                        };
+                        if index > array.len() {
+                            return Err(Error::NoSuchValue(path.clone()));
+                        }
+                        array.insert(index, new.clone());
+                    }
+                    _ => return Err(Error::NoSuchValue(path.clone())),
+                }
+            }
+            PatchOperation::Remove { path } => {
+                let Some((parent, key)) = split_parent(path)? else {
+                    return Err(Error::InvalidPointer(path.clone()));
+                };
+                let removed = match pointer_mut(value, path, &parent)? {
+                    Value::Object(object) => object.remove(&key),
+                    Value::Array(array) => {
+                        let index = parse_index(&key, path)?;
+                        (index < array.len()).then(|| array.remove(index))
+                    }
+                    _ => None,
+                };
+                if removed.is_none() {
+                    return Err(Error::NoSuchValue(path.clone()));
+                }
+            }
+            PatchOperation::Replace { path, value: new } => {
+                let target = value
+                    .pointer_mut(path)
+                    .ok_or_else(|| Error::NoSuchValue(path.clone()))?;
+                *target = new.clone();
+            }
+        }
+    }
+    Ok(())
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Splits a pointer into the pointer of the parent and the unescaped last reference token,
+/// `None` if the pointer references the whole document
+fn split_parent(path: &str) -> Result<Option<(String, String)>, Error> {
+    if path.is_empty() {
+        return Ok(None);
+    }
+    if !path.starts_with('/') {
+        return Err(Error::InvalidPointer(path.to_string()));
+    }
+    let (parent, key) = path.rsplit_once('/').expect("path starts with a slash");
+    let key = key.replace("~1", "/").replace("~0", "~");
+    Ok(Some((parent.to_string(), key)))
+}
+
+fn pointer_mut<'value>(
+    value: &'value mut Value,
+    path: &str,
+    parent: &str,
+) -> Result<&'value mut Value, Error> {
+    value
+        .pointer_mut(parent)
+        .ok_or_else(|| Error::NoSuchValue(path.to_string()))
+}
+
+fn parse_index(index: &str, path: &str) -> Result<usize, Error> {
+    index
+        .parse()
+        .map_err(|_| Error::InvalidPointer(path.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn assert_round_trip(old: Value, new: Value) {
+        let patch = diff(&old, &new);
+        let mut patched = old;
+        apply(&mut patched, &patch).unwrap();
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn equal_values_have_empty_patch() {
+        let value = json!({"pose": [1.0, 2.0], "score": 0.5});
+
+        assert!(diff(&value, &value).is_empty());
+    }
+
+    #[test]
+    fn changed_fields_are_replaced() {
+        let old = json!({"score": 0.5, "id": 3});
+        let new = json!({"score": 0.7, "id": 3});
+
+        assert_eq!(
+            diff(&old, &new),
+            [PatchOperation::Replace {
+                path: "/score".to_string(),
+                value: json!(0.7),
+            }]
+        );
+    }
+
+    #[test]
+    fn objects_round_trip() {
+        assert_round_trip(
+            json!({"a": 1, "b/c": {"d~e": true}, "removed": null}),
+            json!({"a": 2, "b/c": {"d~e": false, "added": [1]}}),
+        );
+    }
+
+    #[test]
+    fn arrays_round_trip() {
+        assert_round_trip(json!([1, 2, 3, 4]), json!([1, 5]));
+        assert_round_trip(json!([{"x": 1}]), json!([{"x": 2}, {"x": 3}, []]));
+    }
+
+    #[test]
+    fn different_types_replace_the_whole_value() {
+        assert_round_trip(json!({"a": 1}), json!([1]));
+        assert_round_trip(json!(null), json!(42));
+    }
+
+    #[test]
+    fn patch_of_missing_value_fails() {
+        let mut value = json!({"a": 1});
+        let patch = [PatchOperation::Remove {
+            path: "/b".to_string(),
+        }];
+
+        assert_eq!(
+            apply(&mut value, &patch),
+            Err(Error::NoSuchValue("/b".to_string()))
+        );
+    }
+}
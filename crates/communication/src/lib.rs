@@ -9,6 +9,7 @@
 //! Both the server and client are build on `tokio` for asynchronous I/O.
 
 pub mod client;
+pub mod json_patch;
 pub mod messages;
 mod send_or_log;
 pub mod server;
@@ -1,8 +1,14 @@
-use std::{collections::BTreeMap, time::SystemTime};
+use std::{
+    collections::BTreeMap,
+    num::NonZeroUsize,
+    time::{Duration, SystemTime},
+};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::json_patch::Patch;
+
 #[derive(Default, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
 pub struct Entry {
@@ -39,10 +45,52 @@ pub struct Request {
 #[non_exhaustive]
 pub enum RequestKind {
     GetPaths,
-    Read { path: Path, format: Format },
-    Subscribe { path: Path, format: Format },
-    Unsubscribe { id: RequestId },
-    Write { path: Path, value: TextOrBinary },
+    Read {
+        path: Path,
+        format: Format,
+    },
+    Subscribe {
+        path: Path,
+        format: Format,
+        #[serde(default)]
+        rate: SubscriptionRate,
+        #[serde(default)]
+        update_mode: UpdateMode,
+    },
+    Unsubscribe {
+        id: RequestId,
+    },
+    Write {
+        path: Path,
+        value: TextOrBinary,
+    },
+}
+
+/// Limits how often the server sends updates of a subscription, the default sends every update
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct SubscriptionRate {
+    /// Updates arriving earlier than this after the last sent update are skipped
+    pub minimum_interval: Option<Duration>,
+    /// Only every n-th update of the source is sent
+    pub decimation: Option<NonZeroUsize>,
+    /// Skip updates while previous updates have not been sent to the client yet instead of queueing them
+    pub latest_only: bool,
+}
+
+/// How the updates of a subscription transmit the value
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum UpdateMode {
+    /// Every update contains the whole value
+    #[default]
+    Full,
+    /// Updates contain a patch against the previous update, only supported for text subscriptions.
+    /// The server keeps a copy of the last sent value per subscription and diffs every update
+    /// against it, which trades CPU time and memory on the robot for bandwidth.
+    Delta {
+        /// Every n-th update contains the whole value to recover from lost updates
+        keyframe_interval: NonZeroUsize,
+    },
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -72,6 +120,10 @@ pub enum ResponseKind {
         timestamp: SystemTime,
         value: TextOrBinary,
     },
+    Delta {
+        timestamp: SystemTime,
+        patch: Patch,
+    },
     Unsubscribe,
     Write,
 }
@@ -1,4 +1,11 @@
-use std::{collections::HashMap, time::SystemTime};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::SystemTime,
+};
 
 use color_eyre::eyre::{eyre, Report};
 use futures_util::{SinkExt, StreamExt};
@@ -65,6 +72,8 @@ enum Event {
 pub struct ConnectionHandle {
     event_sender: mpsc::Sender<Event>,
     id: ClientId,
+    /// Number of updates which are queued or being sent over the socket
+    queued_updates: Arc<AtomicUsize>,
 }
 
 impl ConnectionHandle {
@@ -72,8 +81,27 @@ impl ConnectionHandle {
         self.id
     }
 
-    pub fn try_send_update(&self, update: Update) {
-        let _ = self.event_sender.try_send(Event::SendUpdate(update));
+    /// Returns whether the update was queued, it is dropped if the connection is congested
+    pub fn try_send_update(&self, update: Update) -> bool {
+        self.queued_updates.fetch_add(1, Ordering::SeqCst);
+        let is_queued = self
+            .event_sender
+            .try_send(Event::SendUpdate(update))
+            .is_ok();
+        if !is_queued {
+            self.mark_update_as_sent();
+        }
+        is_queued
+    }
+
+    /// Whether previous updates are still waiting to be sent over the socket, other queued events
+    /// do not count
+    pub fn has_pending_updates(&self) -> bool {
+        self.queued_updates.load(Ordering::SeqCst) > 0
+    }
+
+    fn mark_update_as_sent(&self) {
+        self.queued_updates.fetch_sub(1, Ordering::SeqCst);
     }
 }
 
@@ -94,7 +122,11 @@ impl Connection {
         server_cancellation: CancellationToken,
     ) -> (Self, ConnectionHandle) {
         let (event_sender, event_receiver) = mpsc::channel(10);
-        let handle = ConnectionHandle { event_sender, id };
+        let handle = ConnectionHandle {
+            event_sender,
+            id,
+            queued_updates: Arc::default(),
+        };
 
         let task = Self {
             subscriptions: HashMap::new(),
@@ -148,7 +180,11 @@ impl Connection {
 
     async fn handle_event(&mut self, event: Event) -> Result<(), ClosingError> {
         match event {
-            Event::SendUpdate(update) => self.send_update(update).await,
+            Event::SendUpdate(update) => {
+                let result = self.send_update(update).await;
+                self.handle.mark_update_as_sent();
+                result
+            }
         }
     }
 
@@ -211,10 +247,22 @@ impl Connection {
                 let (timestamp, value) = self.router.read(path, format).await?;
                 Ok(ResponseKind::Read { timestamp, value })
             }
-            RequestKind::Subscribe { path, format } => {
+            RequestKind::Subscribe {
+                path,
+                format,
+                rate,
+                update_mode,
+            } => {
                 let (handle, timestamp, value) = self
                     .router
-                    .subscribe(path, format, self.handle.clone(), request.id)
+                    .subscribe(
+                        path,
+                        format,
+                        rate,
+                        update_mode,
+                        self.handle.clone(),
+                        request.id,
+                    )
                     .await?;
                 self.subscriptions.insert(request.id, handle);
                 Ok(ResponseKind::Subscribe { timestamp, value })
@@ -273,6 +321,13 @@ fn compose_update_messages(update: Update) -> Result<Vec<Message>, ClosingError>
                 bincode::serialize(&response).map_err(ClosingError::BincodeSerialization)?;
             Ok(Message::Binary(bytes.into()))
         }))
+        .chain(update.deltas.into_iter().map(|(id, patch)| {
+            let kind = Ok(ResponseKind::Delta { timestamp, patch });
+            let response = Response { id, kind };
+            let string =
+                serde_json::to_string(&response).map_err(ClosingError::JsonSerialization)?;
+            Ok(Message::Text(string.into()))
+        }))
         .collect()
 }
 
@@ -281,15 +336,18 @@ mod tests {
     use std::{
         collections::HashSet,
         iter::once,
-        time::{SystemTime, UNIX_EPOCH},
+        num::NonZeroUsize,
+        time::{Duration, SystemTime, UNIX_EPOCH},
     };
 
     use path_serde::{PathIntrospect, PathSerialize};
     use serde::Serialize;
     use serde_json::json;
+    use tokio::time::{sleep, timeout};
 
     use crate::{
-        messages::{Format, Path},
+        json_patch::PatchOperation,
+        messages::{Format, Path, SubscriptionRate, UpdateMode},
         server::source::Source,
     };
 
@@ -314,13 +372,21 @@ mod tests {
         let client = ConnectionHandle {
             event_sender: client_event_sender,
             id: 13,
+            queued_updates: Arc::default(),
         };
 
         let path = Path::from("field");
         let format = Format::Text;
         let id = 4;
         let (subscription, timestamp, value) = handle
-            .subscribe(path, format, client.clone(), id)
+            .subscribe(
+                path,
+                format,
+                SubscriptionRate::default(),
+                UpdateMode::Full,
+                client.clone(),
+                id,
+            )
             .await
             .unwrap();
 
@@ -339,6 +405,7 @@ mod tests {
             timestamp,
             texts: once((id, Ok(json!(1337)))).collect(),
             binaries: HashMap::new(),
+            deltas: HashMap::new(),
         };
         let received_event = client_event_receiver.recv().await.unwrap();
         assert_eq!(received_event, Event::SendUpdate(update));
@@ -353,4 +420,140 @@ mod tests {
         drop(handle);
         task.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn subscribe_with_delta_updates() {
+        let (mut data_sender, data_receiver) =
+            buffered_watch::channel((UNIX_EPOCH, Data { field: 42 }));
+        let (subscriptions_sender, _subscriptions_receiver) =
+            buffered_watch::channel(HashSet::new());
+
+        let (source, handle) = Source::new(data_receiver, subscriptions_sender);
+        let task = tokio::spawn(source.run());
+
+        let (client_event_sender, mut client_event_receiver) = mpsc::channel(1);
+        let client = ConnectionHandle {
+            event_sender: client_event_sender,
+            id: 13,
+            queued_updates: Arc::default(),
+        };
+
+        let id = 4;
+        let update_mode = UpdateMode::Delta {
+            keyframe_interval: NonZeroUsize::new(2).unwrap(),
+        };
+        let (subscription, _timestamp, value) = handle
+            .subscribe(
+                Path::new(),
+                Format::Text,
+                SubscriptionRate::default(),
+                update_mode,
+                client.clone(),
+                id,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(value, TextOrBinary::Text(json!({"field": 42})));
+
+        let timestamp = SystemTime::now();
+        *data_sender.borrow_mut() = (timestamp, Data { field: 1337 });
+
+        let delta = Update {
+            timestamp,
+            texts: HashMap::new(),
+            binaries: HashMap::new(),
+            deltas: once((
+                id,
+                vec![PatchOperation::Replace {
+                    path: "/field".to_string(),
+                    value: json!(1337),
+                }],
+            ))
+            .collect(),
+        };
+        let received_event = client_event_receiver.recv().await.unwrap();
+        assert_eq!(received_event, Event::SendUpdate(delta));
+
+        let timestamp = SystemTime::now();
+        *data_sender.borrow_mut() = (timestamp, Data { field: 7 });
+
+        // every second update is a keyframe containing the whole value
+        let keyframe = Update {
+            timestamp,
+            texts: once((id, Ok(json!({"field": 7})))).collect(),
+            binaries: HashMap::new(),
+            deltas: HashMap::new(),
+        };
+        let received_event = client_event_receiver.recv().await.unwrap();
+        assert_eq!(received_event, Event::SendUpdate(keyframe));
+
+        drop(subscription);
+        drop(handle);
+        task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn latest_only_update_is_retried_after_pending_update_was_sent() {
+        let (mut data_sender, data_receiver) =
+            buffered_watch::channel((UNIX_EPOCH, Data { field: 42 }));
+        let (subscriptions_sender, _subscriptions_receiver) =
+            buffered_watch::channel(HashSet::new());
+
+        let (source, handle) = Source::new(data_receiver, subscriptions_sender);
+        let task = tokio::spawn(source.run());
+
+        let (client_event_sender, mut client_event_receiver) = mpsc::channel(10);
+        let client = ConnectionHandle {
+            event_sender: client_event_sender,
+            id: 13,
+            queued_updates: Arc::default(),
+        };
+
+        let id = 4;
+        let rate = SubscriptionRate {
+            latest_only: true,
+            ..Default::default()
+        };
+        let (subscription, _timestamp, _value) = handle
+            .subscribe(
+                Path::from("field"),
+                Format::Text,
+                rate,
+                UpdateMode::Full,
+                client.clone(),
+                id,
+            )
+            .await
+            .unwrap();
+
+        *data_sender.borrow_mut() = (SystemTime::now(), Data { field: 1 });
+        let Event::SendUpdate(first) = client_event_receiver.recv().await.unwrap();
+        assert_eq!(first.texts[&id], Ok(json!(1)));
+
+        let timestamp = SystemTime::now();
+        *data_sender.borrow_mut() = (timestamp, Data { field: 2 });
+
+        // the first update was not sent over the socket yet, so the second one is held back
+        sleep(Duration::from_millis(50)).await;
+        assert!(client_event_receiver.try_recv().is_err());
+
+        // without a new value, only the retry deadline can send the held back update
+        client.mark_update_as_sent();
+        let update = Update {
+            timestamp,
+            texts: once((id, Ok(json!(2)))).collect(),
+            binaries: HashMap::new(),
+            deltas: HashMap::new(),
+        };
+        let received_event = timeout(Duration::from_secs(1), client_event_receiver.recv())
+            .await
+            .expect("held back update to be retried")
+            .unwrap();
+        assert_eq!(received_event, Event::SendUpdate(update));
+
+        drop(subscription);
+        drop(handle);
+        task.await.unwrap();
+    }
 }
@@ -7,7 +7,7 @@ use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
 
 use crate::{
-    messages::{Entry, Format, Path, RequestId, TextOrBinary},
+    messages::{Entry, Format, Path, RequestId, SubscriptionRate, TextOrBinary, UpdateMode},
     server::source,
 };
 
@@ -48,6 +48,8 @@ enum Event {
     Subscribe {
         path: Path,
         format: Format,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
         client: ConnectionHandle,
         id: RequestId,
         return_sender:
@@ -97,6 +99,8 @@ impl RouterHandle {
         &self,
         path: Path,
         format: Format,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
         client: ConnectionHandle,
         id: RequestId,
     ) -> Result<(SubscriptionHandle, SystemTime, TextOrBinary), Error> {
@@ -105,6 +109,8 @@ impl RouterHandle {
             .send(Event::Subscribe {
                 path,
                 format,
+                rate,
+                update_mode,
                 client,
                 id,
                 return_sender,
@@ -175,11 +181,15 @@ impl Router {
                 Event::Subscribe {
                     path,
                     format,
+                    rate,
+                    update_mode,
                     client,
                     id,
                     return_sender,
                 } => {
-                    let result = self.subscribe(path, format, client, id).await;
+                    let result = self
+                        .subscribe(path, format, rate, update_mode, client, id)
+                        .await;
                     let _ = return_sender.send(result);
                 }
                 Event::Write {
@@ -214,6 +224,8 @@ impl Router {
         &self,
         path: Path,
         format: Format,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
         client: ConnectionHandle,
         id: RequestId,
     ) -> Result<(SubscriptionHandle, SystemTime, TextOrBinary), Error> {
@@ -221,7 +233,7 @@ impl Router {
 
         let response = hit
             .mount
-            .subscribe(hit.path, format, client, id)
+            .subscribe(hit.path, format, rate, update_mode, client, id)
             .await
             .map_err(|error| Error::Source {
                 source: hit.mount_point.to_string(),
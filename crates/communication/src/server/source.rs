@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
-    time::SystemTime,
+    num::NonZeroUsize,
+    time::{Duration, Instant, SystemTime},
 };
 
 use bincode::{DefaultOptions, Options};
@@ -13,9 +14,13 @@ use tokio::{
     select,
     sync::{mpsc, oneshot},
     task::{yield_now, JoinSet},
+    time::sleep_until,
 };
 
-use crate::messages::{Format, Path, RequestId, TextOrBinary};
+use crate::{
+    json_patch::{self, Patch},
+    messages::{Format, Path, RequestId, SubscriptionRate, TextOrBinary, UpdateMode},
+};
 
 use super::{acceptor::ClientId, connection::ConnectionHandle};
 
@@ -24,6 +29,7 @@ pub struct Update {
     pub timestamp: SystemTime,
     pub texts: HashMap<RequestId, Result<Value, String>>,
     pub binaries: HashMap<RequestId, Result<Vec<u8>, String>>,
+    pub deltas: HashMap<RequestId, Patch>,
 }
 
 #[derive(Debug)]
@@ -41,6 +47,8 @@ pub enum Error {
     BinarySerialization(#[source] path_serde::serialize::Error<bincode::Error>),
     #[error("duplicate subscription with id `{0}`")]
     DuplicateSubscription(RequestId),
+    #[error("delta updates are only supported for text subscriptions")]
+    BinaryDelta,
 }
 
 pub enum Event {
@@ -52,6 +60,8 @@ pub enum Event {
     Subscribe {
         path: Path,
         format: Format,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
         client: ConnectionHandle,
         id: RequestId,
         return_sender:
@@ -91,6 +101,8 @@ impl SourceHandle {
         &self,
         path: impl Into<Path>,
         format: Format,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
         client: ConnectionHandle,
         id: RequestId,
     ) -> Result<(SubscriptionHandle, SystemTime, TextOrBinary), Error> {
@@ -99,6 +111,8 @@ impl SourceHandle {
             .send(Event::Subscribe {
                 path: path.into(),
                 format,
+                rate,
+                update_mode,
                 client,
                 id,
                 return_sender,
@@ -109,10 +123,91 @@ impl SourceHandle {
     }
 }
 
+/// How long latest-only subscriptions wait before checking again whether the client caught up
+const PENDING_UPDATES_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
 #[derive(Debug)]
 struct Subscription {
     path: Path,
     format: Format,
+    rate: SubscriptionRate,
+    last_sent: Instant,
+    updates_since_last_sent: usize,
+    update_mode: UpdateMode,
+    /// Last text value received by the client, cloned and diffed per due update in delta mode
+    delta_base: Option<Value>,
+    updates_since_keyframe: usize,
+}
+
+impl Subscription {
+    fn new(path: Path, format: Format, rate: SubscriptionRate, now: Instant) -> Self {
+        Self {
+            path,
+            format,
+            rate,
+            last_sent: now,
+            updates_since_last_sent: 0,
+            update_mode: UpdateMode::Full,
+            delta_base: None,
+            updates_since_keyframe: 0,
+        }
+    }
+
+    fn with_delta_updates(mut self, update_mode: UpdateMode, initial_value: &TextOrBinary) -> Self {
+        if let (UpdateMode::Delta { .. }, TextOrBinary::Text(value)) = (update_mode, initial_value)
+        {
+            self.update_mode = update_mode;
+            self.delta_base = Some(value.clone());
+        }
+        self
+    }
+
+    fn record_update(&mut self) {
+        self.updates_since_last_sent += 1;
+    }
+
+    /// Earliest time at which the latest update may be sent, `None` if there is nothing to send
+    fn next_send_time(&self, now: Instant, client_has_pending_updates: bool) -> Option<Instant> {
+        let decimation = self.rate.decimation.map_or(1, NonZeroUsize::get);
+        if self.updates_since_last_sent < decimation {
+            return None;
+        }
+        let earliest = self.rate.minimum_interval.map_or(now, |minimum_interval| {
+            (self.last_sent + minimum_interval).max(now)
+        });
+        if self.rate.latest_only && client_has_pending_updates {
+            return Some(earliest.max(now + PENDING_UPDATES_RETRY_INTERVAL));
+        }
+        Some(earliest)
+    }
+
+    fn mark_as_sent(&mut self, now: Instant) {
+        self.last_sent = now;
+        self.updates_since_last_sent = 0;
+    }
+
+    /// Base of the next update, `None` if the next update has to contain the whole value
+    fn delta_base(&self) -> Option<&Value> {
+        match self.update_mode {
+            UpdateMode::Full => None,
+            UpdateMode::Delta { keyframe_interval } => self
+                .delta_base
+                .as_ref()
+                .filter(|_| self.updates_since_keyframe + 1 < keyframe_interval.get()),
+        }
+    }
+
+    /// Remembers the text value which was handed to the client as base of the next delta
+    fn record_sent_text(&mut self, value: &Result<Value, String>) {
+        if self.update_mode == UpdateMode::Full {
+            return;
+        }
+        self.updates_since_keyframe = match self.delta_base() {
+            Some(_) => self.updates_since_keyframe + 1,
+            None => 0,
+        };
+        self.delta_base = value.as_ref().ok().cloned();
+    }
 }
 
 #[derive(Debug)]
@@ -142,6 +237,8 @@ pub struct Source<T> {
     client_subscriptions: HashMap<ClientId, ClientSubscriptions>,
     subscriptions_sender: buffered_watch::Sender<HashSet<Path>>,
     unsubscriptions: JoinSet<Unsubscribe>,
+    /// When skipped updates of rate limited subscriptions are sent next
+    retry_deadline: Option<Instant>,
 }
 
 impl<T> Source<T>
@@ -159,6 +256,7 @@ where
             client_subscriptions: HashMap::new(),
             subscriptions_sender,
             unsubscriptions: JoinSet::new(),
+            retry_deadline: None,
         };
         let handle = SourceHandle { command_sender };
         (task, handle)
@@ -179,6 +277,10 @@ where
                 Ok(()) = self.data.wait_for_change() => {
                     self.handle_update().await;
                 },
+                () = sleep_until(self.retry_deadline.unwrap_or_else(Instant::now).into()),
+                    if self.retry_deadline.is_some() => {
+                    self.send_due_updates(false).await;
+                },
             }
         }
     }
@@ -196,11 +298,13 @@ where
             Event::Subscribe {
                 path,
                 format,
+                rate,
+                update_mode,
                 client,
                 id,
                 return_sender,
             } => {
-                let response = self.subscribe(path, format, client, id);
+                let response = self.subscribe(path, format, rate, update_mode, client, id);
                 let _ = return_sender.send(response);
             }
         }
@@ -228,11 +332,17 @@ where
         &mut self,
         path: String,
         format: Format,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
         client: ConnectionHandle,
         id: usize,
     ) -> Result<(SubscriptionHandle, SystemTime, TextOrBinary), Error> {
         let client_id = client.id();
 
+        if format == Format::Binary && update_mode != UpdateMode::Full {
+            return Err(Error::BinaryDelta);
+        }
+
         if let Some(subscriptions) = self.client_subscriptions.get(&client_id) {
             if subscriptions.subscriptions.contains_key(&id) {
                 return Err(Error::DuplicateSubscription(id));
@@ -254,7 +364,8 @@ where
             (*timestamp, value)
         };
 
-        let subscription = Subscription { path, format };
+        let subscription = Subscription::new(path, format, rate, Instant::now())
+            .with_delta_updates(update_mode, &value);
         self.client_subscriptions
             .entry(client_id)
             .or_insert_with(|| ClientSubscriptions::new(client))
@@ -293,15 +404,62 @@ where
     }
 
     async fn handle_update(&mut self) {
-        let cache = self.serialize_subscribed();
-        for client_subscriptions in self.client_subscriptions.values() {
+        for subscription in self
+            .client_subscriptions
+            .values_mut()
+            .flat_map(|map| map.subscriptions.values_mut())
+        {
+            subscription.record_update();
+        }
+        self.send_due_updates(true).await;
+    }
+
+    async fn send_due_updates(&mut self, mark_as_seen: bool) {
+        let now = Instant::now();
+        let mut due_subscriptions = HashMap::new();
+        let mut retry_deadline: Option<Instant> = None;
+        for (client_id, client_subscriptions) in &mut self.client_subscriptions {
+            let client_has_pending_updates = client_subscriptions.client.has_pending_updates();
+            let mut due_ids = Vec::new();
+            for (id, subscription) in &mut client_subscriptions.subscriptions {
+                match subscription.next_send_time(now, client_has_pending_updates) {
+                    Some(send_time) if send_time <= now => {
+                        subscription.mark_as_sent(now);
+                        due_ids.push(*id);
+                    }
+                    Some(send_time) => {
+                        retry_deadline = Some(
+                            retry_deadline.map_or(send_time, |deadline| deadline.min(send_time)),
+                        );
+                    }
+                    None => {}
+                }
+            }
+            if !due_ids.is_empty() {
+                due_subscriptions.insert(*client_id, due_ids);
+            }
+        }
+        self.retry_deadline = retry_deadline;
+
+        let cache = self.serialize_subscribed(&due_subscriptions, mark_as_seen);
+        for (client_id, due_ids) in &due_subscriptions {
+            let client_subscriptions = &self.client_subscriptions[client_id];
             let mut texts = HashMap::new();
             let mut binaries = HashMap::new();
-            for (id, subscription) in &client_subscriptions.subscriptions {
+            let mut deltas = HashMap::new();
+            for id in due_ids {
+                let subscription = &client_subscriptions.subscriptions[id];
                 match subscription.format {
                     Format::Text => {
-                        let value = cache.values[&subscription.path].clone();
-                        texts.insert(*id, value);
+                        let value = &cache.values[&subscription.path];
+                        match (value, subscription.delta_base()) {
+                            (Ok(value), Some(base)) => {
+                                deltas.insert(*id, json_patch::diff(base, value));
+                            }
+                            _ => {
+                                texts.insert(*id, value.clone());
+                            }
+                        }
                     }
                     Format::Binary => {
                         let bytes = cache.bytes[&subscription.path].clone();
@@ -313,23 +471,46 @@ where
                 timestamp: cache.timestamp,
                 texts,
                 binaries,
+                deltas,
             };
-            client_subscriptions.client.try_send_update(update);
+            if !client_subscriptions.client.try_send_update(update) {
+                // the next delta is based on the last update the client actually received
+                continue;
+            }
+            let subscriptions = &mut self
+                .client_subscriptions
+                .get_mut(client_id)
+                .expect("client to exist")
+                .subscriptions;
+            for id in due_ids {
+                let subscription = subscriptions.get_mut(id).expect("subscription to exist");
+                if subscription.format == Format::Text {
+                    subscription.record_sent_text(&cache.values[&subscription.path]);
+                }
+            }
         }
         yield_now().await;
     }
 
-    fn serialize_subscribed(&mut self) -> SerializationCache {
+    fn serialize_subscribed(
+        &mut self,
+        due_subscriptions: &HashMap<ClientId, Vec<RequestId>>,
+        mark_as_seen: bool,
+    ) -> SerializationCache {
         let mut serialized_values = HashMap::new();
         let mut serialized_bytes = HashMap::new();
 
-        let (timestamp, data) = &*self.data.borrow_and_mark_as_seen();
+        let guard = if mark_as_seen {
+            self.data.borrow_and_mark_as_seen()
+        } else {
+            self.data.borrow()
+        };
+        let (timestamp, data) = &*guard;
 
-        for subscription in self
-            .client_subscriptions
-            .values()
-            .flat_map(|map| map.subscriptions.values())
-        {
+        for subscription in due_subscriptions.iter().flat_map(|(client_id, due_ids)| {
+            let subscriptions = &self.client_subscriptions[client_id].subscriptions;
+            due_ids.iter().map(|id| &subscriptions[id])
+        }) {
             match subscription.format {
                 Format::Text => {
                     let value = serialize_as_text(data, &subscription.path)
@@ -393,3 +574,84 @@ where
     }
     Ok(bytes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(rate: SubscriptionRate, now: Instant) -> Subscription {
+        Subscription::new(Path::from("field"), Format::Text, rate, now)
+    }
+
+    #[test]
+    fn default_rate_sends_every_update() {
+        let now = Instant::now();
+        let mut subscription = subscription(SubscriptionRate::default(), now);
+
+        assert_eq!(subscription.next_send_time(now, false), None);
+        subscription.record_update();
+        assert_eq!(subscription.next_send_time(now, true), Some(now));
+    }
+
+    #[test]
+    fn decimation_sends_every_nth_update() {
+        let now = Instant::now();
+        let rate = SubscriptionRate {
+            decimation: NonZeroUsize::new(3),
+            ..Default::default()
+        };
+        let mut subscription = subscription(rate, now);
+
+        let sent: Vec<_> = (0..6)
+            .map(|_| {
+                subscription.record_update();
+                let is_due = subscription.next_send_time(now, false).is_some();
+                if is_due {
+                    subscription.mark_as_sent(now);
+                }
+                is_due
+            })
+            .collect();
+
+        assert_eq!(sent, [false, false, true, false, false, true]);
+    }
+
+    #[test]
+    fn minimum_interval_delays_updates() {
+        let start = Instant::now();
+        let rate = SubscriptionRate {
+            minimum_interval: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let mut subscription = subscription(rate, start);
+
+        subscription.record_update();
+        assert_eq!(
+            subscription.next_send_time(start + Duration::from_millis(50), false),
+            Some(start + Duration::from_millis(100))
+        );
+
+        let now = start + Duration::from_millis(120);
+        assert_eq!(subscription.next_send_time(now, false), Some(now));
+        subscription.mark_as_sent(now);
+        assert_eq!(subscription.next_send_time(now, false), None);
+    }
+
+    #[test]
+    fn latest_only_waits_for_pending_updates() {
+        let now = Instant::now();
+        let rate = SubscriptionRate {
+            latest_only: true,
+            ..Default::default()
+        };
+        let mut subscription = subscription(rate, now);
+
+        subscription.record_update();
+        subscription.record_update();
+        assert_eq!(
+            subscription.next_send_time(now, true),
+            Some(now + PENDING_UPDATES_RETRY_INTERVAL)
+        );
+        assert_eq!(subscription.next_send_time(now, false), Some(now));
+    }
+}
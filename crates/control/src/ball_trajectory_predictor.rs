@@ -0,0 +1,90 @@
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use ball_filter::trajectory::predict_trajectory;
+use context_attribute::context;
+use coordinate_systems::{Field, Ground};
+use framework::MainOutput;
+use geometry::circle::Circle;
+use itertools::iproduct;
+use linear_algebra::{point, Isometry2, Point2};
+use types::{
+    ball_position::BallPosition,
+    ball_trajectory::BallTrajectory,
+    field_dimensions::FieldDimensions,
+    obstacles::{Obstacle, ObstacleKind},
+    parameters::BallTrajectoryParameters,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct BallTrajectoryPredictor {}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    ball_position: RequiredInput<Option<BallPosition<Ground>>, "ball_position?">,
+    ground_to_field: Input<Option<Isometry2<Ground, Field>>, "ground_to_field?">,
+    obstacles: Input<Vec<Obstacle>, "obstacles">,
+
+    field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
+    parameters: Parameter<BallTrajectoryParameters, "ball_trajectory">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub ball_trajectory: MainOutput<Option<BallTrajectory>>,
+    pub ball_rest_position: MainOutput<Option<Point2<Ground>>>,
+}
+
+impl BallTrajectoryPredictor {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {})
+    }
+
+    pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
+        let goal_posts = context
+            .ground_to_field
+            .map(|ground_to_field| goal_posts(ground_to_field.inverse(), context.field_dimensions))
+            .unwrap_or_default();
+        let robots = context
+            .obstacles
+            .iter()
+            .filter(|obstacle| !matches!(obstacle.kind, ObstacleKind::Ball))
+            .map(|obstacle| Circle {
+                center: obstacle.position,
+                radius: obstacle.radius_at_foot_height,
+            });
+        let colliders: Vec<_> = goal_posts.into_iter().chain(robots).collect();
+
+        let trajectory = predict_trajectory(
+            context.ball_position.position,
+            context.ball_position.velocity,
+            context.field_dimensions.ball_radius,
+            &colliders,
+            context.parameters,
+        );
+
+        Ok(MainOutputs {
+            ball_rest_position: Some(trajectory.rest_position).into(),
+            ball_trajectory: Some(trajectory).into(),
+        })
+    }
+}
+
+fn goal_posts(
+    field_to_ground: Isometry2<Field, Ground>,
+    field_dimensions: &FieldDimensions,
+) -> Vec<Circle<Ground>> {
+    let radius = field_dimensions.goal_post_diameter / 2.0;
+    let goal_post_x = field_dimensions.length / 2.0 + radius - field_dimensions.line_width / 2.0;
+    let goal_post_y = field_dimensions.goal_inner_width / 2.0 + radius;
+    iproduct!([-1.0, 1.0], [-1.0, 1.0])
+        .map(|(x_sign, y_sign)| Circle {
+            center: field_to_ground * point![x_sign * goal_post_x, y_sign * goal_post_y],
+            radius,
+        })
+        .collect()
+}
@@ -1,8 +1,10 @@
 use types::{motion_command::MotionCommand, primary_state::PrimaryState, world_state::WorldState};
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+use super::ActionResult;
+
+pub fn execute(world_state: &WorldState) -> ActionResult {
     match world_state.robot.primary_state {
-        PrimaryState::Animation { stiff } => Some(MotionCommand::Animation { stiff }),
-        _ => None,
+        PrimaryState::Animation { stiff } => Ok(MotionCommand::Animation { stiff }),
+        _ => Err("not in animation"),
     }
 }
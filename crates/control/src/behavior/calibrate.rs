@@ -5,15 +5,14 @@ use types::{
     world_state::WorldState,
 };
 
-pub fn execute(
-    world_state: &WorldState,
-    use_stand_head_unstiff_calibration: bool,
-) -> Option<MotionCommand> {
+use super::ActionResult;
+
+pub fn execute(world_state: &WorldState, use_stand_head_unstiff_calibration: bool) -> ActionResult {
     if PrimaryState::Calibration != world_state.robot.primary_state {
-        return None;
+        return Err("not in calibration");
     }
     if use_stand_head_unstiff_calibration {
-        return Some(MotionCommand::Stand {
+        return Ok(MotionCommand::Stand {
             head: HeadMotion::Unstiff,
         });
     }
@@ -28,5 +27,5 @@ pub fn execute(
         } else {
             HeadMotion::Unstiff
         };
-    Some(MotionCommand::Stand { head })
+    Ok(MotionCommand::Stand { head })
 }
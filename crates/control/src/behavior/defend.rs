@@ -13,13 +13,14 @@ use spl_network_messages::{GamePhase, SubState, Team};
 use types::{
     field_dimensions::{FieldDimensions, Side},
     filtered_game_controller_state::FilteredGameControllerState,
+    goal_threat::KeeperDive,
     motion_command::{JumpDirection, MotionCommand, WalkSpeed},
-    parameters::{KeeperMotionParameters, RolePositionsParameters},
+    parameters::RolePositionsParameters,
     path_obstacles::PathObstacle,
     world_state::{BallState, WorldState},
 };
 
-use super::{head::LookAction, walk_to_pose::WalkAndStand};
+use super::{head::LookAction, walk_to_pose::WalkAndStand, ActionResult};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DefendMode {
@@ -62,7 +63,7 @@ impl<'cycle> Defend<'cycle> {
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
         hysteresis: nalgebra::Vector2<f32>,
-    ) -> Option<MotionCommand> {
+    ) -> ActionResult {
         self.walk_and_stand.execute(
             pose,
             self.look_action.execute(),
@@ -73,45 +74,22 @@ impl<'cycle> Defend<'cycle> {
         )
     }
 
-    pub fn keeper_motion(&self, parameters: KeeperMotionParameters) -> Option<MotionCommand> {
-        let ball = self.world_state.ball?;
-
-        let position = ball.ball_in_ground;
-        let velocity = ball.ball_in_ground_velocity;
-
-        let ball_is_in_front_of_robot =
-            position.coords().norm() < parameters.maximum_ball_distance && position.x() > 0.0;
-        let ball_is_moving_towards_robot =
-            ball.ball_in_ground_velocity.x() < -parameters.minimum_ball_velocity;
-
-        if !ball_is_in_front_of_robot || !ball_is_moving_towards_robot {
-            return None;
-        }
-
-        let horizontal_distance_to_intersection =
-            position.y() - position.x() / velocity.x() * velocity.y();
-
-        if (-parameters.action_radius_center..=parameters.action_radius_center)
-            .contains(&horizontal_distance_to_intersection)
-        {
-            Some(MotionCommand::KeeperMotion {
+    pub fn keeper_motion(&self) -> ActionResult {
+        let motion = match self.world_state.keeper_dive.ok_or("no keeper dive")? {
+            KeeperDive::WideStance => MotionCommand::KeeperMotion {
+                direction: JumpDirection::Center,
+            },
+            KeeperDive::CenterJump => MotionCommand::Jump {
                 direction: JumpDirection::Center,
-            })
-        } else if (parameters.action_radius_center..parameters.action_radius_left)
-            .contains(&horizontal_distance_to_intersection)
-        {
-            Some(MotionCommand::KeeperMotion {
+            },
+            KeeperDive::JumpLeft => MotionCommand::KeeperMotion {
                 direction: JumpDirection::Left,
-            })
-        } else if (-parameters.action_radius_left..-parameters.action_radius_center)
-            .contains(&horizontal_distance_to_intersection)
-        {
-            Some(MotionCommand::KeeperMotion {
+            },
+            KeeperDive::JumpRight => MotionCommand::KeeperMotion {
                 direction: JumpDirection::Right,
-            })
-        } else {
-            None
-        }
+            },
+        };
+        Ok(motion)
     }
 
     pub fn left(
@@ -119,7 +97,7 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> ActionResult {
         let pose = defend_pose(
             self.world_state,
             self.field_dimensions,
@@ -127,7 +105,8 @@ impl<'cycle> Defend<'cycle> {
             -self.field_dimensions.length / 2.0,
             Side::Left,
             self.last_defender_mode,
-        )?;
+        )
+        .ok_or("not localized")?;
         self.with_pose(
             pose,
             path_obstacles_output,
@@ -142,7 +121,7 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> ActionResult {
         let pose = defend_pose(
             self.world_state,
             self.field_dimensions,
@@ -150,7 +129,8 @@ impl<'cycle> Defend<'cycle> {
             -self.field_dimensions.length / 2.0,
             Side::Right,
             self.last_defender_mode,
-        )?;
+        )
+        .ok_or("not localized")?;
         self.with_pose(
             pose,
             path_obstacles_output,
@@ -166,7 +146,7 @@ impl<'cycle> Defend<'cycle> {
         walk_speed: WalkSpeed,
         field_side: Side,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> ActionResult {
         let pose = defend_pose(
             self.world_state,
             self.field_dimensions,
@@ -174,7 +154,8 @@ impl<'cycle> Defend<'cycle> {
             -self.field_dimensions.length / 2.0 + self.field_dimensions.goal_box_area_length * 2.0,
             field_side,
             self.last_defender_mode,
-        )?;
+        )
+        .ok_or("not localized")?;
         self.with_pose(
             pose,
             path_obstacles_output,
@@ -189,9 +170,10 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> ActionResult {
         let pose =
-            defend_penalty_kick(self.world_state, self.field_dimensions, self.role_positions)?;
+            defend_penalty_kick(self.world_state, self.field_dimensions, self.role_positions)
+                .ok_or("not localized")?;
         self.with_pose(
             pose,
             path_obstacles_output,
@@ -206,8 +188,9 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
-        let pose = defend_goal_pose(self.world_state, self.field_dimensions, self.role_positions)?;
+    ) -> ActionResult {
+        let pose = defend_goal_pose(self.world_state, self.field_dimensions, self.role_positions)
+            .ok_or("not localized")?;
         self.with_pose(
             pose,
             path_obstacles_output,
@@ -222,9 +205,10 @@ impl<'cycle> Defend<'cycle> {
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
-    ) -> Option<MotionCommand> {
+    ) -> ActionResult {
         let pose =
-            defend_kick_off_pose(self.world_state, self.field_dimensions, self.role_positions)?;
+            defend_kick_off_pose(self.world_state, self.field_dimensions, self.role_positions)
+                .ok_or("not localized")?;
         self.with_pose(
             pose,
             path_obstacles_output,
@@ -10,7 +10,7 @@ use types::{
     world_state::WorldState,
 };
 
-use super::walk_to_pose::WalkPathPlanner;
+use super::{walk_to_pose::WalkPathPlanner, ActionResult};
 
 #[allow(clippy::too_many_arguments)]
 pub fn execute(
@@ -20,8 +20,8 @@ pub fn execute(
     parameters: &DribblingParameters,
     dribble_path_plan: Option<DribblePathPlan>,
     mut walk_speed: WalkSpeed,
-) -> Option<MotionCommand> {
-    let ball_position = world_state.ball?.ball_in_ground;
+) -> ActionResult {
+    let ball_position = world_state.ball.ok_or("ball not seen")?.ball_in_ground;
     let distance_to_ball = ball_position.coords().norm();
     let head = if distance_to_ball < parameters.distance_to_look_directly_at_the_ball {
         HeadMotion::LookAt {
@@ -34,8 +34,14 @@ pub fn execute(
             target: ball_position,
         }
     };
-    let kick_decisions = world_state.kick_decisions.as_ref()?;
-    let instant_kick_decisions = world_state.instant_kick_decisions.as_ref()?;
+    let kick_decisions = world_state
+        .kick_decisions
+        .as_ref()
+        .ok_or("no kick decisions")?;
+    let instant_kick_decisions = world_state
+        .instant_kick_decisions
+        .as_ref()
+        .ok_or("no instant kick decisions")?;
 
     let available_kick = kick_decisions
         .iter()
@@ -56,7 +62,7 @@ pub fn execute(
             left_arm: ArmMotion::Swing,
             right_arm: ArmMotion::Swing,
         };
-        return Some(command);
+        return Ok(command);
     }
 
     if let Some(FilteredGameControllerState {
@@ -71,13 +77,13 @@ pub fn execute(
         Some(DribblePathPlan {
             orientation_mode,
             path,
-        }) => Some(walk_path_planner.walk_with_obstacle_avoiding_arms(
+        }) => Ok(walk_path_planner.walk_with_obstacle_avoiding_arms(
             head,
             orientation_mode,
             path,
             walk_speed,
         )),
-        None => Some(MotionCommand::Stand { head }),
+        None => Ok(MotionCommand::Stand { head }),
     }
 }
 
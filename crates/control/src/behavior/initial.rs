@@ -9,31 +9,31 @@ use types::{
     world_state::WorldState,
 };
 
+use super::ActionResult;
+
 pub fn execute(
     world_state: &WorldState,
     expected_referee_position: Option<Point2<Field>>,
     enable_pose_detection: bool,
-) -> Option<MotionCommand> {
+) -> ActionResult {
     if world_state.robot.primary_state == PrimaryState::Initial {
-        return Some(MotionCommand::Initial {
+        return Ok(MotionCommand::Initial {
             head: HeadMotion::Center,
             should_look_for_referee: false,
         });
     }
     if world_state.robot.primary_state == PrimaryState::Standby {
-        return Some(
-            look_at_referee(
-                expected_referee_position,
-                world_state.clone(),
-                enable_pose_detection,
-            )
-            .unwrap_or(MotionCommand::Initial {
-                head: HeadMotion::Center,
-                should_look_for_referee: false,
-            }),
-        );
+        return Ok(look_at_referee(
+            expected_referee_position,
+            world_state.clone(),
+            enable_pose_detection,
+        )
+        .unwrap_or(MotionCommand::Initial {
+            head: HeadMotion::Center,
+            should_look_for_referee: false,
+        }));
     }
-    None
+    Err("not in Initial or Standby")
 }
 
 fn look_at_referee(
@@ -13,12 +13,14 @@ use types::{
     world_state::{BallState, WorldState},
 };
 
+use super::ActionResult;
+
 pub fn execute(
     world_state: &WorldState,
     parameters: InterceptBallParameters,
     maximum_step_size: Step,
     walk_speed: WalkSpeed,
-) -> Option<MotionCommand> {
+) -> ActionResult {
     if let Some(
         FilteredGameControllerState {
             game_phase: GamePhase::PenaltyShootout { .. },
@@ -30,7 +32,7 @@ pub fn execute(
         },
     ) = world_state.filtered_game_controller_state
     {
-        return None;
+        return Err("in penalty kick or shootout");
     }
 
     let filtered_game_state = world_state
@@ -51,7 +53,7 @@ pub fn execute(
             Some(ground_to_field),
         ) => {
             if !ball_is_interception_candidate(ball, ground_to_field, &parameters) {
-                return None;
+                return Err("ball not approaching");
             }
 
             let Step {
@@ -61,17 +63,24 @@ pub fn execute(
             } = maximum_step_size;
 
             if forward == 0.0 || left == 0.0 {
-                return None;
+                return Err("no step size");
             }
 
-            let ball_line = Line {
-                point: ball.ball_in_ground,
-                direction: ball.ball_in_ground_velocity,
+            let interception_point = match &world_state.ball_trajectory {
+                Some(trajectory) => trajectory
+                    .closest_approach(Point::origin())
+                    .map_or(ball.ball_in_ground, |state| state.position),
+                None => {
+                    let ball_line = Line {
+                        point: ball.ball_in_ground,
+                        direction: ball.ball_in_ground_velocity,
+                    };
+                    ball_line.closest_point(Point::origin())
+                }
             };
-            let interception_point = ball_line.closest_point(Point::origin());
 
             if interception_point.coords().norm() > parameters.maximum_intercept_distance {
-                return None;
+                return Err("interception point too far");
             }
 
             let path = vec![PathSegment::LineSegment(LineSegment(
@@ -79,7 +88,7 @@ pub fn execute(
                 interception_point,
             ))];
 
-            Some(MotionCommand::Walk {
+            Ok(MotionCommand::Walk {
                 head: HeadMotion::LookAt {
                     target: ball.ball_in_ground,
                     image_region_target: ImageRegion::Center,
@@ -92,7 +101,7 @@ pub fn execute(
                 speed: walk_speed,
             })
         }
-        _ => None,
+        _ => Err("ball not free, not seen or not localized"),
     }
 }
 
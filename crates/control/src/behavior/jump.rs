@@ -4,19 +4,20 @@ use types::{
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
-    world_state
-        .ball
-        .and_then(|ball| match ball.penalty_shot_direction {
-            Some(PenaltyShotDirection::Left) => Some(MotionCommand::Jump {
-                direction: JumpDirection::Left,
-            }),
-            Some(PenaltyShotDirection::Right) => Some(MotionCommand::Jump {
-                direction: JumpDirection::Right,
-            }),
-            Some(PenaltyShotDirection::Center) => Some(MotionCommand::Jump {
-                direction: JumpDirection::Center,
-            }),
-            Some(PenaltyShotDirection::NotMoving) | None => None,
-        })
+use super::ActionResult;
+
+pub fn execute(world_state: &WorldState) -> ActionResult {
+    let ball = world_state.ball.ok_or("ball not seen")?;
+    match ball.penalty_shot_direction {
+        Some(PenaltyShotDirection::Left) => Ok(MotionCommand::Jump {
+            direction: JumpDirection::Left,
+        }),
+        Some(PenaltyShotDirection::Right) => Ok(MotionCommand::Jump {
+            direction: JumpDirection::Right,
+        }),
+        Some(PenaltyShotDirection::Center) => Ok(MotionCommand::Jump {
+            direction: JumpDirection::Center,
+        }),
+        Some(PenaltyShotDirection::NotMoving) | None => Err("no penalty shot"),
+    }
 }
@@ -6,7 +6,9 @@ use types::{
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+use super::ActionResult;
+
+pub fn execute(world_state: &WorldState) -> ActionResult {
     match (
         &world_state.filtered_game_controller_state,
         world_state.robot.primary_state,
@@ -17,12 +19,12 @@ pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
                 ..
             }),
             _,
-        ) => None,
+        ) => Err("in penalty shootout"),
         (_, PrimaryState::Ready | PrimaryState::Set | PrimaryState::Playing) => {
-            Some(MotionCommand::Stand {
+            Ok(MotionCommand::Stand {
                 head: HeadMotion::LookAround,
             })
         }
-        _ => None,
+        _ => Err("not in Ready, Set or Playing"),
     }
 }
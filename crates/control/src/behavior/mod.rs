@@ -1,3 +1,5 @@
+use types::motion_command::MotionCommand;
+
 mod animation;
 mod calibrate;
 mod defend;
@@ -13,6 +15,7 @@ mod no_ground_contact;
 pub mod node;
 mod penalize;
 mod prepare_jump;
+mod priority_list;
 mod search;
 mod sit_down;
 mod stand;
@@ -22,3 +25,6 @@ mod unstiff;
 mod walk_to_kick_off;
 mod walk_to_penalty_kick;
 pub mod walk_to_pose;
+
+/// Motion command of an action or the reason why the action does not apply in this cycle
+pub type ActionResult = Result<MotionCommand, &'static str>;
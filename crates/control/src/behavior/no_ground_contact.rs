@@ -3,11 +3,13 @@ use types::{
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+use super::ActionResult;
+
+pub fn execute(world_state: &WorldState) -> ActionResult {
     if world_state.robot.has_ground_contact {
-        return None;
+        return Err("has ground contact");
     }
-    Some(MotionCommand::Stand {
+    Ok(MotionCommand::Stand {
         head: HeadMotion::Center,
     })
 }
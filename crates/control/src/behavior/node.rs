@@ -1,24 +1,23 @@
 use std::time::SystemTime;
 
-use color_eyre::Result;
+use color_eyre::{eyre::WrapErr, Result};
 use serde::{Deserialize, Serialize};
 
 use context_attribute::context;
 use coordinate_systems::Field;
 use framework::{AdditionalOutput, MainOutput};
 use linear_algebra::{point, Point2};
-use spl_network_messages::{GamePhase, PlayerNumber, SubState, Team};
+use log::warn;
 use types::{
     action::Action,
     cycle_time::CycleTime,
+    decision_trace::ActionDecision,
     dribble_path_plan::DribblePathPlan,
     field_dimensions::{FieldDimensions, Side},
-    filtered_game_controller_state::FilteredGameControllerState,
-    filtered_game_state::FilteredGameState,
     kick_decision::DecisionParameters,
     motion_command::{MotionCommand, WalkSpeed},
     parameters::{
-        BehaviorParameters, InWalkKicksParameters, InterceptBallParameters, KeeperMotionParameters,
+        BehaviorParameters, BehaviorRule, InWalkKicksParameters, InterceptBallParameters,
         LostBallParameters,
     },
     path_obstacles::PathObstacle,
@@ -34,8 +33,9 @@ use super::{
     dribble, fall_safely,
     head::LookAction,
     initial, intercept_ball, jump, look_around, lost_ball, no_ground_contact, penalize,
-    prepare_jump, search, sit_down, stand, stand_up, support, unstiff, walk_to_kick_off,
-    walk_to_penalty_kick,
+    prepare_jump,
+    priority_list::{select_actions, validate, RuleState},
+    search, sit_down, stand, stand_up, support, unstiff, walk_to_kick_off, walk_to_penalty_kick,
     walk_to_pose::{WalkAndStand, WalkPathPlanner},
 };
 
@@ -45,10 +45,15 @@ pub struct Behavior {
     active_since: Option<SystemTime>,
     previous_role: Role,
     last_defender_mode: DefendMode,
+    /// Last priority list from the parameters which passed validation
+    priority_list: Vec<BehaviorRule>,
+    rejected_priority_list: Option<Vec<BehaviorRule>>,
 }
 
 #[context]
-pub struct CreationContext {}
+pub struct CreationContext {
+    parameters: Parameter<BehaviorParameters, "behavior">,
+}
 
 #[context]
 pub struct CycleContext {
@@ -67,7 +72,6 @@ pub struct CycleContext {
     intercept_ball_parameters: Parameter<InterceptBallParameters, "behavior.intercept_ball">,
     maximum_step_size: Parameter<Step, "step_planner.max_step_size">,
     enable_pose_detection: Parameter<bool, "pose_detection.enable">,
-    keeper_motion: Parameter<KeeperMotionParameters, "keeper_motion">,
     use_stand_head_unstiff_calibration:
         Parameter<bool, "calibration_controller.use_stand_head_unstiff_calibration">,
 
@@ -82,6 +86,7 @@ pub struct CycleContext {
 
     path_obstacles_output: AdditionalOutput<Vec<PathObstacle>, "path_obstacles">,
     active_action_output: AdditionalOutput<Action, "active_action">,
+    decision_trace_output: AdditionalOutput<Vec<ActionDecision>, "decision_trace">,
 
     last_motion_command: CyclerState<MotionCommand, "last_motion_command">,
 }
@@ -93,16 +98,20 @@ pub struct MainOutputs {
 }
 
 impl Behavior {
-    pub fn new(_context: CreationContext) -> Result<Self> {
+    pub fn new(context: CreationContext) -> Result<Self> {
+        validate(&context.parameters.priority_list).wrap_err("invalid behavior priority list")?;
         Ok(Self {
             last_known_ball_position: point![0.0, 0.0],
             active_since: None,
             previous_role: Role::Searcher,
             last_defender_mode: DefendMode::Passive,
+            priority_list: context.parameters.priority_list.clone(),
+            rejected_priority_list: None,
         })
     }
 
     pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        self.update_priority_list(&context.parameters.priority_list);
         let world_state = context.world_state;
         if let Some(command) = &context.parameters.injected_motion_command {
             return Ok(MainOutputs {
@@ -132,101 +141,18 @@ impl Behavior {
             self.previous_role = context.world_state.robot.role;
         }
 
-        let mut actions = vec![
-            Action::Unstiff,
-            Action::Animation,
-            Action::SitDown,
-            Action::Penalize,
-            Action::Initial,
-            Action::FallSafely,
-            Action::StandUp,
-            Action::NoGroundContact,
-            Action::Stand,
-            Action::Calibrate,
-        ];
-
-        if let Some(active_since) = self.active_since {
-            let duration_active = now.duration_since(active_since)?;
-            if !context.is_localization_converged
-                && (duration_active < context.parameters.maximum_lookaround_duration)
-            {
-                actions.push(Action::LookAround);
-            }
-        }
-
-        if matches!(world_state.robot.player_number, PlayerNumber::One) {
-            actions.push(Action::KeeperMotion);
-        }
-        actions.push(Action::InterceptBall);
-
-        match world_state.robot.role {
-            Role::DefenderLeft => match world_state.filtered_game_controller_state {
-                Some(FilteredGameControllerState {
-                    sub_state: Some(SubState::CornerKick),
-                    kicking_team: Some(Team::Opponent),
-                    ..
-                }) => actions.push(Action::DefendOpponentCornerKick { side: Side::Left }),
-                _ => actions.push(Action::DefendLeft),
-            },
-            Role::DefenderRight => match world_state.filtered_game_controller_state {
-                Some(FilteredGameControllerState {
-                    sub_state: Some(SubState::CornerKick),
-                    kicking_team: Some(Team::Opponent),
-                    ..
-                }) => actions.push(Action::DefendOpponentCornerKick { side: Side::Right }),
-                _ => actions.push(Action::DefendRight),
-            },
-            Role::Keeper => match world_state.filtered_game_controller_state {
-                Some(FilteredGameControllerState {
-                    game_phase: GamePhase::PenaltyShootout { .. },
-                    ..
-                })
-                | Some(FilteredGameControllerState {
-                    game_state: FilteredGameState::Playing { .. },
-                    kicking_team: Some(Team::Opponent),
-                    sub_state: Some(SubState::PenaltyKick),
-                    ..
-                }) => {
-                    actions.push(Action::Jump);
-                    actions.push(Action::PrepareJump);
-                }
-                _ => actions.push(Action::DefendGoal),
+        let active_duration = self
+            .active_since
+            .map(|active_since| now.duration_since(active_since))
+            .transpose()?;
+        let actions = select_actions(
+            &self.priority_list,
+            &RuleState {
+                world_state,
+                is_localization_converged: *context.is_localization_converged,
+                active_duration,
             },
-            Role::Loser => actions.push(Action::SearchForLostBall),
-            Role::MidfielderLeft => actions.push(Action::SupportLeft),
-            Role::MidfielderRight => actions.push(Action::SupportRight),
-            Role::ReplacementKeeper => actions.push(Action::DefendGoal),
-            Role::Searcher => actions.push(Action::Search),
-            Role::Striker => match world_state.filtered_game_controller_state {
-                None
-                | Some(FilteredGameControllerState {
-                    game_state:
-                        FilteredGameState::Playing {
-                            ball_is_free: true, ..
-                        },
-                    ..
-                }) => {
-                    actions.push(Action::Dribble);
-                }
-                Some(FilteredGameControllerState {
-                    game_state: FilteredGameState::Ready,
-                    kicking_team: Some(Team::Hulks),
-                    sub_state,
-                    ..
-                }) => match sub_state {
-                    Some(SubState::PenaltyKick) => actions.push(Action::WalkToPenaltyKick),
-                    _ => actions.push(Action::WalkToKickOff),
-                },
-                Some(FilteredGameControllerState {
-                    game_state: FilteredGameState::Ready | FilteredGameState::Playing { .. },
-                    sub_state: Some(SubState::PenaltyKick),
-                    kicking_team: Some(Team::Opponent),
-                    ..
-                }) => actions.push(Action::DefendPenaltyKick),
-                _ => actions.push(Action::DefendKickOff),
-            },
-            Role::StrikerSupporter => actions.push(Action::SupportStriker),
-        };
+        );
 
         let walk_path_planner = WalkPathPlanner::new(
             context.field_dimensions,
@@ -250,10 +176,11 @@ impl Behavior {
             &mut self.last_defender_mode,
         );
 
+        let mut rejections = Vec::new();
         let (action, motion_command) = actions
             .iter()
             .find_map(|action| {
-                let motion_command = match action {
+                let result = match action {
                     Action::Animation => animation::execute(world_state),
                     Action::Unstiff => unstiff::execute(world_state),
                     Action::SitDown => sit_down::execute(world_state),
@@ -269,7 +196,7 @@ impl Behavior {
                     Action::StandUp => stand_up::execute(world_state),
                     Action::NoGroundContact => no_ground_contact::execute(world_state),
                     Action::LookAround => look_around::execute(world_state),
-                    Action::KeeperMotion => defend.keeper_motion(context.keeper_motion.clone()),
+                    Action::KeeperMotion => defend.keeper_motion(),
                     Action::InterceptBall => intercept_ball::execute(
                         world_state,
                         *context.intercept_ball_parameters,
@@ -389,6 +316,7 @@ impl Behavior {
                             .role_positions
                             .left_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
                         context.parameters.role_positions.left_midfielder_minimum_x,
+                        &context.parameters.receive_pass,
                         &walk_and_stand,
                         &look_action,
                         &mut context.path_obstacles_output,
@@ -411,6 +339,7 @@ impl Behavior {
                             .role_positions
                             .right_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
                         context.parameters.role_positions.right_midfielder_minimum_x,
+                        &context.parameters.receive_pass,
                         &walk_and_stand,
                         &look_action,
                         &mut context.path_obstacles_output,
@@ -436,6 +365,7 @@ impl Behavior {
                             .parameters
                             .role_positions
                             .striker_supporter_minimum_x,
+                        &context.parameters.receive_pass,
                         &walk_and_stand,
                         &look_action,
                         &mut context.path_obstacles_output,
@@ -470,8 +400,14 @@ impl Behavior {
                             .walk_and_stand
                             .normal_distance_to_be_aligned,
                     ),
-                }?;
-                Some((action, motion_command))
+                };
+                match result {
+                    Ok(motion_command) => Some((action, motion_command)),
+                    Err(reason) => {
+                        rejections.push((*action, reason));
+                        None
+                    }
+                }
             })
             .unwrap_or_else(|| {
                 panic!(
@@ -479,6 +415,19 @@ impl Behavior {
                 )
             });
         context.active_action_output.fill_if_subscribed(|| *action);
+        context.decision_trace_output.fill_if_subscribed(|| {
+            rejections
+                .iter()
+                .map(|(action, reason)| ActionDecision {
+                    action: *action,
+                    rejection_reason: Some(reason.to_string()),
+                })
+                .chain([ActionDecision {
+                    action: *action,
+                    rejection_reason: None,
+                }])
+                .collect()
+        });
 
         *context.last_motion_command = motion_command.clone();
 
@@ -486,4 +435,24 @@ impl Behavior {
             motion_command: motion_command.into(),
         })
     }
+
+    /// Takes over changed priority lists from the parameters only if they are valid, an invalid
+    /// list is reported once and the last valid one is kept
+    fn update_priority_list(&mut self, priority_list: &[BehaviorRule]) {
+        if priority_list == self.priority_list
+            || self.rejected_priority_list.as_deref() == Some(priority_list)
+        {
+            return;
+        }
+        match validate(priority_list) {
+            Ok(()) => {
+                self.priority_list = priority_list.to_vec();
+                self.rejected_priority_list = None;
+            }
+            Err(error) => {
+                warn!("keeping last valid behavior priority list: {error:#}");
+                self.rejected_priority_list = Some(priority_list.to_vec());
+            }
+        }
+    }
 }
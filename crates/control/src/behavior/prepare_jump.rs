@@ -1,5 +1,7 @@
 use types::{motion_command::MotionCommand, world_state::WorldState};
 
-pub fn execute(_world_state: &WorldState) -> Option<MotionCommand> {
-    Some(MotionCommand::ArmsUpSquat)
+use super::ActionResult;
+
+pub fn execute(_world_state: &WorldState) -> ActionResult {
+    Ok(MotionCommand::ArmsUpSquat)
 }
@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use color_eyre::{eyre::bail, Result};
+
+use spl_network_messages::GamePhase;
+use types::{
+    action::Action,
+    filtered_game_controller_state::FilteredGameControllerState,
+    filtered_game_state::FilteredGameState,
+    parameters::{BehaviorCondition, BehaviorRule},
+    roles::Role,
+    world_state::WorldState,
+};
+
+const ROLES: [Role; 10] = [
+    Role::DefenderLeft,
+    Role::DefenderRight,
+    Role::Keeper,
+    Role::Loser,
+    Role::MidfielderLeft,
+    Role::MidfielderRight,
+    Role::ReplacementKeeper,
+    Role::Searcher,
+    Role::Striker,
+    Role::StrikerSupporter,
+];
+
+pub struct RuleState<'a> {
+    pub world_state: &'a WorldState,
+    pub is_localization_converged: bool,
+    /// Time since the robot entered Ready, Set or Playing
+    pub active_duration: Option<Duration>,
+}
+
+pub fn select_actions(rules: &[BehaviorRule], state: &RuleState) -> Vec<Action> {
+    let mut actions = Vec::new();
+    for rule in rules {
+        apply_rule(rule, state, &mut actions);
+    }
+    actions
+}
+
+fn apply_rule(rule: &BehaviorRule, state: &RuleState, actions: &mut Vec<Action>) -> bool {
+    if !rule
+        .conditions
+        .iter()
+        .all(|condition| is_fulfilled(condition, state))
+    {
+        return false;
+    }
+    actions.extend_from_slice(&rule.actions);
+    for child in &rule.first_of {
+        if apply_rule(child, state, actions) {
+            break;
+        }
+    }
+    true
+}
+
+fn is_fulfilled(condition: &BehaviorCondition, state: &RuleState) -> bool {
+    let game_controller_state = state.world_state.filtered_game_controller_state.as_ref();
+    match condition {
+        BehaviorCondition::Role(role) => state.world_state.robot.role == *role,
+        BehaviorCondition::PlayerNumber(player_number) => {
+            state.world_state.robot.player_number == *player_number
+        }
+        BehaviorCondition::ActiveShorterThan(maximum) => state
+            .active_duration
+            .is_some_and(|duration| duration < *maximum),
+        BehaviorCondition::LocalizationConverged => state.is_localization_converged,
+        BehaviorCondition::NoGameControllerState => game_controller_state.is_none(),
+        BehaviorCondition::Ready => game_controller_state.is_some_and(|game_controller_state| {
+            game_controller_state.game_state == FilteredGameState::Ready
+        }),
+        BehaviorCondition::Playing => game_controller_state.is_some_and(|game_controller_state| {
+            matches!(
+                game_controller_state.game_state,
+                FilteredGameState::Playing { .. }
+            )
+        }),
+        BehaviorCondition::BallIsFree => matches!(
+            game_controller_state,
+            Some(FilteredGameControllerState {
+                game_state: FilteredGameState::Playing {
+                    ball_is_free: true,
+                    ..
+                },
+                ..
+            })
+        ),
+        BehaviorCondition::PenaltyShootout => matches!(
+            game_controller_state,
+            Some(FilteredGameControllerState {
+                game_phase: GamePhase::PenaltyShootout { .. },
+                ..
+            })
+        ),
+        BehaviorCondition::SubState(sub_state) => {
+            game_controller_state.is_some_and(|game_controller_state| {
+                game_controller_state.sub_state == Some(*sub_state)
+            })
+        }
+        BehaviorCondition::KickingTeam(team) => game_controller_state
+            .is_some_and(|game_controller_state| game_controller_state.kicking_team == Some(*team)),
+        BehaviorCondition::Not(condition) => !is_fulfilled(condition, state),
+    }
+}
+
+/// Rejects priority lists in which rules are empty or a role is never mentioned, since a robot in
+/// such a role would only stand
+pub fn validate(rules: &[BehaviorRule]) -> Result<()> {
+    if rules.is_empty() {
+        bail!("behavior priority list is empty");
+    }
+    let mut mentioned_roles = Vec::new();
+    validate_rules(rules, &mut mentioned_roles)?;
+    if let Some(role) = ROLES.iter().find(|role| !mentioned_roles.contains(role)) {
+        bail!("behavior priority list has no rule for role {role:?}");
+    }
+    Ok(())
+}
+
+fn validate_rules(rules: &[BehaviorRule], mentioned_roles: &mut Vec<Role>) -> Result<()> {
+    for rule in rules {
+        if rule.actions.is_empty() && rule.first_of.is_empty() {
+            bail!(
+                "behavior rule with conditions {:?} has no actions",
+                rule.conditions
+            );
+        }
+        mentioned_roles.extend(
+            rule.conditions
+                .iter()
+                .filter_map(|condition| match condition {
+                    BehaviorCondition::Role(role) => Some(*role),
+                    _ => None,
+                }),
+        );
+        validate_rules(&rule.first_of, mentioned_roles)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use spl_network_messages::{PlayerNumber, SubState, Team};
+    use types::field_dimensions::Side;
+
+    use super::*;
+
+    fn rule(conditions: Vec<BehaviorCondition>, actions: Vec<Action>) -> BehaviorRule {
+        BehaviorRule {
+            conditions,
+            actions,
+            first_of: Vec::new(),
+        }
+    }
+
+    fn defender_rules() -> Vec<BehaviorRule> {
+        vec![
+            rule(vec![], vec![Action::Unstiff, Action::Stand]),
+            rule(
+                vec![BehaviorCondition::PlayerNumber(PlayerNumber::One)],
+                vec![Action::KeeperMotion],
+            ),
+            BehaviorRule {
+                conditions: vec![BehaviorCondition::Role(Role::DefenderLeft)],
+                actions: vec![],
+                first_of: vec![
+                    rule(
+                        vec![
+                            BehaviorCondition::SubState(SubState::CornerKick),
+                            BehaviorCondition::KickingTeam(Team::Opponent),
+                        ],
+                        vec![Action::DefendOpponentCornerKick { side: Side::Left }],
+                    ),
+                    rule(vec![], vec![Action::DefendLeft]),
+                ],
+            },
+        ]
+    }
+
+    fn state(world_state: &WorldState) -> RuleState<'_> {
+        RuleState {
+            world_state,
+            is_localization_converged: true,
+            active_duration: None,
+        }
+    }
+
+    #[test]
+    fn actions_of_matching_rules_are_concatenated() {
+        let mut world_state = WorldState::default();
+        world_state.robot.role = Role::DefenderLeft;
+        world_state.robot.player_number = PlayerNumber::Two;
+
+        assert_eq!(
+            select_actions(&defender_rules(), &state(&world_state)),
+            vec![Action::Unstiff, Action::Stand, Action::DefendLeft]
+        );
+    }
+
+    #[test]
+    fn only_first_matching_alternative_is_applied() {
+        let mut world_state = WorldState::default();
+        world_state.robot.role = Role::DefenderLeft;
+        world_state.robot.player_number = PlayerNumber::One;
+        world_state.filtered_game_controller_state = Some(FilteredGameControllerState {
+            sub_state: Some(SubState::CornerKick),
+            kicking_team: Some(Team::Opponent),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            select_actions(&defender_rules(), &state(&world_state)),
+            vec![
+                Action::Unstiff,
+                Action::Stand,
+                Action::KeeperMotion,
+                Action::DefendOpponentCornerKick { side: Side::Left }
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_without_rules_for_every_role_are_rejected() {
+        assert!(validate(&[]).is_err());
+        assert!(validate(&defender_rules()).is_err());
+
+        let mut rules = defender_rules();
+        rules.extend(
+            ROLES
+                .iter()
+                .map(|role| rule(vec![BehaviorCondition::Role(*role)], vec![Action::Stand])),
+        );
+        assert!(validate(&rules).is_ok());
+        rules.push(rule(vec![], vec![]));
+        assert!(validate(&rules).is_err());
+    }
+}
@@ -3,7 +3,7 @@ use framework::AdditionalOutput;
 use linear_algebra::{point, Isometry2, Orientation2, Point2, Pose2};
 use types::{
     field_dimensions::FieldDimensions,
-    motion_command::{HeadMotion, MotionCommand, OrientationMode, WalkSpeed},
+    motion_command::{HeadMotion, OrientationMode, WalkSpeed},
     parameters::SearchParameters,
     path_obstacles::PathObstacle,
     roles::Role,
@@ -11,7 +11,10 @@ use types::{
     world_state::WorldState,
 };
 
-use super::walk_to_pose::{WalkAndStand, WalkPathPlanner};
+use super::{
+    walk_to_pose::{WalkAndStand, WalkPathPlanner},
+    ActionResult,
+};
 
 #[derive(Clone, Copy)]
 enum SearchRole {
@@ -75,12 +78,18 @@ pub fn execute(
     previous_role: Role,
     walk_speed: WalkSpeed,
     distance_to_be_aligned: f32,
-) -> Option<MotionCommand> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> ActionResult {
+    let ground_to_field = world_state.robot.ground_to_field.ok_or("not localized")?;
     let search_role = assign_search_role(world_state);
-    let search_position = match (world_state.suggested_search_position, previous_role) {
-        (Some(_), Role::Striker | Role::StrikerSupporter) => {
-            ground_to_field.inverse() * world_state.suggested_search_position.unwrap()
+    // The suggestion lies within the search region of this robot, see `search_suggestor`
+    let search_position = match (world_state.suggested_search_position, search_role) {
+        (Some(suggested_search_position), Some(role)) if !matches!(role, SearchRole::Goal) => {
+            ground_to_field.inverse() * suggested_search_position
+        }
+        (Some(suggested_search_position), None)
+            if matches!(previous_role, Role::Striker | Role::StrikerSupporter) =>
+        {
+            ground_to_field.inverse() * suggested_search_position
         }
         _ => search_role
             .map(|role| role.to_position(ground_to_field, field_dimensions))
@@ -127,7 +136,7 @@ pub fn execute(
         } else {
             OrientationMode::AlignWithPath
         };
-        Some(walk_path_planner.walk_with_obstacle_avoiding_arms(
+        Ok(walk_path_planner.walk_with_obstacle_avoiding_arms(
             head,
             orientation_mode,
             path,
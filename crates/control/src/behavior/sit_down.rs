@@ -4,11 +4,13 @@ use types::{
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+use super::ActionResult;
+
+pub fn execute(world_state: &WorldState) -> ActionResult {
     match world_state.robot.primary_state {
-        PrimaryState::Finished => Some(MotionCommand::SitDown {
+        PrimaryState::Finished => Ok(MotionCommand::SitDown {
             head: HeadMotion::Unstiff,
         }),
-        _ => None,
+        _ => Err("not finished"),
     }
 }
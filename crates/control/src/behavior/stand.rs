@@ -8,17 +8,19 @@ use types::{
     world_state::WorldState,
 };
 
+use super::ActionResult;
+
 pub fn execute(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
     role: &Role,
-) -> Option<MotionCommand> {
+) -> ActionResult {
     match world_state.robot.primary_state {
-        PrimaryState::Initial => Some(MotionCommand::Stand {
+        PrimaryState::Initial => Ok(MotionCommand::Stand {
             head: HeadMotion::ZeroAngles,
         }),
         PrimaryState::Set => {
-            let ground_to_field = world_state.robot.ground_to_field?;
+            let ground_to_field = world_state.robot.ground_to_field.ok_or("not localized")?;
             let (fallback_target, is_opponent_penalty_kick) = match world_state
                 .filtered_game_controller_state
             {
@@ -52,7 +54,7 @@ pub fn execute(
                         true,
                     ),
                     _ => {
-                        return Some(MotionCommand::Stand {
+                        return Ok(MotionCommand::Stand {
                             head: HeadMotion::SearchForLostBall,
                         })
                     }
@@ -64,14 +66,14 @@ pub fn execute(
                 .map(|state| state.ball_in_ground)
                 .unwrap_or(fallback_target);
             match (role, is_opponent_penalty_kick) {
-                (Role::Keeper, true) => Some(MotionCommand::ArmsUpStand {
+                (Role::Keeper, true) => Ok(MotionCommand::ArmsUpStand {
                     head: HeadMotion::LookAt {
                         target,
                         image_region_target: Default::default(),
                         camera: None,
                     },
                 }),
-                _ => Some(MotionCommand::Stand {
+                _ => Ok(MotionCommand::Stand {
                     head: HeadMotion::LookAt {
                         target,
                         image_region_target: Default::default(),
@@ -103,7 +105,8 @@ pub fn execute(
                     Role::Striker,
                     None,
                 ) => {
-                    let ground_to_field = world_state.robot.ground_to_field?;
+                    let ground_to_field =
+                        world_state.robot.ground_to_field.ok_or("not localized")?;
                     let target = match kicking_team {
                         Some(Team::Hulks) => world_state
                             .ball
@@ -121,13 +124,13 @@ pub fn execute(
                                 ground_to_field.inverse() * field_dimensions.penalty_spot(Half::Own)
                             }),
                         _ => {
-                            return Some(MotionCommand::Stand {
+                            return Ok(MotionCommand::Stand {
                                 head: HeadMotion::SearchForLostBall,
                             })
                         }
                     };
 
-                    Some(MotionCommand::Stand {
+                    Ok(MotionCommand::Stand {
                         head: HeadMotion::LookAt {
                             target,
                             image_region_target: ImageRegion::Center,
@@ -135,9 +138,9 @@ pub fn execute(
                         },
                     })
                 }
-                _ => None,
+                _ => Err("not a striker without ball in a penalty kick"),
             }
         }
-        _ => None,
+        _ => Err("not in Initial, Set or Playing"),
     }
 }
@@ -1,9 +1,11 @@
 use types::{fall_state::FallState, motion_command::MotionCommand, world_state::WorldState};
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+use super::ActionResult;
+
+pub fn execute(world_state: &WorldState) -> ActionResult {
     match world_state.robot.fall_state {
-        FallState::Fallen { kind } => Some(MotionCommand::StandUp { kind }),
-        FallState::StandingUp { kind, .. } => Some(MotionCommand::StandUp { kind }),
-        _ => None,
+        FallState::Fallen { kind } => Ok(MotionCommand::StandUp { kind }),
+        FallState::StandingUp { kind, .. } => Ok(MotionCommand::StandUp { kind }),
+        _ => Err("not fallen"),
     }
 }
@@ -1,20 +1,21 @@
-use std::f32::consts::FRAC_PI_4;
+use std::{f32::consts::FRAC_PI_4, iter::once};
 
 use coordinate_systems::{Field, Ground};
 use framework::AdditionalOutput;
-use geometry::look_at::LookAt;
-use linear_algebra::{point, Pose2, Rotation2, Vector2};
+use geometry::{circle::Circle, line_segment::LineSegment, look_at::LookAt};
+use linear_algebra::{distance, point, Point2, Pose2, Rotation2, Vector2};
 use spl_network_messages::SubState;
 use types::{
     field_dimensions::{FieldDimensions, Side},
     filtered_game_controller_state::FilteredGameControllerState,
     filtered_game_state::FilteredGameState,
-    motion_command::{MotionCommand, WalkSpeed},
+    motion_command::WalkSpeed,
+    parameters::ReceivePassParameters,
     path_obstacles::PathObstacle,
     world_state::{BallState, WorldState},
 };
 
-use super::{head::LookAction, walk_to_pose::WalkAndStand};
+use super::{head::LookAction, walk_to_pose::WalkAndStand, ActionResult};
 
 #[allow(clippy::too_many_arguments)]
 pub fn execute(
@@ -24,12 +25,13 @@ pub fn execute(
     distance_to_ball: f32,
     maximum_x_in_ready_and_when_ball_is_not_free: f32,
     minimum_x: f32,
+    receive_pass_parameters: &ReceivePassParameters,
     walk_and_stand: &WalkAndStand,
     look_action: &LookAction,
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
     walk_speed: WalkSpeed,
     distance_to_be_aligned: f32,
-) -> Option<MotionCommand> {
+) -> ActionResult {
     let pose = support_pose(
         world_state,
         field_dimensions,
@@ -37,7 +39,9 @@ pub fn execute(
         distance_to_ball,
         maximum_x_in_ready_and_when_ball_is_not_free,
         minimum_x,
-    )?;
+        receive_pass_parameters,
+    )
+    .ok_or("not localized")?;
     walk_and_stand.execute(
         pose,
         look_action.execute(),
@@ -55,6 +59,7 @@ fn support_pose(
     distance_to_ball: f32,
     maximum_x_in_ready_and_when_ball_is_not_free: f32,
     minimum_x: f32,
+    receive_pass_parameters: &ReceivePassParameters,
 ) -> Option<Pose2<Ground>> {
     let ground_to_field = world_state.robot.ground_to_field?;
     let ball = world_state
@@ -143,9 +148,65 @@ fn support_pose(
     };
 
     let clamped_position = point![clamped_x, clamped_y];
+    let is_playing = matches!(filtered_game_state, Some(FilteredGameState::Playing { .. }));
+    let receiving_position = if receive_pass_parameters.enable && is_playing {
+        let obstacles: Vec<_> = world_state
+            .obstacles
+            .iter()
+            .map(|obstacle| {
+                Circle::new(
+                    ground_to_field * obstacle.position,
+                    obstacle.radius_at_foot_height + receive_pass_parameters.obstacle_clearance,
+                )
+            })
+            .filter(|obstacle| {
+                distance(obstacle.center, ball.ball_in_field)
+                    > receive_pass_parameters.minimum_obstacle_distance_to_ball
+            })
+            .collect();
+        open_passing_lane(
+            clamped_position,
+            ball.ball_in_field,
+            &obstacles,
+            field_dimensions,
+            receive_pass_parameters,
+        )
+    } else {
+        clamped_position
+    };
     let support_pose = Pose2::new(
-        clamped_position,
-        clamped_position.look_at(&ball.ball_in_field).angle(),
+        receiving_position,
+        receiving_position.look_at(&ball.ball_in_field).angle(),
     );
     Some(ground_to_field.inverse() * support_pose)
 }
+
+/// Rotates the position around the ball until no obstacle blocks a pass from the ball
+fn open_passing_lane(
+    position: Point2<Field>,
+    ball_position: Point2<Field>,
+    obstacles: &[Circle<Field>],
+    field_dimensions: &FieldDimensions,
+    parameters: &ReceivePassParameters,
+) -> Point2<Field> {
+    if parameters.angle_step <= 0.0 {
+        return position;
+    }
+    let number_of_steps = (parameters.maximum_angle_offset / parameters.angle_step) as usize;
+    let angles = (1..=number_of_steps).flat_map(|step| {
+        let angle = step as f32 * parameters.angle_step;
+        [angle, -angle]
+    });
+
+    once(0.0)
+        .chain(angles)
+        .map(|angle| ball_position + Rotation2::new(angle) * (position - ball_position))
+        .filter(|candidate| field_dimensions.is_inside_field(*candidate))
+        .find(|candidate| {
+            let passing_lane = LineSegment::new(ball_position, *candidate);
+            obstacles
+                .iter()
+                .all(|obstacle| !obstacle.intersects_line_segment(&passing_lane))
+        })
+        .unwrap_or(position)
+}
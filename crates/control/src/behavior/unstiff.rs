@@ -1,8 +1,10 @@
 use types::{motion_command::MotionCommand, primary_state::PrimaryState, world_state::WorldState};
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+use super::ActionResult;
+
+pub fn execute(world_state: &WorldState) -> ActionResult {
     match world_state.robot.primary_state {
-        PrimaryState::Unstiff => Some(MotionCommand::Unstiff),
-        _ => None,
+        PrimaryState::Unstiff => Ok(MotionCommand::Unstiff),
+        _ => Err("not unstiff"),
     }
 }
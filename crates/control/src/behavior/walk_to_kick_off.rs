@@ -1,13 +1,9 @@
 use coordinate_systems::Field;
 use framework::AdditionalOutput;
 use linear_algebra::{Point2, Pose2, Rotation2};
-use types::{
-    motion_command::{MotionCommand, WalkSpeed},
-    path_obstacles::PathObstacle,
-    world_state::WorldState,
-};
+use types::{motion_command::WalkSpeed, path_obstacles::PathObstacle, world_state::WorldState};
 
-use super::{head::LookAction, walk_to_pose::WalkAndStand};
+use super::{head::LookAction, walk_to_pose::WalkAndStand, ActionResult};
 
 #[allow(clippy::too_many_arguments)]
 pub fn execute(
@@ -19,8 +15,8 @@ pub fn execute(
     kick_off_angle: f32,
     walk_speed: WalkSpeed,
     distance_to_be_aligned: f32,
-) -> Option<MotionCommand> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> ActionResult {
+    let ground_to_field = world_state.robot.ground_to_field.ok_or("not localized")?;
     let kick_off_pose =
         Rotation2::<Field, Field>::new(-kick_off_angle) * Pose2::from(kickoff_position);
     walk_and_stand.execute(
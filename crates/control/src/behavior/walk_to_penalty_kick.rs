@@ -1,13 +1,11 @@
 use framework::AdditionalOutput;
 use linear_algebra::{point, Pose2};
 use types::{
-    field_dimensions::FieldDimensions,
-    motion_command::{MotionCommand, WalkSpeed},
-    path_obstacles::PathObstacle,
+    field_dimensions::FieldDimensions, motion_command::WalkSpeed, path_obstacles::PathObstacle,
     world_state::WorldState,
 };
 
-use super::{head::LookAction, walk_to_pose::WalkAndStand};
+use super::{head::LookAction, walk_to_pose::WalkAndStand, ActionResult};
 
 pub fn execute(
     world_state: &WorldState,
@@ -17,8 +15,8 @@ pub fn execute(
     field_dimensions: &FieldDimensions,
     walk_speed: WalkSpeed,
     distance_to_be_aligned: f32,
-) -> Option<MotionCommand> {
-    let ground_to_field = world_state.robot.ground_to_field?;
+) -> ActionResult {
+    let ground_to_field = world_state.robot.ground_to_field.ok_or("not localized")?;
     let kick_off_pose = Pose2::from(point![
         field_dimensions.length / 2.0
             - field_dimensions.penalty_marker_distance
@@ -18,6 +18,8 @@ use types::{
 
 use crate::path_planner::PathPlanner;
 
+use super::ActionResult;
+
 pub struct WalkPathPlanner<'cycle> {
     field_dimensions: &'cycle FieldDimensions,
     obstacles: &'cycle [Obstacle],
@@ -55,7 +57,16 @@ impl<'cycle> WalkPathPlanner<'cycle> {
             self.last_motion_command,
             self.parameters.rotation_penalty_factor,
         );
-        planner.with_obstacles(obstacles, self.parameters.robot_radius_at_hip_height);
+        if self.parameters.moving_obstacles.enable {
+            planner.with_moving_obstacles(
+                obstacles,
+                self.parameters.robot_radius_at_hip_height,
+                self.parameters.line_walking_speed,
+                &self.parameters.moving_obstacles,
+            );
+        } else {
+            planner.with_obstacles(obstacles, self.parameters.robot_radius_at_hip_height);
+        }
         planner.with_rule_obstacles(
             ground_to_field.inverse(),
             rule_obstacles,
@@ -162,8 +173,12 @@ impl<'cycle> WalkAndStand<'cycle> {
         walk_speed: WalkSpeed,
         distance_to_be_aligned: f32,
         hysteresis: nalgebra::Vector2<f32>,
-    ) -> Option<MotionCommand> {
-        let ground_to_field = self.world_state.robot.ground_to_field?;
+    ) -> ActionResult {
+        let ground_to_field = self
+            .world_state
+            .robot
+            .ground_to_field
+            .ok_or("not localized")?;
         let distance_to_walk = target_pose.position().coords().norm();
         let angle_to_walk = target_pose.orientation().angle();
         let was_standing_last_cycle =
@@ -186,7 +201,7 @@ impl<'cycle> WalkAndStand<'cycle> {
         );
 
         if is_reached {
-            Some(MotionCommand::Stand { head })
+            Ok(MotionCommand::Stand { head })
         } else {
             let path = self.walk_path_planner.plan(
                 target_pose.position(),
@@ -197,7 +212,7 @@ impl<'cycle> WalkAndStand<'cycle> {
                 &self.world_state.rule_obstacles,
                 path_obstacles_output,
             );
-            Some(self.walk_path_planner.walk_with_obstacle_avoiding_arms(
+            Ok(self.walk_path_planner.walk_with_obstacle_avoiding_arms(
                 head,
                 orientation_mode,
                 path,
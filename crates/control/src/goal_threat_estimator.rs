@@ -0,0 +1,317 @@
+use std::f32::consts::SQRT_2;
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use coordinate_systems::{Field, Ground};
+use framework::{AdditionalOutput, MainOutput};
+use linear_algebra::Isometry2;
+use types::{
+    ball_trajectory::BallTrajectory,
+    field_dimensions::FieldDimensions,
+    goal_threat::{GoalThreat, KeeperDive, KeeperDiveOption},
+    parameters::{GoalThreatParameters, KeeperDiveParameters},
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct GoalThreatEstimator {
+    committed_dive: Option<KeeperDive>,
+}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    goal_threat: AdditionalOutput<Option<GoalThreat>, "goal_threat">,
+
+    ball_trajectory: Input<Option<BallTrajectory>, "ball_trajectory?">,
+    ground_to_field: Input<Option<Isometry2<Ground, Field>>, "ground_to_field?">,
+
+    field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
+    parameters: Parameter<GoalThreatParameters, "goal_threat_estimator">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub keeper_dive: MainOutput<Option<KeeperDive>>,
+}
+
+impl GoalThreatEstimator {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {
+            committed_dive: None,
+        })
+    }
+
+    pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        let goal_threat = context
+            .ball_trajectory
+            .zip(context.ground_to_field)
+            .and_then(|(ball_trajectory, ground_to_field)| {
+                estimate_goal_threat(
+                    ball_trajectory,
+                    *ground_to_field,
+                    context.field_dimensions,
+                    context.parameters,
+                )
+            });
+        // a started dive cannot be aborted, so it is kept until the ball passed the robot
+        let ball_is_approaching = goal_threat
+            .as_ref()
+            .is_some_and(|goal_threat| goal_threat.time_to_robot_line.is_some());
+        self.committed_dive = match self.committed_dive {
+            Some(dive) if ball_is_approaching => Some(dive),
+            _ => goal_threat
+                .as_ref()
+                .and_then(|goal_threat| choose_dive(goal_threat, context.parameters)),
+        };
+
+        context
+            .goal_threat
+            .fill_if_subscribed(|| goal_threat.clone());
+        Ok(MainOutputs {
+            keeper_dive: self.committed_dive.into(),
+        })
+    }
+}
+
+fn estimate_goal_threat(
+    trajectory: &BallTrajectory,
+    ground_to_field: Isometry2<Ground, Field>,
+    field_dimensions: &FieldDimensions,
+    parameters: &GoalThreatParameters,
+) -> Option<GoalThreat> {
+    let start = trajectory.states.first()?.position;
+    let standard_deviation_after = |travelled_distance: f32| {
+        parameters.position_standard_deviation
+            + parameters.heading_standard_deviation * travelled_distance
+    };
+
+    let goal_line_x = -field_dimensions.length / 2.0;
+    let (goal_line_crossing, time_to_goal_line, goal_line_standard_deviation) =
+        trajectory.states.windows(2).find_map(|window| {
+            let (from, to) = (
+                ground_to_field * window[0].position,
+                ground_to_field * window[1].position,
+            );
+            if from.x() < goal_line_x || to.x() >= goal_line_x {
+                return None;
+            }
+            let factor = (from.x() - goal_line_x) / (from.x() - to.x());
+            let crossing = from + (to - from) * factor;
+            let time = window[0].time + (window[1].time - window[0].time).mul_f32(factor);
+            let travelled_distance = (ground_to_field.inverse() * crossing - start).norm();
+            Some((crossing, time, standard_deviation_after(travelled_distance)))
+        })?;
+
+    let goal_half_width = field_dimensions.goal_inner_width / 2.0;
+    let on_target_probability = probability_within(
+        goal_line_crossing.y(),
+        goal_line_standard_deviation,
+        -goal_half_width,
+        goal_half_width,
+    );
+
+    let robot_line_crossing = trajectory.first_crossing_of_x(0.0);
+    let robot_line_standard_deviation = robot_line_crossing.map_or(0.0, |crossing| {
+        standard_deviation_after((crossing.position - start).norm())
+    });
+    let dive_options = robot_line_crossing
+        .map(|crossing| {
+            [
+                KeeperDive::WideStance,
+                KeeperDive::CenterJump,
+                KeeperDive::JumpLeft,
+                KeeperDive::JumpRight,
+            ]
+            .into_iter()
+            .map(|dive| {
+                let dive_parameters = dive_parameters(dive, parameters);
+                let (minimum, maximum) = covered_interval(dive, dive_parameters);
+                KeeperDiveOption {
+                    dive,
+                    is_reachable: dive_parameters.reaction_time <= crossing.time,
+                    save_probability: probability_within(
+                        crossing.position.y(),
+                        robot_line_standard_deviation,
+                        minimum,
+                        maximum,
+                    ),
+                }
+            })
+            .collect()
+        })
+        .unwrap_or_default();
+
+    Some(GoalThreat {
+        goal_line_crossing,
+        time_to_goal_line,
+        goal_line_standard_deviation,
+        on_target_probability,
+        robot_line_crossing: robot_line_crossing.map(|crossing| crossing.position),
+        time_to_robot_line: robot_line_crossing.map(|crossing| crossing.time),
+        robot_line_standard_deviation,
+        dive_options,
+    })
+}
+
+fn choose_dive(goal_threat: &GoalThreat, parameters: &GoalThreatParameters) -> Option<KeeperDive> {
+    let time_to_robot_line = goal_threat.time_to_robot_line?;
+    if goal_threat.on_target_probability < parameters.minimum_on_target_probability
+        || time_to_robot_line > parameters.maximum_time_to_robot_line
+    {
+        return None;
+    }
+    let reachable_options = || {
+        goal_threat
+            .dive_options
+            .iter()
+            .filter(|option| option.is_reachable)
+    };
+    reachable_options()
+        .find(|option| option.save_probability >= parameters.minimum_save_probability)
+        .or_else(|| {
+            reachable_options().max_by(|a, b| a.save_probability.total_cmp(&b.save_probability))
+        })
+        .map(|option| option.dive)
+}
+
+fn dive_parameters(dive: KeeperDive, parameters: &GoalThreatParameters) -> &KeeperDiveParameters {
+    match dive {
+        KeeperDive::WideStance => &parameters.wide_stance,
+        KeeperDive::CenterJump => &parameters.center_jump,
+        KeeperDive::JumpLeft | KeeperDive::JumpRight => &parameters.side_jump,
+    }
+}
+
+fn covered_interval(dive: KeeperDive, parameters: &KeeperDiveParameters) -> (f32, f32) {
+    match dive {
+        KeeperDive::WideStance | KeeperDive::CenterJump => (-parameters.reach, parameters.reach),
+        KeeperDive::JumpLeft => (0.0, parameters.reach),
+        KeeperDive::JumpRight => (-parameters.reach, 0.0),
+    }
+}
+
+/// Probability that a normally distributed value lies between `minimum` and `maximum`
+fn probability_within(mean: f32, standard_deviation: f32, minimum: f32, maximum: f32) -> f32 {
+    if standard_deviation <= f32::EPSILON {
+        return if (minimum..=maximum).contains(&mean) {
+            1.0
+        } else {
+            0.0
+        };
+    }
+    let cumulative =
+        |value: f32| 0.5 * (1.0 + error_function((value - mean) / (standard_deviation * SQRT_2)));
+    cumulative(maximum) - cumulative(minimum)
+}
+
+/// Abramowitz and Stegun approximation 7.1.26 with a maximum error of 1.5e-7
+fn error_function(x: f32) -> f32 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let polynomial = t
+        * (0.254_829_6
+            + t * (-0.284_496_74 + t * (1.421_413_7 + t * (-1.453_152_1 + t * 1.061_405_4))));
+    let result = 1.0 - polynomial * (-x * x).exp();
+    result.copysign(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use linear_algebra::{point, vector, Point2};
+    use types::ball_trajectory::PredictedBallState;
+
+    use super::*;
+
+    fn parameters() -> GoalThreatParameters {
+        GoalThreatParameters {
+            maximum_time_to_robot_line: Duration::from_secs(1),
+            position_standard_deviation: 0.05,
+            heading_standard_deviation: 0.05,
+            minimum_on_target_probability: 0.3,
+            minimum_save_probability: 0.8,
+            wide_stance: KeeperDiveParameters {
+                reach: 0.25,
+                reaction_time: Duration::from_millis(200),
+            },
+            center_jump: KeeperDiveParameters {
+                reach: 0.35,
+                reaction_time: Duration::from_millis(400),
+            },
+            side_jump: KeeperDiveParameters {
+                reach: 0.6,
+                reaction_time: Duration::from_millis(500),
+            },
+        }
+    }
+
+    fn straight_shot(from: Point2<Ground>, velocity: f32, lateral_velocity: f32) -> BallTrajectory {
+        let states: Vec<_> = (0..=200)
+            .map(|index| {
+                let time = Duration::from_millis(10 * index);
+                PredictedBallState {
+                    time,
+                    position: from + vector![-velocity, lateral_velocity] * time.as_secs_f32(),
+                    velocity: vector![-velocity, lateral_velocity],
+                }
+            })
+            .collect();
+        BallTrajectory {
+            rest_position: states.last().unwrap().position,
+            states,
+            time_to_rest: None,
+        }
+    }
+
+    fn dive_for(trajectory: &BallTrajectory) -> Option<KeeperDive> {
+        let field_dimensions = FieldDimensions {
+            length: 9.0,
+            goal_inner_width: 1.5,
+            ..Default::default()
+        };
+        let ground_to_field = Isometry2::from_parts(vector![-4.3, 0.0], 0.0);
+        let goal_threat = estimate_goal_threat(
+            trajectory,
+            ground_to_field,
+            &field_dimensions,
+            &parameters(),
+        )?;
+        choose_dive(&goal_threat, &parameters())
+    }
+
+    #[test]
+    fn shot_at_robot_is_blocked_with_wide_stance() {
+        assert_eq!(
+            dive_for(&straight_shot(point![1.0, 0.0], 2.0, 0.0)),
+            Some(KeeperDive::WideStance)
+        );
+    }
+
+    #[test]
+    fn shot_next_to_robot_is_blocked_by_jumping_to_that_side() {
+        assert_eq!(
+            dive_for(&straight_shot(point![1.5, 0.0], 2.0, 0.6)),
+            Some(KeeperDive::JumpLeft)
+        );
+        assert_eq!(
+            dive_for(&straight_shot(point![1.5, 0.0], 2.0, -0.6)),
+            Some(KeeperDive::JumpRight)
+        );
+    }
+
+    #[test]
+    fn shot_arriving_before_any_reaction_time_is_not_dived_for() {
+        assert_eq!(dive_for(&straight_shot(point![0.3, 0.0], 2.0, 0.0)), None);
+    }
+
+    #[test]
+    fn distant_or_missing_shots_are_ignored() {
+        assert_eq!(dive_for(&straight_shot(point![4.0, 0.0], 1.0, 0.0)), None);
+        assert_eq!(dive_for(&straight_shot(point![1.0, 0.0], 2.0, 3.0)), None);
+    }
+}
@@ -22,6 +22,7 @@ use types::{
     obstacles::Obstacle,
     parameters::{InWalkKickInfoParameters, InWalkKicksParameters},
     support_foot::Side,
+    teammate::Teammate,
     world_state::BallState,
 };
 
@@ -36,6 +37,7 @@ pub struct CycleContext {
     ground_to_field: RequiredInput<Option<Isometry2<Ground, Field>>, "ground_to_field?">,
     ball_state: RequiredInput<Option<BallState>, "ball_state?">,
     obstacles: Input<Vec<Obstacle>, "obstacles">,
+    teammates: Input<Vec<Teammate>, "teammates">,
     filtered_game_controller_state:
         Input<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
     ground_to_upcoming_support:
@@ -108,6 +110,20 @@ impl KickSelector {
             ball_position,
             context.in_walk_kicks,
         );
+        let pass_receivers = if matches!(playing_situation, PlayingSituation::Normal)
+            && context.decision_parameters.enable_passing
+        {
+            collect_pass_receivers(&context, ball_position)
+        } else {
+            Vec::new()
+        };
+        kick_decisions.extend(generate_pass_decisions(
+            &context,
+            &pass_receivers,
+            &variants,
+            &sides,
+            ball_position,
+        ));
 
         kick_decisions.sort_by(|left, right| {
             compare_decisions(
@@ -116,6 +132,7 @@ impl KickSelector {
                 ball_position,
                 *context.ground_to_upcoming_support,
                 context.obstacles,
+                &pass_receivers,
                 context.decision_parameters,
             )
         });
@@ -269,27 +286,106 @@ fn generate_penalty_shot_kick_targets(context: &CycleContext) -> Vec<Point2<Grou
     vec![left_target, right_target]
 }
 
+/// Teammates a pass can be played to, together with the cost of passing to each of them
+fn collect_pass_receivers(
+    context: &CycleContext,
+    ball_position: Point2<Ground>,
+) -> Vec<(Teammate, f32)> {
+    let parameters = context.decision_parameters;
+    let ball_in_field = *context.ground_to_field * ball_position;
+    let opponent_goal_center = point![context.field_dimensions.length / 2.0, 0.0];
+
+    context
+        .teammates
+        .iter()
+        .filter_map(|teammate| {
+            let receiver_position = teammate.pose.position();
+            let pass_distance = distance(ball_in_field, receiver_position);
+            let progress = distance(ball_in_field, opponent_goal_center)
+                - distance(receiver_position, opponent_goal_center);
+            let angle_to_ball = angle_to_ball(teammate, ball_in_field)?;
+            let is_receivable = (parameters.pass_minimum_distance
+                ..=parameters.pass_maximum_distance)
+                .contains(&pass_distance)
+                && progress >= parameters.pass_minimum_progress
+                && angle_to_ball <= parameters.pass_receiver_maximum_angle_to_ball;
+            is_receivable.then(|| (*teammate, pass_cost(progress, angle_to_ball, parameters)))
+        })
+        .collect()
+}
+
+fn generate_pass_decisions(
+    context: &CycleContext,
+    pass_receivers: &[(Teammate, f32)],
+    variants: &[KickVariant],
+    sides: &[Side],
+    ball_position: Point2<Ground>,
+) -> Vec<KickDecision> {
+    let ground_to_field = *context.ground_to_field;
+
+    pass_receivers
+        .iter()
+        .flat_map(|(teammate, _)| {
+            let target = ground_to_field.inverse() * teammate.pose.position();
+            kick_decisions_from_targets(
+                &[target],
+                variants,
+                sides,
+                context.decision_parameters.pass_kick_strength,
+                ball_position,
+                context.in_walk_kicks,
+            )
+            .into_iter()
+            .map(|decision| KickDecision {
+                receiver: Some(teammate.player_number),
+                ..decision
+            })
+        })
+        .collect()
+}
+
+/// Absolute angle between the orientation of the teammate and the direction to the ball
+fn angle_to_ball(teammate: &Teammate, ball_position: Point2<Field>) -> Option<f32> {
+    let to_ball = (ball_position - teammate.pose.position()).try_normalize(f32::EPSILON)?;
+    let angle_to_ball = teammate
+        .pose
+        .orientation()
+        .rotation_to(Orientation2::from_vector(to_ball))
+        .angle();
+    Some(angle_to_ball.abs())
+}
+
+/// Passes are cheaper the more progress they make and the better the receiver faces the ball
+fn pass_cost(progress: f32, angle_to_ball: f32, parameters: &DecisionParameters) -> f32 {
+    let readiness_cost = angle_to_ball
+        / parameters
+            .pass_receiver_maximum_angle_to_ball
+            .max(f32::EPSILON);
+    parameters.pass_cost - parameters.pass_progress_weight * progress
+        + parameters.pass_readiness_weight * readiness_cost
+}
+
 fn compare_decisions(
     left: &KickDecision,
     right: &KickDecision,
     ball_position: Point2<Ground>,
     ground_to_upcoming_support: Isometry2<Ground, UpcomingSupport>,
     obstacles: &[Obstacle],
+    pass_receivers: &[(Teammate, f32)],
     parameters: &DecisionParameters,
 ) -> Ordering {
     let left_in_obstacle = is_inside_any_obstacle(left.kick_pose, obstacles, parameters);
     let right_in_obstacle = is_inside_any_obstacle(right.kick_pose, obstacles, parameters);
     let left_is_intersecting_with_obstacle =
-        is_intersecting_with_an_obstacle(obstacles, ball_position, left.target, parameters);
+        is_kick_obstructed(left, obstacles, ball_position, parameters);
     let right_is_intersecting_with_obstacle =
-        is_intersecting_with_an_obstacle(obstacles, ball_position, right.target, parameters);
-    let distance_to_left = distance_to_kick_pose(
-        ground_to_upcoming_support * left.kick_pose,
-        parameters.angle_distance_weight,
-    );
-    let distance_to_right = distance_to_kick_pose(
-        ground_to_upcoming_support * right.kick_pose,
-        parameters.angle_distance_weight,
+        is_kick_obstructed(right, obstacles, ball_position, parameters);
+    let cost_of_left = decision_cost(left, ground_to_upcoming_support, pass_receivers, parameters);
+    let cost_of_right = decision_cost(
+        right,
+        ground_to_upcoming_support,
+        pass_receivers,
+        parameters,
     );
 
     match (
@@ -302,10 +398,60 @@ fn compare_decisions(
         (true, false, _, _) => Ordering::Greater,
         (_, _, false, true) => Ordering::Less,
         (_, _, true, false) => Ordering::Greater,
-        _ => distance_to_left.total_cmp(&distance_to_right),
+        _ => cost_of_left.total_cmp(&cost_of_right),
     }
 }
 
+/// Distance to walk to the kick pose plus the cost of the pass for passes
+fn decision_cost(
+    decision: &KickDecision,
+    ground_to_upcoming_support: Isometry2<Ground, UpcomingSupport>,
+    pass_receivers: &[(Teammate, f32)],
+    parameters: &DecisionParameters,
+) -> f32 {
+    let pass_cost = decision
+        .receiver
+        .and_then(|receiver| {
+            pass_receivers
+                .iter()
+                .find(|(teammate, _)| teammate.player_number == receiver)
+        })
+        .map_or(0.0, |(_, cost)| *cost);
+    distance_to_kick_pose(
+        ground_to_upcoming_support * decision.kick_pose,
+        parameters.angle_distance_weight,
+    ) + pass_cost
+}
+
+fn is_kick_obstructed(
+    decision: &KickDecision,
+    obstacles: &[Obstacle],
+    ball_position: Point2<Ground>,
+    parameters: &DecisionParameters,
+) -> bool {
+    if decision.receiver.is_none() {
+        return is_intersecting_with_an_obstacle(
+            obstacles,
+            ball_position,
+            decision.target,
+            parameters,
+        );
+    }
+    let obstacles_without_receiver: Vec<_> = obstacles
+        .iter()
+        .filter(|obstacle| {
+            distance(obstacle.position, decision.target) > parameters.pass_receiver_clearance
+        })
+        .copied()
+        .collect();
+    is_intersecting_with_an_obstacle(
+        &obstacles_without_receiver,
+        ball_position,
+        decision.target,
+        parameters,
+    )
+}
+
 fn is_intersecting_with_an_obstacle(
     obstacles: &[Obstacle],
     ball_position: Point2<Ground>,
@@ -413,6 +559,7 @@ fn generate_decisions_for_instant_kicks(
                     kicking_side,
                     kick_pose,
                     strength: parameters.kick_off_kick_strength,
+                    receiver: None,
                 })
             } else if !is_own_kick_off && (is_inside_field && is_strategic_target || scores_goal) {
                 let kick_pose = compute_kick_pose(ball_position, target, kick_info, kicking_side);
@@ -422,6 +569,7 @@ fn generate_decisions_for_instant_kicks(
                     kicking_side,
                     kick_pose,
                     strength: parameters.default_kick_strength,
+                    receiver: None,
                 })
             } else {
                 None
@@ -470,6 +618,7 @@ fn kick_decisions_from_targets(
                     kicking_side,
                     kick_pose,
                     strength,
+                    receiver: None,
                 }
             })
         })
@@ -535,3 +684,95 @@ pub fn is_inside_kick_off_target_region(position: Point2<Field>, field_width: f3
         && position.x().abs() < field_width / 2.0
         && position.x().abs() <= position.y().abs()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use spl_network_messages::{PlayerNumber, RobotStatus};
+
+    use super::*;
+
+    fn parameters() -> DecisionParameters {
+        DecisionParameters {
+            angle_distance_weight: 0.02,
+            pass_receiver_maximum_angle_to_ball: 0.8,
+            pass_cost: 1.0,
+            pass_progress_weight: 0.5,
+            pass_readiness_weight: 0.5,
+            ..Default::default()
+        }
+    }
+
+    fn decision(receiver: Option<PlayerNumber>) -> KickDecision {
+        KickDecision {
+            target: point![2.0, 0.0],
+            variant: KickVariant::Forward,
+            kicking_side: Side::Left,
+            kick_pose: Pose2::new(point![0.5, 0.0], 0.0),
+            strength: 1.0,
+            receiver,
+        }
+    }
+
+    fn receiver(player_number: PlayerNumber, cost: f32) -> (Teammate, f32) {
+        let teammate = Teammate {
+            player_number,
+            pose: Pose2::new(point![2.0, 0.0], 0.0),
+            status: RobotStatus::default(),
+            last_received: UNIX_EPOCH,
+        };
+        (teammate, cost)
+    }
+
+    fn compare(
+        left: &KickDecision,
+        right: &KickDecision,
+        pass_receivers: &[(Teammate, f32)],
+    ) -> Ordering {
+        compare_decisions(
+            left,
+            right,
+            point![1.0, 0.0],
+            Isometry2::identity(),
+            &[],
+            pass_receivers,
+            &parameters(),
+        )
+    }
+
+    #[test]
+    fn passes_are_preferred_over_shots_only_with_enough_progress() {
+        let shot = decision(None);
+        let pass = decision(Some(PlayerNumber::Two));
+
+        let far_receiver = [receiver(
+            PlayerNumber::Two,
+            pass_cost(3.0, 0.0, &parameters()),
+        )];
+        assert_eq!(compare(&pass, &shot, &far_receiver), Ordering::Less);
+
+        let close_receiver = [receiver(
+            PlayerNumber::Two,
+            pass_cost(1.0, 0.0, &parameters()),
+        )];
+        assert_eq!(compare(&pass, &shot, &close_receiver), Ordering::Greater);
+    }
+
+    #[test]
+    fn receivers_facing_the_ball_are_preferred() {
+        let pass_receivers = [
+            receiver(PlayerNumber::Two, pass_cost(2.0, 0.0, &parameters())),
+            receiver(PlayerNumber::Three, pass_cost(2.0, 0.8, &parameters())),
+        ];
+
+        assert_eq!(
+            compare(
+                &decision(Some(PlayerNumber::Two)),
+                &decision(Some(PlayerNumber::Three)),
+                &pass_receivers
+            ),
+            Ordering::Less
+        );
+    }
+}
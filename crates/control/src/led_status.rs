@@ -14,6 +14,7 @@ use types::{
     primary_state::PrimaryState,
     roles::Role,
     sensor_data::SensorData,
+    thermal_protection::{ThermalLevel, ThermalProtection},
 };
 
 #[derive(Deserialize, Serialize)]
@@ -23,6 +24,8 @@ pub struct LedStatus {
     last_ball_top: SystemTime,
     last_ball_bottom: SystemTime,
     last_game_controller_message: Option<SystemTime>,
+    is_vision_top_degraded: bool,
+    is_vision_bottom_degraded: bool,
 }
 
 #[context]
@@ -41,6 +44,10 @@ pub struct CycleContext {
     balls_top: PerceptionInput<Option<Vec<BallPercept>>, "VisionTop", "balls?">,
     network_message: PerceptionInput<Option<IncomingMessage>, "SplNetwork", "filtered_message?">,
     sensor_data: Input<SensorData, "sensor_data">,
+    thermal_protection: Input<ThermalProtection, "thermal_protection">,
+    degraded_nodes: Input<Vec<String>, "degraded_nodes">,
+    degraded_nodes_top: PerceptionInput<Vec<String>, "VisionTop", "degraded_nodes">,
+    degraded_nodes_bottom: PerceptionInput<Vec<String>, "VisionBottom", "degraded_nodes">,
 }
 
 #[context]
@@ -64,6 +71,8 @@ impl LedStatus {
             last_ball_top: UNIX_EPOCH,
             last_ball_bottom: UNIX_EPOCH,
             last_game_controller_message: None,
+            is_vision_top_degraded: false,
+            is_vision_bottom_degraded: false,
         })
     }
 
@@ -216,12 +225,41 @@ impl LedStatus {
                 .fold(0.0, f32::max),
         );
 
+        if let Some(degraded_nodes) = context
+            .degraded_nodes_top
+            .persistent
+            .values()
+            .flatten()
+            .last()
+        {
+            self.is_vision_top_degraded = !degraded_nodes.is_empty();
+        }
+        if let Some(degraded_nodes) = context
+            .degraded_nodes_bottom
+            .persistent
+            .values()
+            .flatten()
+            .last()
+        {
+            self.is_vision_bottom_degraded = !degraded_nodes.is_empty();
+        }
+        let is_any_node_degraded = !context.degraded_nodes.is_empty()
+            || self.is_vision_top_degraded
+            || self.is_vision_bottom_degraded;
+
+        let feet = match context.thermal_protection.level {
+            _ if is_any_node_degraded && self.blink_state => Rgb::PURPLE,
+            ThermalLevel::Normal => Rgb::GREEN,
+            ThermalLevel::Warning => Rgb::YELLOW,
+            ThermalLevel::Critical => Rgb::RED,
+        };
+
         let leds = Leds {
             left_ear: ears,
             right_ear: ears,
             chest,
-            left_foot: Rgb::GREEN,
-            right_foot: Rgb::GREEN,
+            left_foot: feet,
+            right_foot: feet,
             left_eye,
             right_eye,
         };
@@ -2,6 +2,7 @@ pub mod a_star;
 pub mod active_vision;
 pub mod ball_filter;
 pub mod ball_state_composer;
+pub mod ball_trajectory_predictor;
 pub mod behavior;
 pub mod button_filter;
 pub mod calibration_controller;
@@ -13,12 +14,14 @@ pub mod filtered_game_controller_state_timer;
 pub mod foot_bumper_filter;
 pub mod game_controller_filter;
 pub mod game_controller_state_filter;
+pub mod goal_threat_estimator;
 pub mod ground_contact_detector;
 pub mod ground_provider;
 pub mod kick_selector;
 pub mod kinematics_provider;
 pub mod led_status;
 pub mod localization;
+pub mod message_budget_planner;
 pub mod motion;
 pub mod obstacle_filter;
 pub mod obstacle_receiver;
@@ -27,6 +30,7 @@ pub mod orientation_filter;
 pub mod path_planner;
 pub mod penalty_shot_direction_estimation;
 pub mod primary_state_filter;
+pub mod recording_event_detection;
 pub mod referee_pose_detection_filter;
 pub mod referee_position_provider;
 pub mod role_assignment;
@@ -38,6 +42,9 @@ pub mod sole_pressure_filter;
 pub mod sonar_filter;
 pub mod support_foot_estimation;
 pub mod team_ball_receiver;
+pub mod team_obstacle_receiver;
+pub mod teammate_receiver;
+pub mod thermal_protector;
 pub mod time_to_reach_kick_position;
 pub mod whistle_filter;
 pub mod world_state_composer;
@@ -1,3 +1,5 @@
+mod particle_filter;
+
 use std::{
     f32::consts::{FRAC_PI_2, PI},
     time::{Duration, SystemTime},
@@ -6,7 +8,7 @@ use std::{
 use approx::assert_relative_eq;
 use color_eyre::{eyre::Context, Result};
 use geometry::line_segment::LineSegment;
-use linear_algebra::{distance, point, IntoTransform, Isometry2, Pose2};
+use linear_algebra::{distance, point, IntoTransform, Isometry2, Point2, Pose2};
 use nalgebra::{matrix, Matrix, Matrix2, Matrix3, Rotation2, Translation2, Vector2, Vector3};
 use ordered_float::NotNan;
 use serde::{Deserialize, Serialize};
@@ -15,23 +17,31 @@ use context_attribute::context;
 use coordinate_systems::{Field, Ground};
 use filtering::pose_filter::PoseFilter;
 use framework::{AdditionalOutput, HistoricInput, MainOutput, PerceptionInput};
-use spl_network_messages::{GamePhase, Penalty, PlayerNumber, SubState, Team};
+use spl_network_messages::{GamePhase, Half, Penalty, PlayerNumber, SubState, Team};
 use types::{
+    color::Rgb,
     cycle_time::CycleTime,
+    detected_landmarks::DetectedLandmarks,
     fall_state::FallState,
     field_dimensions::FieldDimensions,
-    field_marks::{field_marks_from_field_dimensions, CorrespondencePoints, Direction, FieldMark},
+    field_marks::{
+        field_marks_from_field_dimensions, CorrespondencePoints, Direction, FieldMark, Goal,
+        PointMarkKind,
+    },
     filtered_game_controller_state::FilteredGameControllerState,
     initial_pose::InitialPose,
     line_data::LineData,
-    localization::{ScoredPose, Update},
+    localization::{LocalizationMode, MeasuredPointMark, Particle, ScoredPose, Update},
     multivariate_normal_distribution::MultivariateNormalDistribution,
+    parameters::{GoalPostColors, ParticleFilterParameters},
     players::Players,
     primary_state::PrimaryState,
     stand_up::RemainingStandUpDuration,
     support_foot::Side,
 };
 
+use particle_filter::{MeasurementParameters, ParticleFilter};
+
 #[derive(Deserialize, Serialize)]
 pub struct Localization {
     field_marks: Vec<FieldMark>,
@@ -41,11 +51,14 @@ pub struct Localization {
     is_penalized_with_motion_in_set_or_initial: bool,
     was_picked_up_while_penalized: bool,
     time_when_penalized_clicked: Option<SystemTime>,
+    last_mode: LocalizationMode,
+    particle_filter: ParticleFilter,
 }
 
 #[context]
 pub struct CreationContext {
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
+    particle_filter_parameters: Parameter<ParticleFilterParameters, "localization.particle_filter">,
 }
 
 #[context]
@@ -55,6 +68,9 @@ pub struct CycleContext {
     fit_errors: AdditionalOutput<Vec<Vec<Vec<Vec<f32>>>>, "localization.fit_errors">,
     measured_lines_in_field:
         AdditionalOutput<Vec<LineSegment<Field>>, "localization.measured_lines_in_field">,
+    measured_point_marks_in_field:
+        AdditionalOutput<Vec<MeasuredPointMark>, "localization.measured_point_marks_in_field">,
+    particles: AdditionalOutput<Vec<Particle>, "localization.particles">,
     pose_hypotheses: AdditionalOutput<Vec<ScoredPose>, "localization.pose_hypotheses">,
     updates: AdditionalOutput<Vec<Vec<Update>>, "localization.updates">,
 
@@ -69,6 +85,9 @@ pub struct CycleContext {
 
     circle_measurement_noise: Parameter<Vector2<f32>, "localization.circle_measurement_noise">,
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
+    goal_post_colors: Parameter<Option<GoalPostColors>, "localization.goal_post_colors?">,
+    goal_post_measurement_noise:
+        Parameter<Vector2<f32>, "localization.goal_post_measurement_noise">,
     good_matching_threshold: Parameter<f32, "localization.good_matching_threshold">,
     gradient_convergence_threshold: Parameter<f32, "localization.gradient_convergence_threshold">,
     gradient_descent_step_size: Parameter<f32, "localization.gradient_descent_step_size">,
@@ -86,20 +105,31 @@ pub struct CycleContext {
         Parameter<usize, "localization.maximum_amount_of_gradient_descent_iterations">,
     maximum_amount_of_outer_iterations:
         Parameter<usize, "localization.maximum_amount_of_outer_iterations">,
+    maximum_goal_post_color_distance:
+        Parameter<f32, "localization.maximum_goal_post_color_distance">,
     minimum_fit_error: Parameter<f32, "localization.minimum_fit_error">,
+    mode: Parameter<LocalizationMode, "localization.mode">,
     odometry_noise: Parameter<Vector3<f32>, "localization.odometry_noise">,
+    particle_filter_parameters: Parameter<ParticleFilterParameters, "localization.particle_filter">,
     player_number: Parameter<PlayerNumber, "player_number">,
     penalized_distance: Parameter<f32, "localization.penalized_distance">,
     penalized_hypothesis_covariance:
         Parameter<Matrix3<f32>, "localization.penalized_hypothesis_covariance">,
+    penalty_mark_measurement_noise:
+        Parameter<Vector2<f32>, "localization.penalty_mark_measurement_noise">,
+    point_mark_acceptance_distance: Parameter<f32, "localization.point_mark_acceptance_distance">,
     score_per_good_match: Parameter<f32, "localization.score_per_good_match">,
     tentative_penalized_duration: Parameter<Duration, "localization.tentative_penalized_duration">,
+    use_landmark_measurements: Parameter<bool, "localization.use_landmark_measurements">,
     use_line_measurements: Parameter<bool, "localization.use_line_measurements">,
     injected_ground_to_field_of_home_after_coin_toss_before_second_half: Parameter<
         Option<Isometry2<Ground, Field>>,
         "injected_ground_to_field_of_home_after_coin_toss_before_second_half?",
     >,
 
+    detected_landmarks_bottom:
+        PerceptionInput<DetectedLandmarks, "VisionBottom", "detected_landmarks">,
+    detected_landmarks_top: PerceptionInput<DetectedLandmarks, "VisionTop", "detected_landmarks">,
     line_data_bottom: PerceptionInput<Option<LineData>, "VisionBottom", "line_data?">,
     line_data_top: PerceptionInput<Option<LineData>, "VisionTop", "line_data?">,
 
@@ -120,6 +150,7 @@ pub struct MainOutputs {
     pub ground_to_field_of_home_after_coin_toss_before_second_half:
         MainOutput<Option<Isometry2<Ground, Field>>>,
     pub is_localization_converged: MainOutput<bool>,
+    pub pose_covariance: MainOutput<Option<Matrix3<f32>>>,
 }
 
 impl Localization {
@@ -137,9 +168,16 @@ impl Localization {
             is_penalized_with_motion_in_set_or_initial: false,
             was_picked_up_while_penalized: false,
             time_when_penalized_clicked: None,
+            last_mode: LocalizationMode::default(),
+            particle_filter: ParticleFilter::new(context.particle_filter_parameters.random_seed),
         })
     }
 
+    fn set_hypotheses(&mut self, hypotheses: Vec<ScoredPose>) {
+        self.particle_filter.reset(&hypotheses);
+        self.hypotheses = hypotheses;
+    }
+
     fn modify_state(
         &mut self,
         context: &CycleContext,
@@ -150,7 +188,7 @@ impl Localization {
             (*context.player_number, sub_state)
         {
             if matches!(kicking_team, Some(Team::Opponent)) {
-                self.hypotheses = self
+                let hypotheses = self
                     .hypotheses
                     .iter()
                     .map(|scored_pose| {
@@ -163,6 +201,7 @@ impl Localization {
                         }
                     })
                     .collect();
+                self.set_hypotheses(hypotheses);
             }
         }
     }
@@ -180,11 +219,11 @@ impl Localization {
                     &context.initial_poses[*context.player_number],
                     context.field_dimensions,
                 );
-                self.hypotheses = vec![ScoredPose::from_isometry(
+                self.set_hypotheses(vec![ScoredPose::from_isometry(
                     initial_pose,
                     *context.initial_hypothesis_covariance,
                     *context.initial_hypothesis_score,
-                )];
+                )]);
                 self.hypotheses_when_entered_playing
                     .clone_from(&self.hypotheses);
             }
@@ -200,11 +239,11 @@ impl Localization {
                         + (context.field_dimensions.length / 2.0),
                     0.0,
                 ]);
-                self.hypotheses = vec![ScoredPose::from_isometry(
+                self.set_hypotheses(vec![ScoredPose::from_isometry(
                     penalty_shoot_out_striker_pose,
                     *context.initial_hypothesis_covariance,
                     *context.initial_hypothesis_score,
-                )];
+                )]);
                 self.hypotheses_when_entered_playing
                     .clone_from(&self.hypotheses);
             }
@@ -217,11 +256,11 @@ impl Localization {
             ) => {
                 let penalty_shoot_out_keeper_pose =
                     Pose2::from(point![-context.field_dimensions.length / 2.0, 0.0]);
-                self.hypotheses = vec![ScoredPose::from_isometry(
+                self.set_hypotheses(vec![ScoredPose::from_isometry(
                     penalty_shoot_out_keeper_pose,
                     *context.initial_hypothesis_covariance,
                     *context.initial_hypothesis_score,
-                )];
+                )]);
                 self.hypotheses_when_entered_playing
                     .clone_from(&self.hypotheses);
             }
@@ -246,8 +285,7 @@ impl Localization {
             (PrimaryState::Penalized, _, _) if primary_state != PrimaryState::Penalized => {
                 if self.is_penalized_with_motion_in_set_or_initial {
                     if self.was_picked_up_while_penalized {
-                        self.hypotheses
-                            .clone_from(&self.hypotheses_when_entered_playing);
+                        self.set_hypotheses(self.hypotheses_when_entered_playing.clone());
                     }
                 } else if self.time_when_penalized_clicked.map_or(true, |time| {
                     context
@@ -261,16 +299,18 @@ impl Localization {
                         context.field_dimensions,
                         *context.penalized_distance,
                     );
-                    self.hypotheses = penalized_poses
-                        .into_iter()
-                        .map(|pose| {
-                            ScoredPose::from_isometry(
-                                pose,
-                                *context.penalized_hypothesis_covariance,
-                                *context.initial_hypothesis_score,
-                            )
-                        })
-                        .collect();
+                    self.set_hypotheses(
+                        penalized_poses
+                            .into_iter()
+                            .map(|pose| {
+                                ScoredPose::from_isometry(
+                                    pose,
+                                    *context.penalized_hypothesis_covariance,
+                                    *context.initial_hypothesis_score,
+                                )
+                            })
+                            .collect(),
+                    );
                     self.hypotheses_when_entered_playing
                         .clone_from(&self.hypotheses);
                 }
@@ -280,16 +320,18 @@ impl Localization {
             (PrimaryState::Unstiff, _, _) => {
                 let penalized_poses =
                     generate_penalized_poses(context.field_dimensions, *context.penalized_distance);
-                self.hypotheses = penalized_poses
-                    .into_iter()
-                    .map(|pose| {
-                        ScoredPose::from_isometry(
-                            pose,
-                            *context.penalized_hypothesis_covariance,
-                            *context.initial_hypothesis_score,
-                        )
-                    })
-                    .collect();
+                self.set_hypotheses(
+                    penalized_poses
+                        .into_iter()
+                        .map(|pose| {
+                            ScoredPose::from_isometry(
+                                pose,
+                                *context.penalized_hypothesis_covariance,
+                                *context.initial_hypothesis_score,
+                            )
+                        })
+                        .collect(),
+                );
                 self.hypotheses_when_entered_playing
                     .clone_from(&self.hypotheses);
             }
@@ -300,31 +342,39 @@ impl Localization {
     fn update_state(&mut self, context: &mut CycleContext) -> Result<()> {
         let mut fit_errors_per_measurement = vec![];
 
-        let getting_up = context
-            .stand_up_back_estimated_remaining_duration
-            .is_running()
-            || context
-                .stand_up_front_estimated_remaining_duration
-                .is_running()
-            || context
-                .stand_up_sitting_estimated_remaining_duration
-                .is_running();
+        let getting_up = is_getting_up(context);
 
         context.measured_lines_in_field.fill_if_subscribed(Vec::new);
+        context
+            .measured_point_marks_in_field
+            .fill_if_subscribed(Vec::new);
         context.correspondence_lines.fill_if_subscribed(Vec::new);
         context
             .updates
             .fill_if_subscribed(|| vec![vec![]; self.hypotheses.len()]);
 
-        let line_data = context
+        let half = context
+            .filtered_game_controller_state
+            .map_or(Half::First, |game_controller_state| {
+                game_controller_state.half
+            });
+        let measurements = context
             .line_data_top
             .persistent
             .iter()
-            .zip(context.line_data_bottom.persistent.iter());
+            .zip(context.line_data_bottom.persistent.iter())
+            .zip(context.detected_landmarks_top.persistent.values())
+            .zip(context.detected_landmarks_bottom.persistent.values());
         for (
-            (line_data_top_timestamp, line_data_top),
-            (line_data_bottom_timestamp, line_data_bottom),
-        ) in line_data
+            (
+                (
+                    (line_data_top_timestamp, line_data_top),
+                    (line_data_bottom_timestamp, line_data_bottom),
+                ),
+                detected_landmarks_top,
+            ),
+            detected_landmarks_bottom,
+        ) in measurements
         {
             assert_eq!(line_data_top_timestamp, line_data_bottom_timestamp);
             let current_odometry_to_last_odometry = context
@@ -342,7 +392,7 @@ impl Localization {
                     .wrap_err("failed to predict pose filter")?;
                     scored_state.score *= *context.hypothesis_prediction_score_reduction_factor;
                 }
-                if *context.use_line_measurements
+                if (*context.use_line_measurements || *context.use_landmark_measurements)
                     && !getting_up
                     && *context.fall_state == FallState::Upright
                 {
@@ -351,6 +401,7 @@ impl Localization {
                     let current_measured_lines_in_field: Vec<_> = line_data_top
                         .iter()
                         .chain(line_data_bottom.iter())
+                        .filter(|_| *context.use_line_measurements)
                         .filter_map(|data| data.as_ref())
                         .flat_map(|line_data| {
                             line_data.lines.iter().map(|&measured_line_in_ground| {
@@ -358,6 +409,23 @@ impl Localization {
                             })
                         })
                         .collect();
+                    let current_measured_point_marks_in_field: Vec<_> = detected_landmarks_top
+                        .iter()
+                        .chain(detected_landmarks_bottom.iter())
+                        .filter(|_| *context.use_landmark_measurements)
+                        .flat_map(|detected_landmarks| {
+                            classify_point_marks(
+                                detected_landmarks,
+                                context.goal_post_colors,
+                                half,
+                                *context.maximum_goal_post_color_distance,
+                            )
+                        })
+                        .map(|(position, kind)| MeasuredPointMark {
+                            position: ground_to_field * position,
+                            kind,
+                        })
+                        .collect();
                     context.measured_lines_in_field.mutate_if_subscribed(
                         |measured_lines_in_field| {
                             if let Some(measured_lines_in_field) = measured_lines_in_field {
@@ -366,17 +434,31 @@ impl Localization {
                             }
                         },
                     );
-                    if current_measured_lines_in_field.is_empty() {
+                    context.measured_point_marks_in_field.mutate_if_subscribed(
+                        |measured_point_marks_in_field| {
+                            if let Some(measured_point_marks_in_field) =
+                                measured_point_marks_in_field
+                            {
+                                measured_point_marks_in_field
+                                    .extend(current_measured_point_marks_in_field.iter());
+                            }
+                        },
+                    );
+                    if current_measured_lines_in_field.is_empty()
+                        && current_measured_point_marks_in_field.is_empty()
+                    {
                         continue;
                     }
 
                     let (field_mark_correspondences, fit_error, fit_errors) =
                         get_fitted_field_mark_correspondence(
                             &current_measured_lines_in_field,
+                            &current_measured_point_marks_in_field,
                             &self.field_marks,
                             *context.gradient_convergence_threshold,
                             *context.gradient_descent_step_size,
                             *context.line_length_acceptance_factor,
+                            *context.point_mark_acceptance_distance,
                             *context.maximum_amount_of_gradient_descent_iterations,
                             *context.maximum_amount_of_outer_iterations,
                             context.fit_errors.is_subscribed(),
@@ -423,6 +505,10 @@ impl Localization {
                                 ground_to_field,
                                 field_mark_correspondence,
                             ),
+                            FieldMark::Point { .. } => get_point_translation_measurement(
+                                ground_to_field,
+                                field_mark_correspondence,
+                            ),
                         };
                         let line_length = field_mark_correspondence.measured_line_in_field.length();
                         let line_length_weight = if line_length == 0.0 {
@@ -461,10 +547,12 @@ impl Localization {
                                                     }
                                                 }
                                             }
-                                            FieldMark::Circle { .. } => nalgebra::Isometry2::new(
-                                                update,
-                                                ground_to_field.orientation().angle(),
-                                            ),
+                                            FieldMark::Circle { .. } | FieldMark::Point { .. } => {
+                                                nalgebra::Isometry2::new(
+                                                    update,
+                                                    ground_to_field.orientation().angle(),
+                                                )
+                                            }
                                         }
                                         .framed_transform();
                                     Update {
@@ -508,6 +596,25 @@ impl Localization {
                                     |state| nalgebra::vector![state.x, state.y],
                                 )
                                 .context("Failed to update pose filter")?,
+                            FieldMark::Point { point: _, kind } => {
+                                let measurement_noise = match kind {
+                                    PointMarkKind::PenaltyMark => {
+                                        context.penalty_mark_measurement_noise
+                                    }
+                                    PointMarkKind::GoalPost { .. } => {
+                                        context.goal_post_measurement_noise
+                                    }
+                                };
+                                scored_state
+                                    .state
+                                    .update_with_2d_translation(
+                                        update,
+                                        Matrix::from_diagonal(measurement_noise)
+                                            * uncertainty_weight,
+                                        |state| nalgebra::vector![state.x, state.y],
+                                    )
+                                    .context("Failed to update pose filter")?
+                            }
                         }
                         if field_mark_correspondence.fit_error_sum()
                             < *context.good_matching_threshold
@@ -545,6 +652,79 @@ impl Localization {
         Ok(())
     }
 
+    fn update_particle_filter(&mut self, context: &mut CycleContext) {
+        let getting_up = is_getting_up(context);
+        let half = context
+            .filtered_game_controller_state
+            .map_or(Half::First, |game_controller_state| {
+                game_controller_state.half
+            });
+        let measurement_parameters = MeasurementParameters {
+            field_marks: &self.field_marks,
+            field_dimensions: context.field_dimensions,
+            line_length_acceptance_factor: *context.line_length_acceptance_factor,
+            point_mark_acceptance_distance: *context.point_mark_acceptance_distance,
+            parameters: context.particle_filter_parameters,
+        };
+
+        let measurements = context
+            .line_data_top
+            .persistent
+            .iter()
+            .zip(context.line_data_bottom.persistent.iter())
+            .zip(context.detected_landmarks_top.persistent.values())
+            .zip(context.detected_landmarks_bottom.persistent.values());
+        for (
+            (((timestamp, line_data_top), (_, line_data_bottom)), detected_landmarks_top),
+            detected_landmarks_bottom,
+        ) in measurements
+        {
+            self.particle_filter.predict(
+                context.current_odometry_to_last_odometry.get(timestamp),
+                context.particle_filter_parameters,
+            );
+            if getting_up || *context.fall_state != FallState::Upright {
+                continue;
+            }
+            let measured_lines_in_ground: Vec<_> = line_data_top
+                .iter()
+                .chain(line_data_bottom.iter())
+                .filter(|_| *context.use_line_measurements)
+                .filter_map(|data| data.as_ref())
+                .flat_map(|line_data| line_data.lines.iter().copied())
+                .collect();
+            let measured_point_marks_in_ground: Vec<_> = detected_landmarks_top
+                .iter()
+                .chain(detected_landmarks_bottom.iter())
+                .filter(|_| *context.use_landmark_measurements)
+                .flat_map(|detected_landmarks| {
+                    classify_point_marks(
+                        detected_landmarks,
+                        context.goal_post_colors,
+                        half,
+                        *context.maximum_goal_post_color_distance,
+                    )
+                })
+                .collect();
+            self.particle_filter.update(
+                &measured_lines_in_ground,
+                &measured_point_marks_in_ground,
+                &measurement_parameters,
+            );
+        }
+
+        if let Some(estimate) = self.particle_filter.estimate() {
+            self.hypotheses = vec![estimate];
+            *context.ground_to_field = estimate.state.as_isometry().framed_transform();
+        }
+        context
+            .particles
+            .fill_if_subscribed(|| self.particle_filter.particles().to_vec());
+        context
+            .pose_hypotheses
+            .fill_if_subscribed(|| self.hypotheses.clone());
+    }
+
     pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
         let primary_state = *context.primary_state;
         let penalty = context
@@ -562,6 +742,10 @@ impl Localization {
             .filtered_game_controller_state
             .and_then(|game_controller_state| game_controller_state.kicking_team);
 
+        if *context.mode != self.last_mode {
+            self.particle_filter.reset(&self.hypotheses);
+            self.last_mode = *context.mode;
+        }
         self.reset_state(primary_state, game_phase, &context, &penalty);
         self.modify_state(&context, sub_state, kicking_team);
         self.last_primary_state = primary_state;
@@ -579,7 +763,10 @@ impl Localization {
                 .as_transform(),
             ),
             PrimaryState::Ready | PrimaryState::Set | PrimaryState::Playing => {
-                self.update_state(&mut context)?;
+                match context.mode {
+                    LocalizationMode::MultiHypothesis => self.update_state(&mut context)?,
+                    LocalizationMode::ParticleFilter => self.update_particle_filter(&mut context),
+                }
                 Some(*context.ground_to_field)
             }
             _ => None,
@@ -604,13 +791,31 @@ impl Localization {
                         }
                     })
             });
-        let is_localization_converged = self.hypotheses.len() == 1;
+        let is_localization_converged = match context.mode {
+            LocalizationMode::MultiHypothesis => self.hypotheses.len() == 1,
+            LocalizationMode::ParticleFilter => {
+                self.get_best_hypothesis().is_some_and(|hypothesis| {
+                    let covariance = hypothesis.state.covariance;
+                    (covariance[(0, 0)] + covariance[(1, 1)]).sqrt()
+                        < context
+                            .particle_filter_parameters
+                            .maximum_converged_position_deviation
+                })
+            }
+        };
+        let pose_covariance = match primary_state {
+            PrimaryState::Ready | PrimaryState::Set | PrimaryState::Playing => self
+                .get_best_hypothesis()
+                .map(|hypothesis| hypothesis.state.covariance),
+            _ => None,
+        };
 
         Ok(MainOutputs {
             ground_to_field: ground_to_field.into(),
             ground_to_field_of_home_after_coin_toss_before_second_half:
                 ground_to_field_of_home_after_coin_toss_before_second_half.into(),
             is_localization_converged: is_localization_converged.into(),
+            pose_covariance: pose_covariance.into(),
         })
     }
 
@@ -735,13 +940,86 @@ fn predict(
     Ok(())
 }
 
+fn is_getting_up(context: &CycleContext) -> bool {
+    context
+        .stand_up_back_estimated_remaining_duration
+        .is_running()
+        || context
+            .stand_up_front_estimated_remaining_duration
+            .is_running()
+        || context
+            .stand_up_sitting_estimated_remaining_duration
+            .is_running()
+}
+
+fn classify_point_marks(
+    detected_landmarks: &DetectedLandmarks,
+    goal_post_colors: Option<&GoalPostColors>,
+    half: Half,
+    maximum_goal_post_color_distance: f32,
+) -> Vec<(Point2<Ground>, PointMarkKind)> {
+    let penalty_marks = detected_landmarks
+        .penalty_marks
+        .iter()
+        .map(|&position| (position, PointMarkKind::PenaltyMark));
+    let goal_posts = detected_landmarks.goal_posts.iter().map(|goal_post| {
+        let goal = goal_post_colors.and_then(|goal_post_colors| {
+            classify_goal(
+                goal_post.color,
+                goal_post_colors,
+                half,
+                maximum_goal_post_color_distance,
+            )
+        });
+        (goal_post.position, PointMarkKind::GoalPost { goal })
+    });
+    penalty_marks.chain(goal_posts).collect()
+}
+
+fn classify_goal(
+    color: Rgb,
+    goal_post_colors: &GoalPostColors,
+    half: Half,
+    maximum_goal_post_color_distance: f32,
+) -> Option<Goal> {
+    // Teams switch sides at half time while the goals keep their colors
+    let (own_goal_color, opponent_goal_color) = match half {
+        Half::First => (
+            goal_post_colors.own_goal_in_first_half,
+            goal_post_colors.opponent_goal_in_first_half,
+        ),
+        Half::Second => (
+            goal_post_colors.opponent_goal_in_first_half,
+            goal_post_colors.own_goal_in_first_half,
+        ),
+    };
+    let matches_own_goal =
+        color_distance(color, own_goal_color) <= maximum_goal_post_color_distance;
+    let matches_opponent_goal =
+        color_distance(color, opponent_goal_color) <= maximum_goal_post_color_distance;
+    match (matches_own_goal, matches_opponent_goal) {
+        (true, false) => Some(Goal::Own),
+        (false, true) => Some(Goal::Opponent),
+        _ => None,
+    }
+}
+
+fn color_distance(left: Rgb, right: Rgb) -> f32 {
+    ((left.red as f32 - right.red as f32).powi(2)
+        + (left.green as f32 - right.green as f32).powi(2)
+        + (left.blue as f32 - right.blue as f32).powi(2))
+    .sqrt()
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn get_fitted_field_mark_correspondence(
     measured_lines_in_field: &[LineSegment<Field>],
+    measured_point_marks_in_field: &[MeasuredPointMark],
     field_marks: &[FieldMark],
     gradient_convergence_threshold: f32,
     gradient_descent_step_size: f32,
     line_length_acceptance_factor: f32,
+    point_mark_acceptance_distance: f32,
     maximum_amount_of_gradient_descent_iterations: usize,
     maximum_amount_of_outer_iterations: usize,
     fit_errors_is_subscribed: bool,
@@ -749,12 +1027,25 @@ pub fn get_fitted_field_mark_correspondence(
     let mut fit_errors = vec![];
     let mut correction = nalgebra::Isometry2::identity();
     for _ in 0..maximum_amount_of_outer_iterations {
-        let correspondence_points = get_correspondence_points(get_field_mark_correspondence(
-            measured_lines_in_field,
-            correction,
-            field_marks,
-            line_length_acceptance_factor,
-        ));
+        let correspondence_points = get_correspondence_points(
+            get_field_mark_correspondence(
+                measured_lines_in_field,
+                correction,
+                field_marks,
+                line_length_acceptance_factor,
+            )
+            .into_iter()
+            .chain(get_point_mark_correspondence(
+                measured_point_marks_in_field,
+                correction,
+                field_marks,
+                point_mark_acceptance_distance,
+            ))
+            .collect(),
+        );
+        if correspondence_points.is_empty() {
+            break;
+        }
 
         let weight_matrices: Vec<_> = correspondence_points
             .iter()
@@ -821,12 +1112,20 @@ pub fn get_fitted_field_mark_correspondence(
         }
     }
 
-    let field_mark_correspondences = get_field_mark_correspondence(
+    let field_mark_correspondences: Vec<_> = get_field_mark_correspondence(
         measured_lines_in_field,
         correction,
         field_marks,
         line_length_acceptance_factor,
-    );
+    )
+    .into_iter()
+    .chain(get_point_mark_correspondence(
+        measured_point_marks_in_field,
+        correction,
+        field_marks,
+        point_mark_acceptance_distance,
+    ))
+    .collect();
 
     let correspondence_points = get_correspondence_points(field_mark_correspondences.clone());
     let weight_matrices: Vec<_> = correspondence_points
@@ -884,6 +1183,7 @@ fn get_field_mark_correspondence(
                     let field_mark_length = match field_mark {
                         FieldMark::Line { line, direction: _ } => line.length(),
                         FieldMark::Circle { center: _, radius } => *radius, // approximation
+                        FieldMark::Point { .. } => return None,
                     };
                     let measured_line_length = transformed_line.length();
                     if measured_line_length <= field_mark_length * line_length_acceptance_factor {
@@ -952,6 +1252,47 @@ fn get_field_mark_correspondence(
         .collect()
 }
 
+fn get_point_mark_correspondence(
+    measured_point_marks_in_field: &[MeasuredPointMark],
+    correction: nalgebra::Isometry2<f32>,
+    field_marks: &[FieldMark],
+    point_mark_acceptance_distance: f32,
+) -> Vec<FieldMarkCorrespondence> {
+    let correction: Isometry2<Field, Field> = correction.framed_transform();
+    measured_point_marks_in_field
+        .iter()
+        .filter_map(|measured_point_mark| {
+            let transformed_point = correction * measured_point_mark.position;
+            let (field_mark, reference, _distance) = field_marks
+                .iter()
+                .filter_map(|field_mark| match *field_mark {
+                    FieldMark::Point { point, kind }
+                        if measured_point_mark.kind.is_compatible_with(kind) =>
+                    {
+                        Some((field_mark, point, distance(transformed_point, point)))
+                    }
+                    _ => None,
+                })
+                .filter(|(_field_mark, _reference, distance)| {
+                    *distance < point_mark_acceptance_distance
+                })
+                .min_by(|(_, _, left), (_, _, right)| left.total_cmp(right))?;
+            let correspondence_points = CorrespondencePoints {
+                measured: measured_point_mark.position,
+                reference,
+            };
+            Some(FieldMarkCorrespondence {
+                measured_line_in_field: LineSegment(
+                    measured_point_mark.position,
+                    measured_point_mark.position,
+                ),
+                field_mark: *field_mark,
+                correspondence_points: (correspondence_points, correspondence_points),
+            })
+        })
+        .collect()
+}
+
 fn get_correspondence_points(
     field_mark_correspondences: Vec<FieldMarkCorrespondence>,
 ) -> Vec<CorrespondencePoints> {
@@ -1048,6 +1389,16 @@ fn get_2d_translation_measurement(
     reference_robot_point.coords
 }
 
+fn get_point_translation_measurement(
+    ground_to_field: Isometry2<Ground, Field>,
+    field_mark_correspondence: FieldMarkCorrespondence,
+) -> Vector2<f32> {
+    let correspondence_points = field_mark_correspondence.correspondence_points.0;
+    let robot_position = ground_to_field.as_pose().position()
+        + (correspondence_points.reference - correspondence_points.measured);
+    robot_position.inner.coords
+}
+
 pub fn generate_initial_pose(
     initial_pose: &InitialPose,
     field_dimensions: &FieldDimensions,
@@ -1777,4 +2128,101 @@ mod tests {
         let update = get_2d_translation_measurement(ground_to_field, field_mark_correspondence);
         assert_relative_eq!(update, nalgebra::vector![0.0, -2.0], epsilon = 0.0001);
     }
+
+    #[test]
+    fn point_marks_only_correspond_to_compatible_marks() {
+        let field_marks = [
+            FieldMark::Point {
+                point: point![-3.0, 0.0],
+                kind: PointMarkKind::PenaltyMark,
+            },
+            FieldMark::Point {
+                point: point![-4.5, 0.8],
+                kind: PointMarkKind::GoalPost {
+                    goal: Some(Goal::Own),
+                },
+            },
+            FieldMark::Point {
+                point: point![4.5, 0.8],
+                kind: PointMarkKind::GoalPost {
+                    goal: Some(Goal::Opponent),
+                },
+            },
+        ];
+        let measured_point_marks_in_field = [
+            MeasuredPointMark {
+                position: point![-3.2, 0.1],
+                kind: PointMarkKind::PenaltyMark,
+            },
+            MeasuredPointMark {
+                position: point![-4.4, 0.9],
+                kind: PointMarkKind::GoalPost {
+                    goal: Some(Goal::Opponent),
+                },
+            },
+            MeasuredPointMark {
+                position: point![4.4, 0.7],
+                kind: PointMarkKind::GoalPost { goal: None },
+            },
+        ];
+
+        let correspondences = get_point_mark_correspondence(
+            &measured_point_marks_in_field,
+            nalgebra::Isometry2::identity(),
+            &field_marks,
+            1.0,
+        );
+
+        assert_eq!(correspondences.len(), 2);
+        assert_relative_eq!(
+            correspondences[0].correspondence_points.0.reference,
+            point![-3.0, 0.0]
+        );
+        assert_relative_eq!(
+            correspondences[1].correspondence_points.0.reference,
+            point![4.5, 0.8]
+        );
+    }
+
+    #[test]
+    fn point_mark_correspondence_translates() {
+        let ground_to_field = Isometry2::from_parts(linear_algebra::vector![1.0, 1.0], 0.5);
+        let correspondence_points = CorrespondencePoints {
+            measured: point![2.0, 1.0],
+            reference: point![2.5, 0.5],
+        };
+        let field_mark_correspondence = FieldMarkCorrespondence {
+            measured_line_in_field: LineSegment(point![2.0, 1.0], point![2.0, 1.0]),
+            field_mark: FieldMark::Point {
+                point: point![2.5, 0.5],
+                kind: PointMarkKind::PenaltyMark,
+            },
+            correspondence_points: (correspondence_points, correspondence_points),
+        };
+
+        let update = get_point_translation_measurement(ground_to_field, field_mark_correspondence);
+
+        assert_relative_eq!(update, nalgebra::vector![1.5, 0.5], epsilon = 0.0001);
+    }
+
+    #[test]
+    fn goal_post_colors_swap_goals_at_half_time() {
+        let goal_post_colors = GoalPostColors {
+            own_goal_in_first_half: Rgb::YELLOW,
+            opponent_goal_in_first_half: Rgb::BLUE,
+        };
+
+        assert_eq!(
+            classify_goal(Rgb::YELLOW, &goal_post_colors, Half::First, 60.0),
+            Some(Goal::Own)
+        );
+        assert_eq!(
+            classify_goal(Rgb::YELLOW, &goal_post_colors, Half::Second, 60.0),
+            Some(Goal::Opponent)
+        );
+        assert_eq!(
+            classify_goal(Rgb::WHITE, &goal_post_colors, Half::First, 60.0),
+            None
+        );
+    }
 }
@@ -0,0 +1,528 @@
+use std::f32::consts::PI;
+
+use nalgebra::{Matrix3, Rotation2, Vector3};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+use rand_distr::StandardNormal;
+use serde::{Deserialize, Serialize};
+
+use coordinate_systems::{Field, Ground};
+use geometry::line_segment::LineSegment;
+use linear_algebra::{distance, point, Isometry2, Point2, Pose2};
+use types::{
+    field_dimensions::FieldDimensions,
+    field_marks::{FieldMark, PointMarkKind},
+    localization::{MeasuredPointMark, Particle, ScoredPose},
+    multivariate_normal_distribution::MultivariateNormalDistribution,
+    parameters::ParticleFilterParameters,
+};
+
+use super::{get_field_mark_correspondence, get_point_mark_correspondence};
+
+/// Augmented Monte Carlo localization: short and long term averages of the measurement
+/// likelihood decide how many particles are injected at random poses during resampling
+#[derive(Deserialize, Serialize)]
+pub struct ParticleFilter {
+    particles: Vec<Particle>,
+    seed: Option<Vec<ScoredPose>>,
+    best_pose: Option<Pose2<Field>>,
+    slow_average_likelihood: f32,
+    fast_average_likelihood: f32,
+    random_state: ChaChaRng,
+}
+
+pub struct MeasurementParameters<'a> {
+    pub field_marks: &'a [FieldMark],
+    pub field_dimensions: &'a FieldDimensions,
+    pub line_length_acceptance_factor: f32,
+    pub point_mark_acceptance_distance: f32,
+    pub parameters: &'a ParticleFilterParameters,
+}
+
+/// Measurements associated to field marks once per update at the best particle, all particles
+/// are scored against these instead of searching the closest field mark for each of them
+struct Associations {
+    lines: Vec<(LineSegment<Ground>, FieldMark)>,
+    point_marks: Vec<(Point2<Ground>, Point2<Field>)>,
+    number_of_unmatched_measurements: usize,
+}
+
+impl Default for ParticleFilter {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl ParticleFilter {
+    /// The random state is seeded explicitly to make replays of recordings reproducible
+    pub fn new(random_seed: u64) -> Self {
+        Self {
+            particles: Vec::new(),
+            seed: None,
+            best_pose: None,
+            slow_average_likelihood: 0.0,
+            fast_average_likelihood: 0.0,
+            random_state: ChaChaRng::seed_from_u64(random_seed),
+        }
+    }
+
+    /// Particles are redrawn around these hypotheses on the next prediction
+    pub fn reset(&mut self, hypotheses: &[ScoredPose]) {
+        self.seed = Some(hypotheses.to_vec());
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    pub fn predict(
+        &mut self,
+        current_odometry_to_last_odometry: Option<&nalgebra::Isometry2<f32>>,
+        parameters: &ParticleFilterParameters,
+    ) {
+        if let Some(hypotheses) = self.seed.take() {
+            self.particles = draw_particles(
+                &hypotheses,
+                parameters.number_of_particles,
+                &mut self.random_state,
+            );
+            self.best_pose = None;
+            self.slow_average_likelihood = 0.0;
+            self.fast_average_likelihood = 0.0;
+        }
+        let Some(odometry) = current_odometry_to_last_odometry else {
+            return;
+        };
+        for particle in &mut self.particles {
+            let noise = sample_standard_normal(&mut self.random_state)
+                .component_mul(&parameters.odometry_deviation);
+            particle.pose = apply_odometry(particle.pose, odometry, noise);
+        }
+        self.best_pose = self
+            .best_pose
+            .map(|pose| apply_odometry(pose, odometry, Vector3::zeros()));
+    }
+
+    pub fn update(
+        &mut self,
+        measured_lines_in_ground: &[LineSegment<Ground>],
+        measured_point_marks_in_ground: &[(Point2<Ground>, PointMarkKind)],
+        measurement_parameters: &MeasurementParameters,
+    ) {
+        if self.particles.is_empty()
+            || (measured_lines_in_ground.is_empty() && measured_point_marks_in_ground.is_empty())
+        {
+            return;
+        }
+        let Some(reference_pose) = self.best_pose.or_else(|| {
+            self.estimate().map(|estimate| {
+                let mean = estimate.state.mean;
+                Pose2::new(point![mean.x, mean.y], mean.z)
+            })
+        }) else {
+            return;
+        };
+        let associations = associate_measurements(
+            reference_pose.as_transform(),
+            measured_lines_in_ground,
+            measured_point_marks_in_ground,
+            measurement_parameters,
+        );
+
+        let parameters = measurement_parameters.parameters;
+        for particle in &mut self.particles {
+            particle.weight = measurement_likelihood(
+                particle.pose.as_transform::<Ground>(),
+                &associations,
+                parameters,
+            );
+        }
+        self.best_pose = self
+            .particles
+            .iter()
+            .max_by(|left, right| left.weight.total_cmp(&right.weight))
+            .map(|particle| particle.pose);
+
+        let average_likelihood = self
+            .particles
+            .iter()
+            .map(|particle| particle.weight)
+            .sum::<f32>()
+            / self.particles.len() as f32;
+        self.slow_average_likelihood +=
+            parameters.slow_average_rate * (average_likelihood - self.slow_average_likelihood);
+        self.fast_average_likelihood +=
+            parameters.fast_average_rate * (average_likelihood - self.fast_average_likelihood);
+        let injection_probability = if self.slow_average_likelihood > 0.0 {
+            (1.0 - self.fast_average_likelihood / self.slow_average_likelihood).max(0.0)
+        } else {
+            0.0
+        };
+
+        self.particles = resample(
+            &self.particles,
+            injection_probability,
+            measurement_parameters.field_dimensions,
+            &mut self.random_state,
+        );
+    }
+
+    pub fn estimate(&self) -> Option<ScoredPose> {
+        let total_weight: f32 = self.particles.iter().map(|particle| particle.weight).sum();
+        if self.particles.is_empty() || total_weight <= 0.0 {
+            return None;
+        }
+        let (x, y, sine, cosine) =
+            self.particles
+                .iter()
+                .fold((0.0, 0.0, 0.0, 0.0), |(x, y, sine, cosine), particle| {
+                    let weight = particle.weight / total_weight;
+                    let angle = particle.pose.orientation().angle();
+                    (
+                        x + weight * particle.pose.position().x(),
+                        y + weight * particle.pose.position().y(),
+                        sine + weight * angle.sin(),
+                        cosine + weight * angle.cos(),
+                    )
+                });
+        let mean = Vector3::new(x, y, sine.atan2(cosine));
+        let covariance = self
+            .particles
+            .iter()
+            .map(|particle| {
+                let mut difference = as_state(&particle.pose) - mean;
+                difference.z = normalize_angle(difference.z);
+                difference * difference.transpose() * (particle.weight / total_weight)
+            })
+            .sum::<Matrix3<f32>>();
+        Some(ScoredPose {
+            state: MultivariateNormalDistribution { mean, covariance },
+            score: self.fast_average_likelihood,
+        })
+    }
+}
+
+fn associate_measurements(
+    ground_to_field: Isometry2<Ground, Field>,
+    measured_lines_in_ground: &[LineSegment<Ground>],
+    measured_point_marks_in_ground: &[(Point2<Ground>, PointMarkKind)],
+    measurement_parameters: &MeasurementParameters,
+) -> Associations {
+    let lines: Vec<_> = measured_lines_in_ground
+        .iter()
+        .filter_map(|&line| {
+            let correspondence = get_field_mark_correspondence(
+                &[ground_to_field * line],
+                nalgebra::Isometry2::identity(),
+                measurement_parameters.field_marks,
+                measurement_parameters.line_length_acceptance_factor,
+            )
+            .pop()?;
+            Some((line, correspondence.field_mark))
+        })
+        .collect();
+    let point_marks: Vec<_> = measured_point_marks_in_ground
+        .iter()
+        .filter_map(|&(position, kind)| {
+            let correspondence = get_point_mark_correspondence(
+                &[MeasuredPointMark {
+                    position: ground_to_field * position,
+                    kind,
+                }],
+                nalgebra::Isometry2::identity(),
+                measurement_parameters.field_marks,
+                measurement_parameters.point_mark_acceptance_distance,
+            )
+            .pop()?;
+            Some((position, correspondence.correspondence_points.0.reference))
+        })
+        .collect();
+
+    let number_of_unmatched_measurements = measured_lines_in_ground.len() - lines.len()
+        + measured_point_marks_in_ground.len()
+        - point_marks.len();
+    Associations {
+        lines,
+        point_marks,
+        number_of_unmatched_measurements,
+    }
+}
+
+fn measurement_likelihood(
+    ground_to_field: Isometry2<Ground, Field>,
+    associations: &Associations,
+    parameters: &ParticleFilterParameters,
+) -> f32 {
+    let line_likelihood = associations
+        .lines
+        .iter()
+        .map(|&(line, field_mark)| {
+            let (first, second) = field_mark
+                .to_correspondence_points(ground_to_field * line)
+                .correspondence_points;
+            gaussian_likelihood(
+                (distance(first.measured, first.reference)
+                    + distance(second.measured, second.reference))
+                    / 2.0,
+                parameters.line_measurement_deviation,
+            )
+        })
+        .product::<f32>();
+    let point_mark_likelihood = associations
+        .point_marks
+        .iter()
+        .map(|&(position, reference)| {
+            gaussian_likelihood(
+                distance(ground_to_field * position, reference),
+                parameters.point_mark_measurement_deviation,
+            )
+        })
+        .product::<f32>();
+    line_likelihood
+        * point_mark_likelihood
+        * parameters
+            .unmatched_measurement_likelihood
+            .powi(associations.number_of_unmatched_measurements as i32)
+}
+
+fn gaussian_likelihood(error: f32, deviation: f32) -> f32 {
+    (-0.5 * (error / deviation).powi(2)).exp()
+}
+
+/// Low variance resampling, every drawn particle is replaced by a uniformly random one with the
+/// injection probability
+fn resample(
+    particles: &[Particle],
+    injection_probability: f32,
+    field_dimensions: &FieldDimensions,
+    random_state: &mut ChaChaRng,
+) -> Vec<Particle> {
+    let total_weight: f32 = particles.iter().map(|particle| particle.weight).sum();
+    let number_of_particles = particles.len();
+    let uniform_weight = 1.0 / number_of_particles as f32;
+    let step = if total_weight > 0.0 {
+        total_weight / number_of_particles as f32
+    } else {
+        0.0
+    };
+    let mut threshold = random_state.random::<f32>() * step;
+    let mut cumulative_weight = particles[0].weight;
+    let mut index = 0;
+    (0..number_of_particles)
+        .map(|draw| {
+            if random_state.random::<f32>() < injection_probability {
+                return Particle {
+                    pose: random_pose(field_dimensions, random_state),
+                    weight: uniform_weight,
+                };
+            }
+            if total_weight <= 0.0 {
+                return Particle {
+                    pose: particles[draw].pose,
+                    weight: uniform_weight,
+                };
+            }
+            while threshold > cumulative_weight && index + 1 < number_of_particles {
+                index += 1;
+                cumulative_weight += particles[index].weight;
+            }
+            threshold += step;
+            Particle {
+                pose: particles[index].pose,
+                weight: uniform_weight,
+            }
+        })
+        .collect()
+}
+
+fn draw_particles(
+    hypotheses: &[ScoredPose],
+    number_of_particles: usize,
+    random_state: &mut ChaChaRng,
+) -> Vec<Particle> {
+    let total_score: f32 = hypotheses
+        .iter()
+        .map(|hypothesis| hypothesis.score.max(0.0))
+        .sum();
+    if hypotheses.is_empty() {
+        return Vec::new();
+    }
+    let weight = 1.0 / number_of_particles as f32;
+    (0..number_of_particles)
+        .map(|_| {
+            let mut threshold = random_state.random::<f32>() * total_score;
+            let hypothesis = hypotheses
+                .iter()
+                .find(|hypothesis| {
+                    threshold -= hypothesis.score.max(0.0);
+                    threshold <= 0.0
+                })
+                .unwrap_or(&hypotheses[0]);
+            let covariance = hypothesis.state.covariance;
+            let square_root = covariance.cholesky().map_or_else(
+                || {
+                    Matrix3::from_diagonal(
+                        &covariance
+                            .diagonal()
+                            .map(|variance| variance.max(0.0).sqrt()),
+                    )
+                },
+                |cholesky| cholesky.l(),
+            );
+            let state = hypothesis.state.mean + square_root * sample_standard_normal(random_state);
+            Particle {
+                pose: Pose2::new(point![state.x, state.y], state.z),
+                weight,
+            }
+        })
+        .collect()
+}
+
+fn random_pose(field_dimensions: &FieldDimensions, random_state: &mut ChaChaRng) -> Pose2<Field> {
+    let half_length = field_dimensions.length / 2.0 + field_dimensions.border_strip_width;
+    let half_width = field_dimensions.width / 2.0 + field_dimensions.border_strip_width;
+    Pose2::new(
+        point![
+            random_state.random_range(-half_length..half_length),
+            random_state.random_range(-half_width..half_width)
+        ],
+        random_state.random_range(-PI..PI),
+    )
+}
+
+fn apply_odometry(
+    pose: Pose2<Field>,
+    odometry: &nalgebra::Isometry2<f32>,
+    noise: Vector3<f32>,
+) -> Pose2<Field> {
+    let translation = odometry.translation.vector + noise.xy();
+    let orientation = pose.orientation().angle();
+    let position = pose.position().inner.coords + Rotation2::new(orientation) * translation;
+    Pose2::new(
+        point![position.x, position.y],
+        orientation + odometry.rotation.angle() + noise.z,
+    )
+}
+
+fn sample_standard_normal(random_state: &mut ChaChaRng) -> Vector3<f32> {
+    Vector3::from_fn(|_, _| random_state.sample(StandardNormal))
+}
+
+fn as_state(pose: &Pose2<Field>) -> Vector3<f32> {
+    Vector3::new(
+        pose.position().x(),
+        pose.position().y(),
+        pose.orientation().angle(),
+    )
+}
+
+fn normalize_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn particle(x: f32, y: f32, angle: f32, weight: f32) -> Particle {
+        Particle {
+            pose: Pose2::new(point![x, y], angle),
+            weight,
+        }
+    }
+
+    #[test]
+    fn estimate_averages_orientation_across_wrap_around() {
+        let filter = ParticleFilter {
+            particles: vec![
+                particle(1.0, 0.0, PI - 0.1, 1.0),
+                particle(3.0, 2.0, -PI + 0.1, 1.0),
+            ],
+            ..Default::default()
+        };
+
+        let estimate = filter.estimate().unwrap();
+
+        assert_relative_eq!(estimate.state.mean.x, 2.0);
+        assert_relative_eq!(estimate.state.mean.y, 1.0);
+        assert_relative_eq!(estimate.state.mean.z.abs(), PI, epsilon = 1e-4);
+        assert_relative_eq!(estimate.state.covariance[(2, 2)], 0.01, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn resampling_concentrates_on_likely_particles() {
+        let particles = vec![
+            particle(0.0, 0.0, 0.0, 0.0),
+            particle(1.0, 0.0, 0.0, 1.0),
+            particle(2.0, 0.0, 0.0, 0.0),
+        ];
+        let mut random_state = ChaChaRng::seed_from_u64(42);
+
+        let resampled = resample(
+            &particles,
+            0.0,
+            &FieldDimensions::default(),
+            &mut random_state,
+        );
+
+        assert_eq!(resampled.len(), 3);
+        assert!(resampled
+            .iter()
+            .all(|particle| particle.pose.position().x() == 1.0));
+    }
+
+    #[test]
+    fn seeding_draws_around_hypotheses() {
+        let hypothesis = ScoredPose::from_isometry(
+            Pose2::new(point![-3.0, 2.0], 0.5),
+            Matrix3::from_diagonal_element(1e-6),
+            1.0,
+        );
+        let mut filter = ParticleFilter::default();
+        filter.reset(&[hypothesis]);
+
+        filter.predict(
+            None,
+            &ParticleFilterParameters {
+                number_of_particles: 10,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(filter.particles().len(), 10);
+        for particle in filter.particles() {
+            assert_relative_eq!(particle.pose.position().x(), -3.0, epsilon = 0.01);
+            assert_relative_eq!(particle.pose.position().y(), 2.0, epsilon = 0.01);
+        }
+    }
+
+    #[test]
+    fn equally_seeded_filters_draw_identical_particles() {
+        let hypothesis = ScoredPose::from_isometry(
+            Pose2::new(point![1.0, -1.0], 0.0),
+            Matrix3::from_diagonal_element(0.1),
+            1.0,
+        );
+        let parameters = ParticleFilterParameters {
+            number_of_particles: 20,
+            odometry_deviation: Vector3::new(0.01, 0.01, 0.01),
+            ..Default::default()
+        };
+        let odometry = nalgebra::Isometry2::new(nalgebra::vector![0.1, 0.0], 0.05);
+        let particles = |random_seed| {
+            let mut filter = ParticleFilter::new(random_seed);
+            filter.reset(&[hypothesis]);
+            filter.predict(Some(&odometry), &parameters);
+            filter
+                .particles()
+                .iter()
+                .map(|particle| as_state(&particle.pose))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(particles(7), particles(7));
+        assert_ne!(particles(7), particles(8));
+    }
+}
@@ -0,0 +1,268 @@
+use std::time::{Duration, SystemTime};
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use framework::{AdditionalOutput, MainOutput};
+use spl_network_messages::Half;
+use types::{
+    cycle_time::CycleTime,
+    filtered_game_controller_state::FilteredGameControllerState,
+    parameters::{SplMessageBudgetParameters, SplNetworkParameters},
+    spl_message_budget::{
+        SentSplMessages, SplMessageBudgetForecast, SplMessageKind, SplMessageKinds,
+    },
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct MessageBudgetPlanner {}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    cycle_time: Input<CycleTime, "cycle_time">,
+    filtered_game_controller_state:
+        Input<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
+    sent_spl_messages: CyclerState<SentSplMessages, "sent_spl_messages">,
+
+    spl_message_budget: Parameter<SplMessageBudgetParameters, "spl_message_budget">,
+    spl_network_parameters: Parameter<SplNetworkParameters, "spl_network">,
+
+    spl_message_budget_forecast:
+        AdditionalOutput<Option<SplMessageBudgetForecast>, "spl_message_budget_forecast">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub spl_message_grants: MainOutput<SplMessageKinds<bool>>,
+}
+
+impl MessageBudgetPlanner {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {})
+    }
+
+    pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        let forecast = context
+            .filtered_game_controller_state
+            .map(|game_controller_state| {
+                forecast_budget(
+                    game_controller_state,
+                    context.spl_message_budget,
+                    context
+                        .spl_network_parameters
+                        .remaining_amount_of_messages_to_stop_sending,
+                )
+            });
+        // Without a GameController there is no budget to plan for
+        let send_intervals = forecast.map_or(
+            SplMessageKinds {
+                striker: Some(context.spl_message_budget.minimum_send_intervals.striker),
+                loser: Some(context.spl_message_budget.minimum_send_intervals.loser),
+                obstacles: Some(context.spl_message_budget.minimum_send_intervals.obstacles),
+            },
+            |forecast| forecast.send_intervals,
+        );
+
+        let now = context.cycle_time.start_time;
+        let sent_spl_messages = *context.sent_spl_messages;
+        let is_silence_period_elapsed = is_interval_elapsed(
+            now,
+            sent_spl_messages.last_sent,
+            context
+                .spl_network_parameters
+                .silence_interval_between_messages,
+        );
+        let is_granted = |kind: SplMessageKind| {
+            // Loser messages hand over the striker role and must not be held back by silence
+            let respects_silence_period =
+                kind == SplMessageKind::Loser || is_silence_period_elapsed;
+            send_intervals[kind].is_some_and(|interval| {
+                respects_silence_period
+                    && is_interval_elapsed(
+                        now,
+                        sent_spl_messages.last_sent_per_kind[kind],
+                        interval,
+                    )
+            })
+        };
+        let spl_message_grants = SplMessageKinds {
+            striker: is_granted(SplMessageKind::Striker),
+            loser: is_granted(SplMessageKind::Loser),
+            obstacles: is_granted(SplMessageKind::Obstacles),
+        };
+
+        context
+            .spl_message_budget_forecast
+            .fill_if_subscribed(|| forecast);
+
+        Ok(MainOutputs {
+            spl_message_grants: spl_message_grants.into(),
+        })
+    }
+}
+
+fn forecast_budget(
+    game_controller_state: &FilteredGameControllerState,
+    parameters: &SplMessageBudgetParameters,
+    reserved_messages: u16,
+) -> SplMessageBudgetForecast {
+    let remaining_game_time = match game_controller_state.half {
+        Half::First => game_controller_state.remaining_time_in_half + parameters.half_duration,
+        Half::Second => game_controller_state.remaining_time_in_half,
+    };
+    let remaining_messages = game_controller_state.remaining_number_of_messages;
+    let usable_messages = remaining_messages.saturating_sub(reserved_messages);
+    let number_of_active_players = game_controller_state
+        .penalties
+        .iter()
+        .filter(|(_player_number, penalty)| penalty.is_none())
+        .count()
+        .max(1);
+
+    let sustainable_team_interval =
+        (usable_messages > 0).then(|| remaining_game_time / u32::from(usable_messages));
+    let send_interval = |kind: SplMessageKind| {
+        let priority = parameters.priorities[kind];
+        if priority <= 0.0 {
+            return None;
+        }
+        sustainable_team_interval.map(|interval| {
+            interval
+                .mul_f32(number_of_senders(kind, number_of_active_players) as f32 / priority)
+                .max(parameters.minimum_send_intervals[kind])
+        })
+    };
+    let send_intervals = SplMessageKinds {
+        striker: send_interval(SplMessageKind::Striker),
+        loser: send_interval(SplMessageKind::Loser),
+        obstacles: send_interval(SplMessageKind::Obstacles),
+    };
+    let expected_messages_until_end_of_game = [SplMessageKind::Striker, SplMessageKind::Obstacles]
+        .into_iter()
+        .filter_map(|kind| {
+            let interval = send_intervals[kind]?;
+            (!interval.is_zero()).then(|| {
+                remaining_game_time.as_secs_f32() / interval.as_secs_f32()
+                    * number_of_senders(kind, number_of_active_players) as f32
+            })
+        })
+        .sum();
+
+    SplMessageBudgetForecast {
+        remaining_messages,
+        usable_messages,
+        remaining_game_time,
+        sustainable_team_interval,
+        send_intervals,
+        expected_messages_until_end_of_game,
+    }
+}
+
+fn number_of_senders(kind: SplMessageKind, number_of_active_players: usize) -> usize {
+    // Only the striker sends striker and loser messages, everyone shares obstacles
+    match kind {
+        SplMessageKind::Striker | SplMessageKind::Loser => 1,
+        SplMessageKind::Obstacles => number_of_active_players,
+    }
+}
+
+fn is_interval_elapsed(now: SystemTime, last: Option<SystemTime>, interval: Duration) -> bool {
+    match last {
+        None => true,
+        Some(last_time) => now.duration_since(last_time).expect("time ran backwards") >= interval,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use spl_network_messages::Penalty;
+
+    use super::*;
+
+    fn parameters() -> SplMessageBudgetParameters {
+        SplMessageBudgetParameters {
+            half_duration: Duration::from_secs(600),
+            minimum_send_intervals: SplMessageKinds {
+                striker: Duration::from_secs(2),
+                loser: Duration::ZERO,
+                obstacles: Duration::from_secs(10),
+            },
+            priorities: SplMessageKinds {
+                striker: 1.0,
+                loser: 1.0,
+                obstacles: 0.25,
+            },
+        }
+    }
+
+    fn game_controller_state(
+        half: Half,
+        remaining_time_in_half: Duration,
+        remaining_number_of_messages: u16,
+    ) -> FilteredGameControllerState {
+        let mut state = FilteredGameControllerState {
+            half,
+            remaining_time_in_half,
+            remaining_number_of_messages,
+            ..Default::default()
+        };
+        state.penalties.six = Some(Penalty::Substitute {
+            remaining: Duration::ZERO,
+        });
+        state.penalties.seven = Some(Penalty::Substitute {
+            remaining: Duration::ZERO,
+        });
+        state
+    }
+
+    #[test]
+    fn full_budget_allows_minimum_intervals() {
+        let state = game_controller_state(Half::First, Duration::from_secs(600), 1200);
+
+        let forecast = forecast_budget(&state, &parameters(), 20);
+
+        assert_eq!(forecast.usable_messages, 1180);
+        assert_eq!(forecast.remaining_game_time, Duration::from_secs(1200));
+        assert_eq!(
+            forecast.send_intervals.striker,
+            Some(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn shrinking_budget_stretches_low_priority_kinds_first() {
+        let state = game_controller_state(Half::Second, Duration::from_secs(300), 120);
+
+        let forecast = forecast_budget(&state, &parameters(), 20);
+
+        assert_eq!(
+            forecast.sustainable_team_interval,
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            forecast.send_intervals.striker,
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            forecast.send_intervals.obstacles,
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn reserve_is_never_granted() {
+        let state = game_controller_state(Half::Second, Duration::from_secs(120), 20);
+
+        let forecast = forecast_budget(&state, &parameters(), 20);
+
+        assert_eq!(forecast.usable_messages, 0);
+        assert_eq!(forecast.send_intervals.striker, None);
+        assert_eq!(forecast.send_intervals.loser, None);
+        assert_eq!(forecast.send_intervals.obstacles, None);
+    }
+}
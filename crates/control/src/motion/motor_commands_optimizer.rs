@@ -3,9 +3,10 @@ use context_attribute::context;
 use framework::MainOutput;
 use serde::{Deserialize, Serialize};
 use types::{
-    joints::Joints,
+    joints::{Joints, JointsName},
     motion_command::{HeadMotion, MotionCommand},
     motor_commands::MotorCommands,
+    thermal_protection::ThermalProtection,
 };
 
 #[derive(Deserialize, Serialize)]
@@ -20,6 +21,7 @@ pub struct CycleContext {
     only_one_foot_has_ground_contact: Input<bool, "only_one_foot_has_ground_contact">,
     has_ground_contact: Input<bool, "has_ground_contact">,
     motion_command: Input<MotionCommand, "motion_command">,
+    thermal_protection: Input<ThermalProtection, "thermal_protection">,
 }
 
 #[context]
@@ -48,6 +50,13 @@ impl MotorCommandsOptimizer {
             motor_commands.stiffnesses = Joints::fill(0.3);
         }
 
+        // leg stiffnesses are reduced by the walking engine which compensates the stiffness loss
+        for (joint, factor) in context.thermal_protection.stiffness_factors.enumerate() {
+            if !matches!(joint, JointsName::LeftLeg(_) | JointsName::RightLeg(_)) {
+                motor_commands.stiffnesses[joint] *= factor;
+            }
+        }
+
         Ok(MainOutputs {
             optimized_motor_commands: motor_commands.into(),
         })
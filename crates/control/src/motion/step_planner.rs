@@ -11,6 +11,7 @@ use types::{
     planned_path::PathSegment,
     step::Step,
     support_foot::Side,
+    thermal_protection::ThermalProtection,
 };
 use walking_engine::mode::Mode;
 
@@ -25,6 +26,7 @@ pub struct CreationContext {}
 #[context]
 pub struct CycleContext {
     motion_command: Input<MotionCommand, "motion_command">,
+    thermal_protection: Input<ThermalProtection, "thermal_protection">,
 
     injected_step: Parameter<Option<Step>, "step_planner.injected_step?">,
     max_step_size: Parameter<Step, "step_planner.max_step_size">,
@@ -108,6 +110,11 @@ impl StepPlanner {
             Step::default()
         };
 
+        let speed = if context.thermal_protection.limit_walk_speed {
+            WalkSpeed::Slow
+        } else {
+            *speed
+        };
         let max_step_size = match speed {
             WalkSpeed::Slow => *context.max_step_size + *context.step_size_delta_slow,
             WalkSpeed::Normal => *context.max_step_size + initial_side_bonus,
@@ -17,6 +17,7 @@ use types::{
     sensor_data::SensorData,
     step::Step,
     support_foot::Side,
+    thermal_protection::ThermalProtection,
     walk_command::WalkCommand,
 };
 use walking_engine::{kick_steps::KickSteps, mode::Mode, parameters::Parameters, Context, Engine};
@@ -54,6 +55,7 @@ pub struct CycleContext {
     zero_moment_point: Input<Point2<Ground>, "zero_moment_point">,
     number_of_consecutive_cycles_zero_moment_point_outside_support_polygon:
         Input<i32, "number_of_consecutive_cycles_zero_moment_point_outside_support_polygon">,
+    thermal_protection: Input<ThermalProtection, "thermal_protection">,
 
     debug_output: AdditionalOutput<Engine, "walking.engine">,
     last_actuated_joints: AdditionalOutput<BodyJoints, "walking.last_actuated_joints">,
@@ -145,6 +147,13 @@ impl WalkingEngine {
             zero_moment_point: cycle_context.zero_moment_point,
             number_of_consecutive_cycles_zero_moment_point_outside_support_polygon: cycle_context
                 .number_of_consecutive_cycles_zero_moment_point_outside_support_polygon,
+            leg_stiffness_factor: cycle_context
+                .thermal_protection
+                .stiffness_factors
+                .left_leg
+                .into_iter()
+                .chain(cycle_context.thermal_protection.stiffness_factors.right_leg)
+                .fold(1.0, f32::min),
         };
 
         match *cycle_context.walk_command {
@@ -6,12 +6,13 @@ use coordinate_systems::{Field, Ground};
 use filtering::kalman_filter::KalmanFilter;
 use framework::{AdditionalOutput, HistoricInput, MainOutput, PerceptionInput};
 use itertools::{chain, iproduct};
-use linear_algebra::{distance, point, IntoFramed, Isometry2, Point2};
-use nalgebra::Matrix2;
+use linear_algebra::{distance, point, Isometry2, Point2};
+use nalgebra::{matrix, vector, Matrix2, Matrix2x4, Matrix4, Matrix4x2};
 use serde::{Deserialize, Serialize};
 use types::{
     cycle_time::CycleTime,
     detected_feet::DetectedFeet,
+    detected_robots::DetectedRobot,
     field_dimensions::FieldDimensions,
     foot_bumper_obstacle::FootBumperObstacle,
     multivariate_normal_distribution::MultivariateNormalDistribution,
@@ -26,6 +27,7 @@ use types::{
 pub struct ObstacleFilter {
     hypotheses: Vec<Hypothesis>,
     last_primary_state: PrimaryState,
+    last_prediction_time: Option<SystemTime>,
 }
 
 #[context]
@@ -57,6 +59,8 @@ pub struct CycleContext {
 
     detected_feet_bottom: PerceptionInput<DetectedFeet, "VisionBottom", "detected_feet">,
     detected_feet_top: PerceptionInput<DetectedFeet, "VisionTop", "detected_feet">,
+    detected_robots_top:
+        PerceptionInput<Vec<DetectedRobot>, "ObjectDetectionTop", "detected_robots">,
 }
 
 #[context]
@@ -70,12 +74,18 @@ impl ObstacleFilter {
         Ok(Self {
             hypotheses: Vec::new(),
             last_primary_state: PrimaryState::Unstiff,
+            last_prediction_time: None,
         })
     }
 
     pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
         let field_dimensions = context.field_dimensions;
         let cycle_start_time = context.cycle_time.start_time;
+        let initial_velocity_covariance = Matrix2::from_diagonal(
+            &context
+                .obstacle_filter_parameters
+                .initial_velocity_covariance,
+        );
         let measurements = context
             .detected_feet_top
             .persistent
@@ -88,9 +98,18 @@ impl ObstacleFilter {
                 .copied()
                 .unwrap_or_default();
 
-            self.predict_hypotheses_with_odometry(
+            let delta_time = self
+                .last_prediction_time
+                .and_then(|last_prediction_time| {
+                    detection_time.duration_since(last_prediction_time).ok()
+                })
+                .unwrap_or_default();
+            self.last_prediction_time = Some(*detection_time);
+            self.predict_hypotheses(
+                delta_time,
                 current_odometry_to_last_odometry.inverse(),
-                Matrix2::from_diagonal(&context.obstacle_filter_parameters.process_noise),
+                context.obstacle_filter_parameters.velocity_decay,
+                Matrix4::from_diagonal(&context.obstacle_filter_parameters.process_noise),
             );
 
             let network_robot_obstacles = context.network_robot_obstacles.get(detection_time);
@@ -111,6 +130,7 @@ impl ObstacleFilter {
                             .obstacle_filter_parameters
                             .network_robot_measurement_noise,
                     ),
+                    initial_velocity_covariance,
                 );
             }
 
@@ -134,6 +154,7 @@ impl ObstacleFilter {
                         Matrix2::from_diagonal(
                             &context.obstacle_filter_parameters.feet_measurement_noise,
                         ),
+                        initial_velocity_covariance,
                     );
                 }
             }
@@ -159,6 +180,7 @@ impl ObstacleFilter {
                         Matrix2::from_diagonal(
                             &context.obstacle_filter_parameters.sonar_measurement_noise,
                         ),
+                        initial_velocity_covariance,
                     );
                 }
             }
@@ -179,11 +201,42 @@ impl ObstacleFilter {
                         Matrix2::from_diagonal(
                             &context.obstacle_filter_parameters.feet_measurement_noise,
                         ),
+                        initial_velocity_covariance,
                     );
                 }
             }
         }
 
+        if context
+            .obstacle_filter_parameters
+            .use_robot_detection_measurements
+        {
+            // Teammates are already known from the network, only track everyone else
+            let detected_robots = context.detected_robots_top.persistent.iter().flat_map(
+                |(detection_time, robots)| {
+                    robots
+                        .iter()
+                        .flat_map(|robots| robots.iter())
+                        .filter(|robot| robot.is_teammate != Some(true))
+                        .filter_map(move |robot| Some((*detection_time, robot.ground_position?)))
+                },
+            );
+            for (detection_time, position) in detected_robots {
+                self.update_hypotheses_with_measurement(
+                    position,
+                    ObstacleKind::Robot,
+                    detection_time,
+                    context
+                        .obstacle_filter_parameters
+                        .robot_detection_measurement_matching_distance,
+                    Matrix2::from_diagonal(
+                        &context.obstacle_filter_parameters.robot_measurement_noise,
+                    ),
+                    initial_velocity_covariance,
+                );
+            }
+        }
+
         self.remove_hypotheses(
             cycle_start_time,
             context.obstacle_filter_parameters.hypothesis_timeout,
@@ -219,10 +272,11 @@ impl ObstacleFilter {
                     _ => panic!("Unexpected obstacle radius"),
                 };
                 Obstacle {
-                    position: hypothesis.state.mean.framed().as_point(),
+                    position: hypothesis.position(),
                     kind: hypothesis.obstacle_kind,
                     radius_at_hip_height,
                     radius_at_foot_height,
+                    velocity: hypothesis.velocity(),
                 }
             })
             .collect::<Vec<_>>();
@@ -243,22 +297,40 @@ impl ObstacleFilter {
         })
     }
 
-    fn predict_hypotheses_with_odometry(
+    fn predict_hypotheses(
         &mut self,
+        delta_time: Duration,
         last_odometry_to_current_odometry: nalgebra::Isometry2<f32>,
-        process_noise: Matrix2<f32>,
+        velocity_decay: f32,
+        process_noise: Matrix4<f32>,
     ) {
+        let dt = delta_time.as_secs_f32();
+        let velocity_decay = velocity_decay.powf(dt);
+        let constant_velocity_prediction = matrix![
+            1.0, 0.0, dt, 0.0;
+            0.0, 1.0, 0.0, dt;
+            0.0, 0.0, velocity_decay, 0.0;
+            0.0, 0.0, 0.0, velocity_decay;
+        ];
+        let rotation = last_odometry_to_current_odometry
+            .rotation
+            .to_rotation_matrix();
+        let rotation = rotation.matrix();
+        let state_rotation = matrix![
+            rotation.m11, rotation.m12, 0.0, 0.0;
+            rotation.m21, rotation.m22, 0.0, 0.0;
+            0.0, 0.0, rotation.m11, rotation.m12;
+            0.0, 0.0, rotation.m21, rotation.m22;
+        ];
+        let state_prediction = constant_velocity_prediction * state_rotation;
+        let odometry_translation = last_odometry_to_current_odometry.translation.vector;
+
         for hypothesis in self.hypotheses.iter_mut() {
-            let state_prediction = last_odometry_to_current_odometry
-                .rotation
-                .to_rotation_matrix();
-            let control_input_model = Matrix2::identity();
-            let odometry_translation = last_odometry_to_current_odometry.translation.vector;
             hypothesis.state.predict(
-                *state_prediction.matrix(),
-                control_input_model,
+                state_prediction,
+                Matrix4x2::identity(),
                 odometry_translation,
-                process_noise,
+                process_noise * dt,
             )
         }
     }
@@ -270,12 +342,13 @@ impl ObstacleFilter {
         detection_time: SystemTime,
         matching_distance: f32,
         measurement_noise: Matrix2<f32>,
+        initial_velocity_covariance: Matrix2<f32>,
     ) {
         let mut matching_hypotheses = self
             .hypotheses
             .iter_mut()
             .filter(|hypothesis| {
-                (hypothesis.state.mean - detected_position.inner.coords).norm() < matching_distance
+                distance(hypothesis.position(), detected_position) < matching_distance
             })
             .peekable();
         if matching_hypotheses.peek().is_none() {
@@ -284,12 +357,13 @@ impl ObstacleFilter {
                 detected_obstacle_kind,
                 detection_time,
                 measurement_noise,
+                initial_velocity_covariance,
             );
             return;
         }
         matching_hypotheses.for_each(|hypothesis| {
             hypothesis.state.update(
-                Matrix2::identity(),
+                Matrix2x4::identity(),
                 detected_position.inner.coords,
                 measurement_noise * (detected_position.coords().norm_squared() + f32::EPSILON),
             );
@@ -308,9 +382,17 @@ impl ObstacleFilter {
         detected_position: Point2<Ground>,
         obstacle_kind: ObstacleKind,
         detection_time: SystemTime,
-        initial_covariance: Matrix2<f32>,
+        initial_position_covariance: Matrix2<f32>,
+        initial_velocity_covariance: Matrix2<f32>,
     ) {
-        let initial_state = detected_position.inner.coords;
+        let initial_state = vector![detected_position.x(), detected_position.y(), 0.0, 0.0];
+        let mut initial_covariance = Matrix4::zeros();
+        initial_covariance
+            .fixed_view_mut::<2, 2>(0, 0)
+            .copy_from(&initial_position_covariance);
+        initial_covariance
+            .fixed_view_mut::<2, 2>(2, 2)
+            .copy_from(&initial_velocity_covariance);
         let new_hypothesis = Hypothesis {
             state: MultivariateNormalDistribution {
                 mean: initial_state,
@@ -340,13 +422,13 @@ impl ObstacleFilter {
                 deduplicated_hypotheses
                     .iter_mut()
                     .find(|existing_hypothesis| {
-                        (existing_hypothesis.state.mean - hypothesis.state.mean).norm()
+                        distance(existing_hypothesis.position(), hypothesis.position())
                             < merge_distance
                     });
             match hypothesis_in_merge_distance {
                 Some(existing_hypothesis) => {
                     existing_hypothesis.state.update(
-                        Matrix2::identity(),
+                        Matrix4::identity(),
                         hypothesis.state.mean,
                         hypothesis.state.covariance,
                     );
@@ -386,3 +468,49 @@ fn calculate_goal_post_positions(
         .flatten()
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn velocity_of_walking_robot_is_estimated() {
+        let mut filter = ObstacleFilter {
+            hypotheses: Vec::new(),
+            last_primary_state: PrimaryState::Playing,
+            last_prediction_time: None,
+        };
+        let cycle_time = Duration::from_millis(100);
+        let measurement_noise = Matrix2::from_diagonal(&nalgebra::vector![0.01, 0.01]);
+        let initial_velocity_covariance = Matrix2::from_diagonal(&nalgebra::vector![0.1, 0.1]);
+
+        for cycle in 0..50 {
+            let time = cycle as f32 * cycle_time.as_secs_f32();
+            filter.predict_hypotheses(
+                cycle_time,
+                nalgebra::Isometry2::identity(),
+                1.0,
+                Matrix4::from_diagonal(&nalgebra::vector![0.01, 0.01, 0.01, 0.01]),
+            );
+            filter.update_hypotheses_with_measurement(
+                point![1.0, 1.0 - 0.2 * time],
+                ObstacleKind::Robot,
+                UNIX_EPOCH + cycle_time * cycle,
+                0.5,
+                measurement_noise,
+                initial_velocity_covariance,
+            );
+        }
+
+        assert_eq!(filter.hypotheses.len(), 1);
+        assert_relative_eq!(
+            filter.hypotheses[0].velocity(),
+            linear_algebra::vector![0.0, -0.2],
+            epsilon = 0.02
+        );
+    }
+}
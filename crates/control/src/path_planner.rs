@@ -1,4 +1,5 @@
 use color_eyre::{eyre::eyre, Result};
+use filtering::hysteresis::less_than_with_absolute_hysteresis;
 use geometry::{
     arc::Arc,
     circle::Circle,
@@ -15,6 +16,7 @@ use types::{
     field_dimensions::FieldDimensions,
     motion_command::MotionCommand,
     obstacles::Obstacle,
+    parameters::MovingObstaclesParameters,
     path_obstacles::{PathObstacle, PathObstacleShape},
     planned_path::PathSegment,
     rule_obstacles::RuleObstacle,
@@ -47,6 +49,7 @@ pub struct PathPlanner {
     pub nodes: Vec<PathNode>,
     pub obstacles: Vec<PathObstacle>,
     pub last_path_direction: Option<Orientation2<Ground>>,
+    pub last_path: Vec<PathSegment>,
     pub rotation_penalty_factor: f32,
 }
 
@@ -72,6 +75,10 @@ impl PathPlanner {
             }),
             _ => None,
         };
+        self.last_path = match last_motion_command {
+            MotionCommand::Walk { path, .. } => path.clone(),
+            _ => Vec::new(),
+        };
 
         self.rotation_penalty_factor = rotation_penalty_factor;
     }
@@ -86,6 +93,62 @@ impl PathPlanner {
         self.obstacles.extend(new_obstacles);
     }
 
+    /// Adds the obstacles like [`Self::with_obstacles`] and additionally sweeps moving obstacles
+    /// along their velocity over the time horizon. A swept position is only added if the robot,
+    /// starting at the origin, could reach it before the obstacle does. Positions close to this
+    /// boundary keep the decision of the last path to avoid flickering between crossing in front
+    /// of an obstacle and walking around it.
+    pub fn with_moving_obstacles(
+        &mut self,
+        obstacles: &[Obstacle],
+        own_robot_radius: f32,
+        own_walking_speed: f32,
+        parameters: &MovingObstaclesParameters,
+    ) {
+        self.with_obstacles(obstacles, own_robot_radius);
+        if parameters.sample_interval.is_zero() || own_walking_speed <= 0.0 {
+            return;
+        }
+
+        let sample_interval = parameters.sample_interval.as_secs_f32();
+        let number_of_samples = (parameters.time_horizon.as_secs_f32() / sample_interval) as usize;
+        let arrival_time_hysteresis = parameters.arrival_time_hysteresis.as_secs_f32();
+
+        for obstacle in obstacles
+            .iter()
+            .filter(|obstacle| obstacle.velocity.norm() >= parameters.minimum_speed)
+        {
+            let radius = obstacle.radius_at_hip_height + own_robot_radius;
+            let swept_circles: Vec<_> = (1..=number_of_samples)
+                .map(|sample| {
+                    let time = sample as f32 * sample_interval;
+                    let center = obstacle.position + obstacle.velocity * time;
+                    (time, Circle::new(center, radius))
+                })
+                .collect();
+            let was_avoiding = !self.last_path.is_empty()
+                && swept_circles.iter().all(|(_, circle)| {
+                    self.last_path
+                        .iter()
+                        .all(|segment| !segment_overlaps_circle(segment, circle))
+                });
+
+            let new_obstacles = swept_circles
+                .into_iter()
+                .filter(|(time, circle)| {
+                    let arrival_time = (circle.center.coords().norm() - circle.radius).max(0.0)
+                        / own_walking_speed;
+                    less_than_with_absolute_hysteresis(
+                        was_avoiding,
+                        arrival_time - time,
+                        0.0..=arrival_time_hysteresis,
+                    )
+                })
+                .map(|(_, circle)| PathObstacle::from(circle));
+            self.obstacles.extend(new_obstacles);
+        }
+    }
+
     pub fn with_rule_obstacles(
         &mut self,
         field_to_robot: Isometry2<Field, Ground>,
@@ -471,6 +534,13 @@ impl PathPlanner {
     }
 }
 
+fn segment_overlaps_circle(segment: &PathSegment, circle: &Circle<Ground>) -> bool {
+    match segment {
+        PathSegment::LineSegment(line_segment) => circle.intersects_line_segment(line_segment),
+        PathSegment::Arc(arc) => circle.overlaps_arc(*arc),
+    }
+}
+
 impl DynamicMap for PathPlanner {
     fn get_pathing_distance(&self, index1: usize, index2: usize) -> f32 {
         let direction = self.nodes[index2].position - self.nodes[index1].position;
@@ -543,7 +613,10 @@ impl DynamicMap for PathPlanner {
 
 #[cfg(test)]
 mod tests {
-    use std::f32::consts::{FRAC_PI_3, PI};
+    use std::{
+        f32::consts::{FRAC_PI_3, PI},
+        time::Duration,
+    };
 
     use approx::assert_relative_eq;
     use linear_algebra::point;
@@ -839,4 +912,53 @@ mod tests {
             .expect("Path error")
             .is_none());
     }
+
+    fn moving_obstacles_parameters() -> MovingObstaclesParameters {
+        MovingObstaclesParameters {
+            enable: true,
+            minimum_speed: 0.05,
+            time_horizon: Duration::from_secs(3),
+            sample_interval: Duration::from_millis(500),
+            arrival_time_hysteresis: Duration::from_millis(500),
+        }
+    }
+
+    #[test]
+    fn avoids_obstacle_crossing_path() {
+        let obstacle = Obstacle {
+            velocity: vector![0.0, -0.2],
+            ..Obstacle::robot(point![1.0, 0.6], 0.1, 0.1)
+        };
+        let mut map = PathPlanner::default();
+        map.with_moving_obstacles(&[obstacle], 0.15, 0.5, &moving_obstacles_parameters());
+
+        let path = map
+            .plan(Point2::origin(), point![2.0, 0.0])
+            .expect("Path error")
+            .expect("Path was none");
+
+        assert!(path.len() > 1);
+        assert!(path.iter().map(PathSegment::length).sum::<f32>() > 2.0);
+    }
+
+    #[test]
+    fn ignores_obstacle_moving_away_from_path() {
+        let obstacle = Obstacle {
+            velocity: vector![0.0, 0.2],
+            ..Obstacle::robot(point![1.0, 0.6], 0.1, 0.1)
+        };
+        let mut map = PathPlanner::default();
+        map.with_moving_obstacles(&[obstacle], 0.15, 0.5, &moving_obstacles_parameters());
+
+        run_test_scenario(
+            Point2::origin(),
+            point![2.0, 0.0],
+            &mut map,
+            &[PathSegment::LineSegment(LineSegment(
+                Point2::origin(),
+                point![2.0, 0.0],
+            ))],
+            2.0,
+        );
+    }
 }
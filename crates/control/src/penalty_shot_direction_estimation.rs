@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use spl_network_messages::{GamePhase, SubState, Team};
 use types::{
     ball_position::BallPosition,
+    ball_trajectory::BallTrajectory,
     field_dimensions::{FieldDimensions, Half},
     filtered_game_controller_state::FilteredGameControllerState,
     parameters::PenaltyShotDirectionParameters,
@@ -32,6 +33,7 @@ pub struct CycleContext {
         Parameter<f32, "behavior.path_planning.minimum_robot_radius_at_foot_height">,
 
     ball_position: RequiredInput<Option<BallPosition<Ground>>, "ball_position?">,
+    ball_trajectory: Input<Option<BallTrajectory>, "ball_trajectory?">,
     filtered_game_controller_state:
         RequiredInput<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
     primary_state: Input<PrimaryState, "primary_state">,
@@ -72,22 +74,30 @@ impl PenaltyShotDirectionEstimation {
                 let reference_position = self
                     .placed_ball_position
                     .unwrap_or(penalty_marker_position_in_ground);
-                let side_jump_threshold =
-                    (context.penalty_shot_parameters.moving_distance_threshold
-                        * (context.minimum_robot_radius_at_foot_height
-                            + context.penalty_shot_parameters.center_jump_trigger_radius))
-                        / context.field_dimensions.penalty_marker_distance;
+                let predicted_crossing = context
+                    .ball_trajectory
+                    .and_then(|trajectory| trajectory.first_crossing_of_x(0.0));
+                let (lateral_offset, side_jump_threshold) = match predicted_crossing {
+                    Some(crossing) => (
+                        crossing.position.y(),
+                        context.minimum_robot_radius_at_foot_height
+                            + context.penalty_shot_parameters.center_jump_trigger_radius,
+                    ),
+                    None => (
+                        context.ball_position.position.y() - reference_position.y(),
+                        (context.penalty_shot_parameters.moving_distance_threshold
+                            * (context.minimum_robot_radius_at_foot_height
+                                + context.penalty_shot_parameters.center_jump_trigger_radius))
+                            / context.field_dimensions.penalty_marker_distance,
+                    ),
+                };
                 if let PenaltyShotDirection::NotMoving = self.last_shot_direction {
                     if context.ball_position.velocity.x()
                         <= context.penalty_shot_parameters.minimum_velocity
                     {
-                        if context.ball_position.position.y() - reference_position.y()
-                            > side_jump_threshold
-                        {
+                        if lateral_offset > side_jump_threshold {
                             self.last_shot_direction = PenaltyShotDirection::Left
-                        } else if context.ball_position.position.y() - reference_position.y()
-                            < -side_jump_threshold
-                        {
+                        } else if lateral_offset < -side_jump_threshold {
                             self.last_shot_direction = PenaltyShotDirection::Right
                         } else {
                             self.last_shot_direction = PenaltyShotDirection::Center
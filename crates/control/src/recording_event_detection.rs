@@ -0,0 +1,154 @@
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use hardware::RecordingInterface;
+use types::{
+    fall_state::FallState, parameters::RecordingEventDetectionParameters,
+    primary_state::PrimaryState, recording_event::RecordingEvent, roles::Role,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct RecordingEventDetection {
+    last_fall_state: FallState,
+    last_role: Option<Role>,
+    last_primary_state: PrimaryState,
+    was_localization_converged: bool,
+}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    fall_state: Input<FallState, "fall_state">,
+    is_localization_converged: Input<bool, "is_localization_converged">,
+    primary_state: Input<PrimaryState, "primary_state">,
+    role: Input<Role, "role">,
+
+    parameters: Parameter<RecordingEventDetectionParameters, "recording_event_detection">,
+
+    hardware_interface: HardwareInterface,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {}
+
+impl RecordingEventDetection {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {
+            last_fall_state: FallState::Upright,
+            last_role: None,
+            last_primary_state: PrimaryState::Unstiff,
+            was_localization_converged: false,
+        })
+    }
+
+    pub fn cycle(&mut self, context: CycleContext<impl RecordingInterface>) -> Result<MainOutputs> {
+        for event in self.detect_events(
+            *context.fall_state,
+            *context.is_localization_converged,
+            *context.primary_state,
+            *context.role,
+            context.parameters,
+        ) {
+            context.hardware_interface.trigger_recording_event(event);
+        }
+        Ok(MainOutputs {})
+    }
+
+    fn detect_events(
+        &mut self,
+        fall_state: FallState,
+        is_localization_converged: bool,
+        primary_state: PrimaryState,
+        role: Role,
+        parameters: &RecordingEventDetectionParameters,
+    ) -> Vec<RecordingEvent> {
+        let is_falling = |fall_state| matches!(fall_state, FallState::Falling { .. });
+        let has_started_falling = is_falling(fall_state) && !is_falling(self.last_fall_state);
+        let has_changed_role = self.last_role.is_some_and(|last_role| last_role != role);
+        let got_penalized = primary_state == PrimaryState::Penalized
+            && self.last_primary_state != PrimaryState::Penalized;
+        let has_diverged = self.was_localization_converged && !is_localization_converged;
+
+        self.last_fall_state = fall_state;
+        self.last_role = Some(role);
+        self.last_primary_state = primary_state;
+        self.was_localization_converged = is_localization_converged;
+
+        [
+            (parameters.fall && has_started_falling, RecordingEvent::Fall),
+            (
+                parameters.role_change && has_changed_role,
+                RecordingEvent::RoleChange,
+            ),
+            (parameters.penalty && got_penalized, RecordingEvent::Penalty),
+            (
+                parameters.localization_divergence && has_diverged,
+                RecordingEvent::LocalizationDivergence,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(occurred, event)| occurred.then_some(event))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use types::fall_state::{Direction, Side};
+
+    use super::*;
+
+    #[test]
+    fn events_are_detected_once_on_transitions() {
+        let parameters = RecordingEventDetectionParameters {
+            fall: true,
+            role_change: true,
+            penalty: false,
+            localization_divergence: true,
+        };
+        let mut detection = RecordingEventDetection::new(CreationContext {}).unwrap();
+        let falling = FallState::Falling {
+            start_time: SystemTime::UNIX_EPOCH,
+            direction: Direction::Forward { side: Side::Left },
+        };
+
+        assert!(detection
+            .detect_events(
+                FallState::Upright,
+                true,
+                PrimaryState::Playing,
+                Role::Striker,
+                &parameters
+            )
+            .is_empty());
+        assert_eq!(
+            detection.detect_events(
+                falling,
+                false,
+                PrimaryState::Penalized,
+                Role::Keeper,
+                &parameters
+            ),
+            vec![
+                RecordingEvent::Fall,
+                RecordingEvent::RoleChange,
+                RecordingEvent::LocalizationDivergence
+            ]
+        );
+        assert!(detection
+            .detect_events(
+                falling,
+                false,
+                PrimaryState::Penalized,
+                Role::Keeper,
+                &parameters
+            )
+            .is_empty());
+    }
+}
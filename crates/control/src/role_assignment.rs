@@ -1,3 +1,5 @@
+mod field_roles;
+
 use std::{
     collections::{BTreeMap, VecDeque},
     net::SocketAddr,
@@ -14,10 +16,12 @@ use context_attribute::context;
 use coordinate_systems::{Field, Ground};
 use framework::{AdditionalOutput, MainOutput, PerceptionInput};
 use hardware::NetworkInterface;
-use linear_algebra::Isometry2;
+use linear_algebra::{distance, Isometry2, Point2, Pose2};
+use nalgebra::Matrix3;
 use spl_network_messages::{
-    GameControllerReturnMessage, GamePhase, HulkMessage, LoserMessage, Penalty, PlayerNumber,
-    StrikerMessage, SubState, Team,
+    BallSearchHotspot, GameControllerReturnMessage, GamePhase, HulkMessage, LoserMessage,
+    ObstacleMessage, Penalty, PlayerNumber, RobotStatus, SharedObstacle, StrikerMessage, SubState,
+    Team, MAXIMUM_NUMBER_OF_SHARED_OBSTACLES,
 };
 use types::{
     ball_position::BallPosition,
@@ -27,12 +31,18 @@ use types::{
     filtered_game_controller_state::FilteredGameControllerState,
     initial_pose::InitialPose,
     messages::{IncomingMessage, OutgoingMessage},
-    parameters::SplNetworkParameters,
+    obstacles::{Obstacle, ObstacleKind},
+    parameters::{RolePositionsParameters, RoleUtilityParameters, SplNetworkParameters},
     players::Players,
     primary_state::PrimaryState,
     roles::Role,
+    sensor_data::SensorData,
+    spl_message_budget::{SentSplMessages, SplMessageKind, SplMessageKinds},
+    teammate::Teammate,
 };
 
+use field_roles::{assign_field_roles, is_field_role, FieldPlayer};
+
 use crate::localization::generate_initial_pose;
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -45,10 +55,16 @@ enum SentState {
 pub struct RoleAssignment {
     last_received_striker_message: Option<SystemTime>,
     last_system_time_transmitted_game_controller_return_message: Option<SystemTime>,
-    last_transmitted_spl_message: Option<SystemTime>,
     role: Role,
     last_time_player_was_penalized: Players<Option<SystemTime>>,
     last_sent_state: SentState,
+    striker_player_number: Option<PlayerNumber>,
+    field_roles: Players<Option<Role>>,
+    last_shared_state: Option<(SystemTime, Pose2<Field>, RobotStatus)>,
+    last_shared_hotspot: Option<Point2<Field>>,
+    loser_since: Option<SystemTime>,
+    /// The loser message is retried until the message budget grants it or the role changes
+    loser_message_pending: bool,
 }
 
 #[context]
@@ -61,8 +77,6 @@ pub struct CreationContext {
 pub struct CycleContext {
     ball_position: Input<Option<BallPosition<Ground>>, "ball_position?">,
     fall_state: Input<FallState, "fall_state">,
-    remaining_amount_of_messages:
-        Input<Option<u16>, "game_controller_state?.hulks_team.remaining_amount_of_messages">,
     filtered_game_controller_state:
         Input<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
     primary_state: Input<PrimaryState, "primary_state">,
@@ -72,16 +86,31 @@ pub struct CycleContext {
     game_controller_address: Input<Option<SocketAddr>, "game_controller_address?">,
     time_to_reach_kick_position: Input<Option<Duration>, "time_to_reach_kick_position?">,
     team_ball: Input<Option<BallPosition<Field>>, "team_ball?">,
+    obstacles: Input<Vec<Obstacle>, "obstacles">,
+    network_robot_obstacles: Input<Vec<Point2<Ground>>, "network_robot_obstacles">,
+    team_obstacles: Input<Vec<Obstacle>, "team_obstacles">,
+    pose_covariance: Input<Option<Matrix3<f32>>, "pose_covariance?">,
+    sensor_data: Input<SensorData, "sensor_data">,
+    teammates: Input<Vec<Teammate>, "teammates">,
+    ball_search_hotspot: Input<Option<Point2<Field>>, "ball_search_hotspot?">,
+    spl_message_grants: Input<SplMessageKinds<bool>, "spl_message_grants">,
+    sent_spl_messages: CyclerState<SentSplMessages, "sent_spl_messages">,
 
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
     forced_role: Parameter<Option<Role>, "role_assignment.forced_role?">,
     keeper_replacementkeeper_switch_time:
         Parameter<Duration, "role_assignment.keeper_replacementkeeper_switch_time">,
     striker_trusts_team_ball: Parameter<Duration, "role_assignment.striker_trusts_team_ball">,
+    maximum_loser_duration: Parameter<Duration, "role_assignment.maximum_loser_duration">,
     initial_poses: Parameter<Players<InitialPose>, "localization.initial_poses">,
     optional_roles: Parameter<Vec<Role>, "behavior.optional_roles">,
     player_number: Parameter<PlayerNumber, "player_number">,
+    role_positions: Parameter<RolePositionsParameters, "behavior.role_positions">,
+    role_utilities: Parameter<RoleUtilityParameters, "role_assignment.utilities">,
     spl_network_parameters: Parameter<SplNetworkParameters, "spl_network">,
+    team_obstacle_merge_distance: Parameter<f32, "team_obstacles.merge_distance">,
+    teammate_maximum_age: Parameter<Duration, "teammate_receiver.maximum_age">,
+    hotspot_share_distance: Parameter<f32, "search_suggestor.hotspot_share_distance">,
 
     hardware: HardwareInterface,
 
@@ -89,6 +118,7 @@ pub struct CycleContext {
         AdditionalOutput<Players<Option<SystemTime>>, "last_time_player_penalized">,
     last_sent_state: AdditionalOutput<String, "last_sent_state">,
     last_sent_message: AdditionalOutput<String, "last_sent_message">,
+    field_roles: AdditionalOutput<Players<Option<Role>>, "field_roles">,
 }
 
 #[context]
@@ -113,10 +143,15 @@ impl RoleAssignment {
         Ok(Self {
             last_received_striker_message: None,
             last_system_time_transmitted_game_controller_return_message: None,
-            last_transmitted_spl_message: None,
             role,
             last_time_player_was_penalized: Players::new(None),
             last_sent_state: SentState::Loser,
+            striker_player_number: None,
+            field_roles: Players::new(None),
+            last_shared_state: None,
+            last_shared_hotspot: None,
+            loser_since: None,
+            loser_message_pending: false,
         })
     }
 
@@ -147,6 +182,9 @@ impl RoleAssignment {
 
         let role_from_state_machine =
             self.role_from_state_machine(&context, cycle_start_time, self.role);
+        let role_from_state_machine =
+            self.assign_field_role(&context, cycle_start_time, role_from_state_machine);
+        context.field_roles.fill_if_subscribed(|| self.field_roles);
 
         let mut new_role = [
             context.forced_role.copied(),
@@ -185,25 +223,38 @@ impl RoleAssignment {
             }
         }
 
+        // A loser which did not find the ball where it was lost joins the team's ball search
+        if new_role == Role::Loser {
+            let loser_since = *self.loser_since.get_or_insert(cycle_start_time);
+            if cycle_start_time
+                .duration_since(loser_since)
+                .expect("time ran backwards")
+                >= *context.maximum_loser_duration
+            {
+                new_role = Role::Searcher;
+            }
+        }
+        if new_role != Role::Loser {
+            self.loser_since = None;
+        }
+
         context
             .last_time_player_was_penalized
             .fill_if_subscribed(|| self.last_time_player_was_penalized);
+        if self.role == Role::Striker && new_role == Role::Loser {
+            self.loser_message_pending = true;
+        }
+        if new_role != Role::Loser {
+            self.loser_message_pending = false;
+        }
         if is_allowed_to_send_messages(&context) {
-            match (self.role, new_role) {
-                (Role::Striker, Role::Striker) => {
-                    if self.is_striker_beacon_cooldown_elapsed(&context) {
-                        self.try_sending_striker_message(&mut context)?;
-                    }
-                }
-                (_other_role, Role::Striker) => {
-                    self.try_sending_striker_message(&mut context)?;
-                }
-
-                (Role::Striker, Role::Loser) => {
-                    self.try_sending_loser_message(&mut context)?;
-                }
-                _ => {}
+            if new_role == Role::Striker {
+                self.try_sending_striker_message(&mut context)?;
+            }
+            if self.loser_message_pending {
+                self.try_sending_loser_message(&mut context)?;
             }
+            self.try_sending_obstacle_message(&mut context)?;
         }
 
         context
@@ -275,6 +326,7 @@ impl RoleAssignment {
                         .spl_striker_message_receive_timeout
                 {
                     self.last_received_striker_message = None;
+                    self.striker_player_number = None;
                     true
                 } else {
                     false
@@ -311,8 +363,13 @@ impl RoleAssignment {
 
         let mut new_role = current_role;
         for event in events {
-            if let Event::Striker(_) = event {
-                self.last_received_striker_message = Some(cycle_start_time)
+            match event {
+                Event::Striker(striker_event) => {
+                    self.last_received_striker_message = Some(cycle_start_time);
+                    self.striker_player_number = Some(striker_event.player_number);
+                }
+                Event::Loser => self.striker_player_number = None,
+                Event::None => {}
             }
 
             new_role = update_role_state_machine(
@@ -332,42 +389,87 @@ impl RoleAssignment {
         new_role
     }
 
-    fn is_return_message_cooldown_elapsed(
-        &self,
-        context: &CycleContext<impl NetworkInterface>,
-    ) -> bool {
-        is_cooldown_elapsed(
-            context.cycle_time.start_time,
-            self.last_system_time_transmitted_game_controller_return_message,
-            context
-                .spl_network_parameters
-                .game_controller_return_message_interval,
-        )
+    fn assign_field_role(
+        &mut self,
+        context: &CycleContext<'_, impl NetworkInterface>,
+        cycle_start_time: SystemTime,
+        role: Role,
+    ) -> Role {
+        let Some(game_controller_state) = context.filtered_game_controller_state else {
+            return role;
+        };
+        let striker = match role {
+            Role::Striker => Some(*context.player_number),
+            _ => self.striker_player_number,
+        };
+        let mut active_players = game_controller_state
+            .penalties
+            .iter()
+            .filter_map(|(player_number, penalty)| penalty.is_none().then_some(player_number))
+            .filter(|player_number| Some(*player_number) != striker);
+        // the lowest active player number is the (replacement) keeper
+        active_players.next();
+        let players: Vec<_> = active_players
+            .map(|player_number| FieldPlayer {
+                player_number,
+                shared_state: self.shared_state(context, cycle_start_time, player_number),
+            })
+            .collect();
+        // The own ball is not known to the team, so it would lead to diverging assignments
+        let ball = context
+            .team_ball
+            .map_or_else(Point2::origin, |team_ball| team_ball.position);
+
+        self.field_roles = assign_field_roles(
+            &players,
+            context.optional_roles,
+            ball,
+            &self.field_roles,
+            context.field_dimensions,
+            context.role_positions,
+            context.role_utilities,
+        );
+
+        match self.field_roles[*context.player_number] {
+            Some(field_role) if is_field_role(role) => field_role,
+            _ => role,
+        }
     }
 
-    fn is_striker_beacon_cooldown_elapsed(
+    /// Pose and status of a player as the whole team knows it, i.e. for the own player the last
+    /// sent state instead of the current one
+    fn shared_state(
         &self,
-        context: &CycleContext<impl NetworkInterface>,
-    ) -> bool {
-        is_cooldown_elapsed(
-            context.cycle_time.start_time,
-            self.last_transmitted_spl_message,
-            context
-                .spl_network_parameters
-                .spl_striker_message_send_interval,
-        )
+        context: &CycleContext<'_, impl NetworkInterface>,
+        cycle_start_time: SystemTime,
+        player_number: PlayerNumber,
+    ) -> Option<(Pose2<Field>, RobotStatus)> {
+        if player_number != *context.player_number {
+            return context
+                .teammates
+                .iter()
+                .find(|teammate| teammate.player_number == player_number)
+                .map(|teammate| (teammate.pose, teammate.status));
+        }
+        self.last_shared_state
+            .filter(|(time, ..)| {
+                cycle_start_time
+                    .duration_since(*time)
+                    .is_ok_and(|age| age < *context.teammate_maximum_age)
+            })
+            .map(|(_, pose, status)| (pose, status))
     }
 
-    fn is_striker_silence_period_elapsed(
+    fn is_return_message_cooldown_elapsed(
         &self,
         context: &CycleContext<impl NetworkInterface>,
     ) -> bool {
         is_cooldown_elapsed(
             context.cycle_time.start_time,
-            self.last_transmitted_spl_message,
+            self.last_system_time_transmitted_game_controller_return_message,
             context
                 .spl_network_parameters
-                .silence_interval_between_messages,
+                .game_controller_return_message_interval,
         )
     }
 
@@ -406,22 +508,13 @@ impl RoleAssignment {
         &mut self,
         context: &mut CycleContext<impl NetworkInterface>,
     ) -> Result<()> {
-        if !self.is_striker_silence_period_elapsed(context) {
-            return Ok(());
-        }
-        if context
-            .remaining_amount_of_messages
-            .is_some_and(|remaining_amount_of_messages| {
-                *remaining_amount_of_messages
-                    < context
-                        .spl_network_parameters
-                        .remaining_amount_of_messages_to_stop_sending
-            })
-        {
+        if !context.spl_message_grants[SplMessageKind::Striker] {
             return Ok(());
         }
 
-        self.last_transmitted_spl_message = Some(context.cycle_time.start_time);
+        context
+            .sent_spl_messages
+            .record(SplMessageKind::Striker, context.cycle_time.start_time);
         self.last_received_striker_message = None;
 
         let ground_to_field = ground_to_field_or_initial_pose(context);
@@ -440,7 +533,9 @@ impl RoleAssignment {
             .or(team_network_ball)
             .ok_or_eyre("we are striker without a ball, this should never happen")?;
 
+        let status = own_status(context);
         self.last_sent_state = SentState::Striker;
+        self.last_shared_state = Some((context.cycle_time.start_time, pose, status));
         context
             .last_sent_message
             .fill_if_subscribed(|| "Striker".to_string());
@@ -449,6 +544,7 @@ impl RoleAssignment {
             .write_to_network(OutgoingMessage::Spl(HulkMessage::Striker(StrikerMessage {
                 player_number: *context.player_number,
                 pose,
+                status,
                 ball_position,
                 time_to_reach_kick_position: *context.time_to_reach_kick_position.unwrap(),
             })))
@@ -459,27 +555,26 @@ impl RoleAssignment {
         &mut self,
         context: &mut CycleContext<impl NetworkInterface>,
     ) -> Result<()> {
-        if context
-            .remaining_amount_of_messages
-            .is_some_and(|remaining_amount_of_messages| {
-                *remaining_amount_of_messages
-                    < context
-                        .spl_network_parameters
-                        .remaining_amount_of_messages_to_stop_sending
-            })
-        {
+        if !context.spl_message_grants[SplMessageKind::Loser] {
             return Ok(());
         }
+        self.loser_message_pending = false;
 
         if self.last_sent_state == SentState::Loser {
             return Ok(());
         }
         self.last_sent_state = SentState::Loser;
 
-        self.last_transmitted_spl_message = Some(context.cycle_time.start_time);
+        context
+            .sent_spl_messages
+            .record(SplMessageKind::Loser, context.cycle_time.start_time);
         self.last_received_striker_message = None;
 
-        let ground_to_field = ground_to_field_or_initial_pose(context);
+        let pose = ground_to_field_or_initial_pose(context).as_pose();
+        let status = own_status(context);
+        self.last_shared_state = Some((context.cycle_time.start_time, pose, status));
+        let ball_search_hotspot = context.ball_search_hotspot.copied();
+        self.last_shared_hotspot = ball_search_hotspot;
         context
             .last_sent_message
             .fill_if_subscribed(|| "Loser".to_string());
@@ -487,10 +582,109 @@ impl RoleAssignment {
             .hardware
             .write_to_network(OutgoingMessage::Spl(HulkMessage::Loser(LoserMessage {
                 player_number: *context.player_number,
-                pose: ground_to_field.as_pose(),
+                pose,
+                status,
+                ball_search_hotspot: ball_search_hotspot.map(BallSearchHotspot::new),
             })))
             .wrap_err("failed to write LoserMessage to hardware")
     }
+
+    fn try_sending_obstacle_message(
+        &mut self,
+        context: &mut CycleContext<impl NetworkInterface>,
+    ) -> Result<()> {
+        if !context.spl_message_grants[SplMessageKind::Obstacles]
+            || context
+                .sent_spl_messages
+                .last_sent
+                .is_some_and(|last_sent| last_sent == context.cycle_time.start_time)
+        {
+            return Ok(());
+        }
+        let (Some(ground_to_field), Some(pose_covariance)) =
+            (context.ground_to_field, context.pose_covariance)
+        else {
+            return Ok(());
+        };
+
+        let merge_distance = *context.team_obstacle_merge_distance;
+        let mut robot_obstacles: Vec<_> = context
+            .obstacles
+            .iter()
+            .filter(|obstacle| matches!(obstacle.kind, ObstacleKind::Robot))
+            .map(|obstacle| obstacle.position)
+            .filter(|position| {
+                context
+                    .network_robot_obstacles
+                    .iter()
+                    .all(|teammate| distance(*teammate, *position) > merge_distance)
+            })
+            .collect();
+        let pose = ground_to_field.as_pose();
+        let status = own_status(context);
+        let fall_state_changed = self
+            .last_shared_state
+            .map_or(true, |(_, _, shared_status)| {
+                shared_status.fallen != status.fallen
+            });
+        let ball_search_hotspot = context.ball_search_hotspot.copied();
+        let hotspot_moved = self.role == Role::Searcher
+            && ball_search_hotspot.is_some_and(|hotspot| {
+                self.last_shared_hotspot
+                    .map_or(true, |last_shared_hotspot| {
+                        distance(hotspot, last_shared_hotspot) > *context.hotspot_share_distance
+                    })
+            });
+        // Only spend messages on obstacles our teammates have not reported yet, to let the team
+        // reassign roles after we fell or got up, or to coordinate the ball search
+        if !fall_state_changed
+            && !hotspot_moved
+            && robot_obstacles.iter().all(|position| {
+                context.team_obstacles.iter().any(|team_obstacle| {
+                    distance(team_obstacle.position, *position) < merge_distance
+                })
+            })
+        {
+            return Ok(());
+        }
+        robot_obstacles.sort_by(|left, right| {
+            left.coords()
+                .norm_squared()
+                .total_cmp(&right.coords().norm_squared())
+        });
+
+        let mut obstacles = [None; MAXIMUM_NUMBER_OF_SHARED_OBSTACLES];
+        for (slot, position) in obstacles.iter_mut().zip(robot_obstacles) {
+            let variance = pose_covariance[(0, 0)]
+                + pose_covariance[(1, 1)]
+                + position.coords().norm_squared() * pose_covariance[(2, 2)];
+            *slot = Some(SharedObstacle::new(
+                *ground_to_field * position,
+                variance.max(0.0).sqrt(),
+            ));
+        }
+
+        context
+            .sent_spl_messages
+            .record(SplMessageKind::Obstacles, context.cycle_time.start_time);
+        self.last_shared_state = Some((context.cycle_time.start_time, pose, status));
+        self.last_shared_hotspot = ball_search_hotspot;
+        context
+            .last_sent_message
+            .fill_if_subscribed(|| "Obstacles".to_string());
+        context
+            .hardware
+            .write_to_network(OutgoingMessage::Spl(HulkMessage::Obstacles(
+                ObstacleMessage {
+                    player_number: *context.player_number,
+                    pose,
+                    status,
+                    obstacles,
+                    ball_search_hotspot: ball_search_hotspot.map(BallSearchHotspot::new),
+                },
+            )))
+            .wrap_err("failed to write ObstacleMessage to hardware")
+    }
 }
 
 fn ground_to_field_or_initial_pose(
@@ -509,6 +703,19 @@ fn ground_to_field_or_initial_pose(
         })
 }
 
+fn own_status(context: &CycleContext<'_, impl NetworkInterface>) -> RobotStatus {
+    let maximum_localization_uncertainty = context.role_utilities.maximum_localization_uncertainty;
+    RobotStatus {
+        fallen: matches!(context.fall_state, FallState::Fallen { .. }),
+        battery_charge: context.sensor_data.battery.map(|battery| battery.charge),
+        localization_uncertainty: context
+            .pose_covariance
+            .map_or(maximum_localization_uncertainty, |covariance| {
+                (covariance[(0, 0)] + covariance[(1, 1)]).sqrt()
+            }),
+    }
+}
+
 fn is_allowed_to_send_messages(context: &CycleContext<'_, impl NetworkInterface>) -> bool {
     let is_playing = *context.primary_state == PrimaryState::Playing;
     let is_penalty_kick =
@@ -834,6 +1041,9 @@ fn pick_keeper_or_searcher(
 
 #[cfg(test)]
 mod test {
+    use std::cell::RefCell;
+
+    use linear_algebra::Vector2;
     use proptest::prelude::*;
 
     use super::*;
@@ -896,4 +1106,150 @@ mod test {
             assert_eq!(new_role, third_role);
         }
     }
+
+    #[derive(Default)]
+    struct RecordingNetwork {
+        messages: RefCell<Vec<OutgoingMessage>>,
+    }
+
+    impl NetworkInterface for RecordingNetwork {
+        fn read_from_network(&self) -> Result<IncomingMessage> {
+            unimplemented!()
+        }
+
+        fn write_to_network(&self, message: OutgoingMessage) -> Result<()> {
+            self.messages.borrow_mut().push(message);
+            Ok(())
+        }
+    }
+
+    fn cycle(
+        role_assignment: &mut RoleAssignment,
+        hardware: &RecordingNetwork,
+        cycle_start_time: SystemTime,
+        sees_ball: bool,
+        spl_message_grants: SplMessageKinds<bool>,
+    ) -> Role {
+        let ball_position = BallPosition {
+            position: Point2::origin(),
+            velocity: Vector2::zeros(),
+            last_seen: cycle_start_time,
+        };
+        let cycle_time = CycleTime {
+            start_time: cycle_start_time,
+            last_cycle_duration: Duration::from_millis(12),
+        };
+        let optional_roles = vec![Role::DefenderLeft];
+        let mut sent_spl_messages = SentSplMessages::default();
+        let mut last_time_player_was_penalized = None;
+        let mut last_sent_state = None;
+        let mut last_sent_message = None;
+        let mut field_roles = None;
+
+        role_assignment
+            .cycle(CycleContext::new(
+                sees_ball.then_some(&ball_position),
+                &FallState::default(),
+                None,
+                &PrimaryState::Playing,
+                None,
+                &cycle_time,
+                PerceptionInput {
+                    persistent: Default::default(),
+                    temporary: Default::default(),
+                },
+                None,
+                Some(&Duration::from_secs(1)),
+                None,
+                &Vec::new(),
+                &Vec::new(),
+                &Vec::new(),
+                None,
+                &SensorData::default(),
+                &Vec::new(),
+                None,
+                &spl_message_grants,
+                &mut sent_spl_messages,
+                &FieldDimensions::default(),
+                None,
+                &Duration::from_secs(12),
+                &Duration::from_secs(5),
+                &Duration::from_secs(10),
+                &Players::<InitialPose>::default(),
+                &optional_roles,
+                &PlayerNumber::Seven,
+                &RolePositionsParameters::default(),
+                &RoleUtilityParameters::default(),
+                &SplNetworkParameters::default(),
+                &0.5,
+                &Duration::from_secs(5),
+                &1.0,
+                hardware,
+                AdditionalOutput::new(false, &mut last_time_player_was_penalized),
+                AdditionalOutput::new(false, &mut last_sent_state),
+                AdditionalOutput::new(false, &mut last_sent_message),
+                AdditionalOutput::new(false, &mut field_roles),
+            ))
+            .unwrap()
+            .role
+            .value
+    }
+
+    fn sent_loser_messages(hardware: &RecordingNetwork) -> usize {
+        hardware
+            .messages
+            .borrow()
+            .iter()
+            .filter(|message| matches!(message, OutgoingMessage::Spl(HulkMessage::Loser(..))))
+            .count()
+    }
+
+    #[test]
+    fn loser_message_denied_by_the_budget_is_retried() {
+        let optional_roles = vec![Role::DefenderLeft];
+        let mut role_assignment =
+            RoleAssignment::new(CreationContext::new(&optional_roles, &PlayerNumber::Seven))
+                .unwrap();
+        let hardware = RecordingNetwork::default();
+        let all_granted = SplMessageKinds {
+            striker: true,
+            loser: true,
+            obstacles: false,
+        };
+        let loser_denied = SplMessageKinds {
+            loser: false,
+            ..all_granted
+        };
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+
+        assert_eq!(
+            cycle(&mut role_assignment, &hardware, start, true, all_granted),
+            Role::Striker
+        );
+        assert_eq!(
+            cycle(
+                &mut role_assignment,
+                &hardware,
+                start + Duration::from_secs(1),
+                false,
+                loser_denied
+            ),
+            Role::Loser
+        );
+        assert_eq!(sent_loser_messages(&hardware), 0);
+
+        for seconds in 2..5 {
+            assert_eq!(
+                cycle(
+                    &mut role_assignment,
+                    &hardware,
+                    start + Duration::from_secs(seconds),
+                    false,
+                    all_granted
+                ),
+                Role::Loser
+            );
+        }
+        assert_eq!(sent_loser_messages(&hardware), 1);
+    }
 }
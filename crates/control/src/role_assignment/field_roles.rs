@@ -0,0 +1,303 @@
+use std::f32::consts::FRAC_PI_4;
+
+use coordinate_systems::Field;
+use hungarian_algorithm::AssignmentProblem;
+use linear_algebra::{distance, point, Point2, Pose2, Rotation2, Vector2};
+use ndarray::Array2;
+use ordered_float::NotNan;
+use spl_network_messages::{PlayerNumber, RobotStatus};
+use types::{
+    field_dimensions::FieldDimensions,
+    parameters::{RolePositionsParameters, RoleUtilityParameters},
+    players::Players,
+    roles::Role,
+};
+
+/// Player taking part in the assignment of field roles
+#[derive(Clone, Copy, Debug)]
+pub struct FieldPlayer {
+    pub player_number: PlayerNumber,
+    /// Last pose and status the player shared with the team
+    pub shared_state: Option<(Pose2<Field>, RobotStatus)>,
+}
+
+pub fn is_field_role(role: Role) -> bool {
+    matches!(
+        role,
+        Role::DefenderLeft
+            | Role::DefenderRight
+            | Role::MidfielderLeft
+            | Role::MidfielderRight
+            | Role::StrikerSupporter
+    )
+}
+
+/// Added to the time to reach per squared difference of player and role index, which breaks ties
+/// by giving the more important roles to the lower player numbers
+const TIE_BREAK_TIME: f32 = 1e-3;
+
+/// Assigns the most important `roles` to `players` such that the weighted time to reach the role
+/// positions is minimal. `players` are sorted by player number and the assignment only depends on
+/// the arguments, so robots agree on the assignment as long as they received the same messages.
+/// Keeping the `previous_assignment` is rewarded to not swap roles over small changes in the time
+/// to reach.
+pub fn assign_field_roles(
+    players: &[FieldPlayer],
+    roles: &[Role],
+    ball: Point2<Field>,
+    previous_assignment: &Players<Option<Role>>,
+    field_dimensions: &FieldDimensions,
+    role_positions: &RolePositionsParameters,
+    parameters: &RoleUtilityParameters,
+) -> Players<Option<Role>> {
+    let mut assignment = Players::new(None);
+    let roles = &roles[..roles.len().min(players.len())];
+    if roles.is_empty() {
+        return assignment;
+    }
+
+    let utilities = Array2::from_shape_fn(
+        (players.len(), roles.len()),
+        |(player_index, role_index)| {
+            let player = &players[player_index];
+            let role = roles[role_index];
+            let position = role_position(role, ball, field_dimensions, role_positions);
+            let time_to_reach = time_to_reach(player, position, parameters);
+            let weight = parameters.role_priority_decay.powi(role_index as i32);
+            let tie_break = TIE_BREAK_TIME * (player_index as f32 - role_index as f32).powi(2);
+            let keeping_bonus = if previous_assignment[player.player_number] == Some(role) {
+                parameters.role_keeping_bonus.as_secs_f32()
+            } else {
+                0.0
+            };
+            NotNan::new(-weight * time_to_reach - tie_break + keeping_bonus)
+                .expect("utility should not be NaN")
+        },
+    );
+
+    for (player, solution) in players
+        .iter()
+        .zip(AssignmentProblem::from_costs(utilities).solve())
+    {
+        assignment[player.player_number] = solution.map(|solution| roles[solution.to]);
+    }
+    assignment
+}
+
+fn time_to_reach(
+    player: &FieldPlayer,
+    position: Point2<Field>,
+    parameters: &RoleUtilityParameters,
+) -> f32 {
+    let Some((pose, status)) = player.shared_state else {
+        return parameters.unknown_pose_time.as_secs_f32();
+    };
+    let walking_time = distance(pose.position(), position) / parameters.walking_speed;
+    let stand_up_time = if status.fallen {
+        parameters.stand_up_time.as_secs_f32()
+    } else {
+        0.0
+    };
+    let localization_uncertainty = status
+        .localization_uncertainty
+        .clamp(0.0, parameters.maximum_localization_uncertainty);
+    let battery_discharge = 1.0 - status.battery_charge.unwrap_or(1.0).clamp(0.0, 1.0);
+    (walking_time + stand_up_time)
+        * (1.0
+            + parameters.localization_uncertainty_factor * localization_uncertainty
+            + parameters.empty_battery_factor * battery_discharge)
+}
+
+/// Approximation of the positions the role behaviors walk to
+fn role_position(
+    role: Role,
+    ball: Point2<Field>,
+    field_dimensions: &FieldDimensions,
+    role_positions: &RolePositionsParameters,
+) -> Point2<Field> {
+    let supporting_position = |angle: f32, distance_to_ball: f32, minimum_x: f32| {
+        let position =
+            ball + Rotation2::new(angle) * (Vector2::<Field>::x_axis() * distance_to_ball);
+        let maximum_x = field_dimensions.length / 2.0 - field_dimensions.penalty_area_length;
+        point![position.x().clamp(minimum_x, maximum_x), position.y()]
+    };
+    match role {
+        Role::DefenderLeft | Role::DefenderRight => {
+            let y_offset = if role == Role::DefenderLeft {
+                role_positions.defender_y_offset
+            } else {
+                -role_positions.defender_y_offset
+            };
+            let position_to_defend = point![-field_dimensions.length / 2.0, y_offset];
+            let direction_to_ball = (ball - position_to_defend)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector2::x_axis);
+            position_to_defend + direction_to_ball * role_positions.defender_aggressive_ring_radius
+        }
+        Role::MidfielderLeft => supporting_position(
+            FRAC_PI_4,
+            role_positions.left_midfielder_distance_to_ball,
+            role_positions.left_midfielder_minimum_x,
+        ),
+        Role::MidfielderRight => supporting_position(
+            -FRAC_PI_4,
+            role_positions.right_midfielder_distance_to_ball,
+            role_positions.right_midfielder_minimum_x,
+        ),
+        Role::StrikerSupporter => supporting_position(
+            if ball.y() < 0.0 {
+                FRAC_PI_4
+            } else {
+                -FRAC_PI_4
+            },
+            role_positions.striker_supporter_distance_to_ball,
+            role_positions.striker_supporter_minimum_x,
+        ),
+        _ => ball,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn parameters() -> RoleUtilityParameters {
+        RoleUtilityParameters {
+            walking_speed: 0.25,
+            stand_up_time: Duration::from_secs(5),
+            unknown_pose_time: Duration::from_secs(30),
+            localization_uncertainty_factor: 2.0,
+            maximum_localization_uncertainty: 1.0,
+            empty_battery_factor: 0.5,
+            role_priority_decay: 0.8,
+            role_keeping_bonus: Duration::from_secs(1),
+        }
+    }
+
+    fn player(player_number: PlayerNumber, x: f32, y: f32, fallen: bool) -> FieldPlayer {
+        FieldPlayer {
+            player_number,
+            shared_state: Some((
+                Pose2::new(point![x, y], 0.0),
+                RobotStatus {
+                    fallen,
+                    battery_charge: Some(1.0),
+                    localization_uncertainty: 0.1,
+                },
+            )),
+        }
+    }
+
+    fn assign(players: &[FieldPlayer]) -> Players<Option<Role>> {
+        assign_with_previous(players, &Players::new(None))
+    }
+
+    fn assign_with_previous(
+        players: &[FieldPlayer],
+        previous_assignment: &Players<Option<Role>>,
+    ) -> Players<Option<Role>> {
+        let field_dimensions = FieldDimensions {
+            length: 9.0,
+            width: 6.0,
+            penalty_area_length: 1.65,
+            ..Default::default()
+        };
+        let role_positions = RolePositionsParameters {
+            defender_aggressive_ring_radius: 1.5,
+            defender_y_offset: 0.7,
+            left_midfielder_distance_to_ball: 1.5,
+            left_midfielder_minimum_x: -1.0,
+            right_midfielder_distance_to_ball: 1.5,
+            right_midfielder_minimum_x: -1.0,
+            ..Default::default()
+        };
+        assign_field_roles(
+            players,
+            &[
+                Role::DefenderLeft,
+                Role::DefenderRight,
+                Role::MidfielderLeft,
+            ],
+            Point2::origin(),
+            previous_assignment,
+            &field_dimensions,
+            &role_positions,
+            &parameters(),
+        )
+    }
+
+    #[test]
+    fn players_take_the_closest_roles() {
+        let assignment = assign(&[
+            player(PlayerNumber::Two, 1.0, 1.0, false),
+            player(PlayerNumber::Three, -3.0, -1.0, false),
+            player(PlayerNumber::Four, -3.0, 1.0, false),
+        ]);
+
+        assert_eq!(assignment.two, Some(Role::MidfielderLeft));
+        assert_eq!(assignment.three, Some(Role::DefenderRight));
+        assert_eq!(assignment.four, Some(Role::DefenderLeft));
+    }
+
+    #[test]
+    fn fallen_player_takes_least_important_role() {
+        let players = [
+            player(PlayerNumber::Two, -1.0, 0.5, true),
+            player(PlayerNumber::Three, -1.0, -0.5, false),
+            player(PlayerNumber::Four, -1.0, 0.0, false),
+        ];
+
+        let assignment = assign(&players);
+
+        assert_eq!(assignment.two, Some(Role::MidfielderLeft));
+        assert_eq!(assignment.four, Some(Role::DefenderLeft));
+    }
+
+    #[test]
+    fn ties_are_broken_by_player_number() {
+        let assignment = assign(&[
+            player(PlayerNumber::Two, -1.0, 0.0, false),
+            player(PlayerNumber::Three, -1.0, 0.0, false),
+            player(PlayerNumber::Four, -1.0, 0.0, false),
+        ]);
+
+        assert_eq!(assignment.two, Some(Role::DefenderLeft));
+        assert_eq!(assignment.three, Some(Role::DefenderRight));
+        assert_eq!(assignment.four, Some(Role::MidfielderLeft));
+    }
+
+    #[test]
+    fn small_changes_in_time_to_reach_keep_previous_roles() {
+        let players = [
+            player(PlayerNumber::Two, -1.0, -0.1, false),
+            player(PlayerNumber::Three, -1.0, 0.1, false),
+            player(PlayerNumber::Four, -1.0, 0.0, false),
+        ];
+        let previous_assignment = Players {
+            two: Some(Role::DefenderLeft),
+            three: Some(Role::DefenderRight),
+            four: Some(Role::MidfielderLeft),
+            ..Players::new(None)
+        };
+
+        let without_previous = assign(&players);
+        let with_previous = assign_with_previous(&players, &previous_assignment);
+
+        assert_ne!(without_previous, previous_assignment);
+        assert_eq!(with_previous, previous_assignment);
+    }
+
+    #[test]
+    fn most_important_roles_are_filled_when_players_are_missing() {
+        let assignment = assign(&[
+            player(PlayerNumber::Two, 1.0, 1.0, false),
+            player(PlayerNumber::Five, 1.0, -1.0, false),
+        ]);
+
+        assert_eq!(assignment.two, Some(Role::DefenderLeft));
+        assert_eq!(assignment.five, Some(Role::DefenderRight));
+        assert_eq!(assignment.three, None);
+    }
+}
@@ -163,7 +163,7 @@ fn unpack_game_controller_messages<'a>(
         .flatten()
         .filter_map(|message| match message {
             Some(IncomingMessage::GameController(_, message)) => Some(message),
-            Some(IncomingMessage::Spl(..)) | None => None,
+            Some(IncomingMessage::Spl(..) | IncomingMessage::RejectedSpl(..)) | None => None,
         })
         .collect()
 }
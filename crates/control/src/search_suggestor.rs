@@ -1,5 +1,5 @@
 use std::{
-    ops::{Index, IndexMut},
+    ops::{Index, IndexMut, Range},
     time::SystemTime,
 };
 
@@ -7,19 +7,19 @@ use color_eyre::{eyre::Context, Result};
 use context_attribute::context;
 use coordinate_systems::{Field, Ground};
 use framework::{AdditionalOutput, MainOutput, PerceptionInput};
-use itertools::Itertools;
 use linear_algebra::{point, Isometry2, Point2, Vector2};
 use nalgebra::clamp;
 use ndarray::{array, Array2};
 use ndarray_conv::{ConvExt, ConvMode, PaddingMode};
 use serde::{Deserialize, Serialize};
-use spl_network_messages::{HulkMessage, SubState, Team};
+use spl_network_messages::{HulkMessage, Penalty, PlayerNumber, SubState, Team};
 use types::{
     ball_position::{BallPosition, HypotheticalBallPosition},
     field_dimensions::{FieldDimensions, Half, Side},
     filtered_game_controller_state::FilteredGameControllerState,
     messages::IncomingMessage,
     parameters::SearchSuggestorParameters,
+    players::Players,
     primary_state::PrimaryState,
 };
 
@@ -40,6 +40,7 @@ pub struct CreationContext {
 pub struct CycleContext {
     search_suggestor_configuration: Parameter<SearchSuggestorParameters, "search_suggestor">,
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
+    player_number: Parameter<PlayerNumber, "player_number">,
 
     ball_position: Input<Option<BallPosition<Ground>>, "ball_position?">,
     hypothetical_ball_positions:
@@ -49,14 +50,18 @@ pub struct CycleContext {
     filtered_game_controller_state:
         Input<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
     network_message: PerceptionInput<Option<IncomingMessage>, "SplNetwork", "filtered_message?">,
+    teammate_ball_search_hotspots:
+        PerceptionInput<Option<Point2<Field>>, "SplNetwork", "teammate_ball_search_hotspot?">,
 
     heatmap: AdditionalOutput<Array2<f32>, "ball_search_heatmap">,
+    search_region: AdditionalOutput<Option<Range<f32>>, "ball_search_region">,
 }
 
 #[context]
 #[derive(Default)]
 pub struct MainOutputs {
     pub suggested_search_position: MainOutput<Option<Point2<Field>>>,
+    pub ball_search_hotspot: MainOutput<Option<Point2<Field>>>,
 }
 
 impl SearchSuggestor {
@@ -80,16 +85,39 @@ impl SearchSuggestor {
 
     pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
         self.update_heatmap(&context)?;
+        let minimum_validity = context.search_suggestor_configuration.minimum_validity;
+        let ball_search_hotspot = self.heatmap.get_maximum_position(minimum_validity, None);
+        let search_region =
+            context
+                .filtered_game_controller_state
+                .and_then(|filtered_game_controller_state| {
+                    search_region(
+                        *context.player_number,
+                        &filtered_game_controller_state.penalties,
+                        context.field_dimensions,
+                    )
+                });
+        // Without any clue within the own region, its center is searched since the region spans
+        // the whole field width
         let suggested_search_position = self
             .heatmap
-            .get_maximum_position(context.search_suggestor_configuration.minimum_validity);
+            .get_maximum_position(minimum_validity, search_region.as_ref())
+            .or_else(|| {
+                search_region
+                    .as_ref()
+                    .map(|region| point![(region.start + region.end) / 2.0, 0.0])
+            });
 
         context
             .heatmap
             .fill_if_subscribed(|| self.heatmap.map.clone());
+        context
+            .search_region
+            .fill_if_subscribed(|| search_region.clone());
 
         Ok(MainOutputs {
             suggested_search_position: suggested_search_position.into(),
+            ball_search_hotspot: ball_search_hotspot.into(),
         })
     }
 
@@ -127,6 +155,18 @@ impl SearchSuggestor {
                 context.search_suggestor_configuration.team_ball_weight,
             );
         }
+        for hotspot in context
+            .teammate_ball_search_hotspots
+            .persistent
+            .values()
+            .flatten()
+            .flatten()
+        {
+            self.heatmap.add_team_hotspot(
+                **hotspot,
+                context.search_suggestor_configuration.team_hotspot_weight,
+            );
+        }
 
         let kernel = create_kernel(
             context
@@ -171,21 +211,33 @@ impl Heatmap {
         )
     }
 
-    fn get_maximum_position(&self, minimum_validity: f32) -> Option<Point2<Field>> {
-        let linear_maximum_heat_heatmap_position =
-            self.map.iter().position_max_by(|a, b| a.total_cmp(b))?;
-        let maximum_heat_heatmap_position = (
-            linear_maximum_heat_heatmap_position / self.map.dim().1,
-            linear_maximum_heat_heatmap_position % self.map.dim().1,
-        );
-        if self.map[maximum_heat_heatmap_position] > minimum_validity {
-            let search_suggestion = point![
-                ((maximum_heat_heatmap_position.0 as f32 + 1.0 / 2.0) / self.cells_per_meter
-                    - self.field_dimensions.length / 2.0),
-                ((maximum_heat_heatmap_position.1 as f32 + 1.0 / 2.0) / self.cells_per_meter
-                    - self.field_dimensions.width / 2.0)
-            ];
-            return Some(search_suggestion);
+    fn heatmap_to_field(&self, heatmap_point: (usize, usize)) -> Point2<Field> {
+        point![
+            ((heatmap_point.0 as f32 + 1.0 / 2.0) / self.cells_per_meter
+                - self.field_dimensions.length / 2.0),
+            ((heatmap_point.1 as f32 + 1.0 / 2.0) / self.cells_per_meter
+                - self.field_dimensions.width / 2.0)
+        ]
+    }
+
+    /// Center of the hottest cell, only considering cells whose center lies within the x range of
+    /// `region` if given
+    fn get_maximum_position(
+        &self,
+        minimum_validity: f32,
+        region: Option<&Range<f32>>,
+    ) -> Option<Point2<Field>> {
+        let (maximum_heat_heatmap_position, maximum_heat) = self
+            .map
+            .indexed_iter()
+            .filter(|(heatmap_point, _)| {
+                region.map_or(true, |region| {
+                    region.contains(&self.heatmap_to_field(*heatmap_point).x())
+                })
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        if *maximum_heat > minimum_validity {
+            return Some(self.heatmap_to_field(maximum_heat_heatmap_position));
         }
         None
     }
@@ -200,12 +252,19 @@ impl Heatmap {
                     last_seen: time - striker_message.ball_position.age,
                 }),
             ),
-            HulkMessage::Loser(_) | HulkMessage::VisualReferee(_) => return,
+            HulkMessage::Loser(_) | HulkMessage::VisualReferee(_) | HulkMessage::Obstacles(_) => {
+                return
+            }
         };
         if let Some(ball_position) = ball {
             self[ball_position.position] = team_ball_weight;
         }
     }
+
+    fn add_team_hotspot(&mut self, hotspot: Point2<Field>, team_hotspot_weight: f32) {
+        let heat = &mut self[hotspot];
+        *heat = heat.max(team_hotspot_weight);
+    }
 }
 
 impl Index<Point2<Field>> for Heatmap {
@@ -223,6 +282,28 @@ impl IndexMut<Point2<Field>> for Heatmap {
     }
 }
 
+/// Splits the field along its length into one strip per searching player, ordered by player
+/// number from the own goal. Searchers are all unpenalized players except the lowest one, who
+/// searches at the own goal. All robots know the penalties from the GameController and thus
+/// compute the same disjoint regions.
+fn search_region(
+    player_number: PlayerNumber,
+    penalties: &Players<Option<Penalty>>,
+    field_dimensions: &FieldDimensions,
+) -> Option<Range<f32>> {
+    let searchers: Vec<_> = penalties
+        .iter()
+        .filter_map(|(number, penalty)| penalty.is_none().then_some(number))
+        .skip(1)
+        .collect();
+    let index = searchers
+        .iter()
+        .position(|number| *number == player_number)?;
+    let region_length = field_dimensions.length / searchers.len() as f32;
+    let start = -field_dimensions.length / 2.0 + index as f32 * region_length;
+    Some(start..start + region_length)
+}
+
 fn get_rule_hypotheses(
     primary_state: PrimaryState,
     filtered_game_controller_state: &FilteredGameControllerState,
@@ -279,3 +360,111 @@ fn kicking_team_half(kicking_team: Option<Team>) -> Option<Half> {
         None => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn field_dimensions() -> FieldDimensions {
+        FieldDimensions {
+            length: 9.0,
+            width: 6.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn searchers_get_disjoint_regions_along_the_field() {
+        let mut penalties = Players::new(None);
+        penalties.three = Some(Penalty::PlayerPushing {
+            remaining: Duration::from_secs(30),
+        });
+        penalties.six = Some(Penalty::PlayerPushing {
+            remaining: Duration::from_secs(30),
+        });
+
+        let region = |player_number| search_region(player_number, &penalties, &field_dimensions());
+
+        assert_eq!(region(PlayerNumber::One), None);
+        assert_eq!(region(PlayerNumber::Three), None);
+        assert_eq!(region(PlayerNumber::Two), Some(-4.5..-2.25));
+        assert_eq!(region(PlayerNumber::Four), Some(-2.25..0.0));
+        assert_eq!(region(PlayerNumber::Five), Some(0.0..2.25));
+        assert_eq!(region(PlayerNumber::Seven), Some(2.25..4.5));
+    }
+
+    #[test]
+    fn hotspot_of_teammate_becomes_own_hotspot() {
+        let field_dimensions = field_dimensions();
+        let configuration = SearchSuggestorParameters {
+            cells_per_meter: 2.0,
+            heatmap_convolution_kernel_weight: 0.001,
+            minimum_validity: 0.01,
+            own_ball_weight: 1.0,
+            team_ball_weight: 1.0,
+            rule_ball_weight: 1.0,
+            team_hotspot_weight: 0.1,
+            hotspot_share_distance: 1.0,
+        };
+        let mut search_suggestor =
+            SearchSuggestor::new(CreationContext::new(&field_dimensions, &configuration)).unwrap();
+        let teammate_hotspot = point![3.0, 1.0];
+        let mut cycle = |hotspots: Vec<Option<&Point2<Field>>>| {
+            let mut heatmap = None;
+            let mut search_region = None;
+            search_suggestor
+                .cycle(CycleContext::new(
+                    &configuration,
+                    &field_dimensions,
+                    &PlayerNumber::Five,
+                    None,
+                    &Vec::new(),
+                    None,
+                    &PrimaryState::Playing,
+                    None,
+                    PerceptionInput {
+                        persistent: Default::default(),
+                        temporary: Default::default(),
+                    },
+                    PerceptionInput {
+                        persistent: [(SystemTime::UNIX_EPOCH, hotspots)].into(),
+                        temporary: Default::default(),
+                    },
+                    AdditionalOutput::new(false, &mut heatmap),
+                    AdditionalOutput::new(false, &mut search_region),
+                ))
+                .unwrap()
+                .ball_search_hotspot
+                .value
+        };
+
+        assert_eq!(cycle(Vec::new()), None);
+        assert_eq!(
+            cycle(vec![Some(&teammate_hotspot)]),
+            Some(point![3.25, 1.25])
+        );
+    }
+
+    #[test]
+    fn maximum_is_searched_within_region() {
+        let mut heatmap = Heatmap {
+            map: Array2::zeros((18, 12)),
+            field_dimensions: field_dimensions(),
+            cells_per_meter: 2.0,
+        };
+        heatmap[point![3.0, 1.0]] = 0.5;
+        heatmap[point![-3.0, -1.0]] = 0.2;
+
+        assert_eq!(
+            heatmap.get_maximum_position(0.1, None),
+            Some(point![3.25, 1.25])
+        );
+        assert_eq!(
+            heatmap.get_maximum_position(0.1, Some(&(-4.5..0.0))),
+            Some(point![-2.75, -0.75])
+        );
+        assert_eq!(heatmap.get_maximum_position(0.1, Some(&(-1.0..1.0))), None);
+    }
+}
@@ -0,0 +1,232 @@
+use std::time::{Duration, SystemTime};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use coordinate_systems::{Field, Ground};
+use framework::{AdditionalOutput, MainOutput, PerceptionInput};
+use linear_algebra::{distance, Isometry2, Point2};
+use spl_network_messages::{GamePhase, HulkMessage, ObstacleMessage, SubState};
+use types::{
+    cycle_time::CycleTime,
+    filtered_game_controller_state::FilteredGameControllerState,
+    messages::IncomingMessage,
+    obstacles::{Obstacle, ObstacleKind},
+    players::Players,
+};
+
+use crate::team_ball_receiver::get_spl_messages;
+
+#[derive(Deserialize, Serialize)]
+pub struct TeamObstacleReceiver {
+    received_obstacles: Players<Option<(SystemTime, ObstacleMessage)>>,
+}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    cycle_time: Input<CycleTime, "cycle_time">,
+    filtered_game_controller_state:
+        Input<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
+    ground_to_field: Input<Option<Isometry2<Ground, Field>>, "ground_to_field?">,
+    obstacles: Input<Vec<Obstacle>, "obstacles">,
+    network_message: PerceptionInput<Option<IncomingMessage>, "SplNetwork", "filtered_message?">,
+
+    maximum_age: Parameter<Duration, "team_obstacles.maximum_age">,
+    merge_distance: Parameter<f32, "team_obstacles.merge_distance">,
+    measurement_noise: Parameter<f32, "team_obstacles.measurement_noise">,
+    robot_obstacle_radius_at_foot_height:
+        Parameter<f32, "obstacle_filter.robot_obstacle_radius_at_foot_height">,
+    robot_obstacle_radius_at_hip_height:
+        Parameter<f32, "obstacle_filter.robot_obstacle_radius_at_hip_height">,
+
+    team_obstacles_in_field: AdditionalOutput<Vec<Point2<Field>>, "team_obstacles_in_field">,
+}
+
+#[context]
+pub struct MainOutputs {
+    pub team_obstacles: MainOutput<Vec<Obstacle>>,
+    pub team_obstacles_unseen_by_us: MainOutput<Vec<Obstacle>>,
+}
+
+struct WeightedObstacle {
+    position: Point2<Field>,
+    weight: f32,
+}
+
+impl TeamObstacleReceiver {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {
+            received_obstacles: Players::default(),
+        })
+    }
+
+    pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        for (time, message) in get_spl_messages(&context.network_message.persistent) {
+            if let HulkMessage::Obstacles(message) = message {
+                self.received_obstacles[message.player_number] = Some((time, message));
+            }
+        }
+
+        // Ignore everything during penalty_*
+        if let Some(game_controller_state) = context.filtered_game_controller_state {
+            let in_penalty_shootout = matches!(
+                game_controller_state.game_phase,
+                GamePhase::PenaltyShootout { .. }
+            );
+            let in_penalty_kick = game_controller_state.sub_state == Some(SubState::PenaltyKick);
+
+            if in_penalty_shootout || in_penalty_kick {
+                return Ok(MainOutputs {
+                    team_obstacles: Vec::new().into(),
+                    team_obstacles_unseen_by_us: Vec::new().into(),
+                });
+            }
+        }
+
+        let Some(ground_to_field) = context.ground_to_field else {
+            return Ok(MainOutputs {
+                team_obstacles: Vec::new().into(),
+                team_obstacles_unseen_by_us: Vec::new().into(),
+            });
+        };
+
+        let now = context.cycle_time.start_time;
+        let fresh_messages = self
+            .received_obstacles
+            .iter()
+            .filter_map(|(_player_number, received)| *received)
+            .filter(|(time, _message)| {
+                now.duration_since(*time).expect("time ran backwards") < *context.maximum_age
+            })
+            .map(|(_time, message)| message);
+        let fused_obstacles = fuse_obstacles(
+            fresh_messages,
+            *context.merge_distance,
+            *context.measurement_noise,
+        );
+
+        context.team_obstacles_in_field.fill_if_subscribed(|| {
+            fused_obstacles
+                .iter()
+                .map(|obstacle| obstacle.position)
+                .collect()
+        });
+
+        let field_to_ground = ground_to_field.inverse();
+        let team_obstacles: Vec<_> = fused_obstacles
+            .into_iter()
+            .map(|obstacle| field_to_ground * obstacle.position)
+            .filter(|position| position.coords().norm() > *context.merge_distance)
+            .map(|position| {
+                Obstacle::robot(
+                    position,
+                    *context.robot_obstacle_radius_at_foot_height,
+                    *context.robot_obstacle_radius_at_hip_height,
+                )
+            })
+            .collect();
+        let team_obstacles_unseen_by_us: Vec<_> = team_obstacles
+            .iter()
+            .filter(|team_obstacle| {
+                context
+                    .obstacles
+                    .iter()
+                    .filter(|obstacle| matches!(obstacle.kind, ObstacleKind::Robot))
+                    .all(|obstacle| {
+                        distance(obstacle.position, team_obstacle.position)
+                            > *context.merge_distance
+                    })
+            })
+            .copied()
+            .collect();
+
+        Ok(MainOutputs {
+            team_obstacles: team_obstacles.into(),
+            team_obstacles_unseen_by_us: team_obstacles_unseen_by_us.into(),
+        })
+    }
+}
+
+fn fuse_obstacles(
+    messages: impl Iterator<Item = ObstacleMessage>,
+    merge_distance: f32,
+    measurement_noise: f32,
+) -> Vec<WeightedObstacle> {
+    let mut fused_obstacles: Vec<WeightedObstacle> = Vec::new();
+    for message in messages {
+        for obstacle in message.obstacles.into_iter().flatten() {
+            let position = obstacle.position;
+            let variance = obstacle.deviation().powi(2) + measurement_noise;
+            let weight = 1.0 / variance.max(f32::EPSILON);
+
+            match fused_obstacles
+                .iter_mut()
+                .find(|obstacle| distance(obstacle.position, position) < merge_distance)
+            {
+                Some(obstacle) => {
+                    let total_weight = obstacle.weight + weight;
+                    obstacle.position = ((obstacle.position.coords() * obstacle.weight
+                        + position.coords() * weight)
+                        / total_weight)
+                        .as_point();
+                    obstacle.weight = total_weight;
+                }
+                None => fused_obstacles.push(WeightedObstacle { position, weight }),
+            }
+        }
+    }
+    fused_obstacles
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use linear_algebra::{point, Pose2};
+    use spl_network_messages::{
+        PlayerNumber, RobotStatus, SharedObstacle, MAXIMUM_NUMBER_OF_SHARED_OBSTACLES,
+    };
+
+    use super::*;
+
+    fn message(
+        player_number: PlayerNumber,
+        deviation: f32,
+        obstacle: Point2<Field>,
+    ) -> ObstacleMessage {
+        let mut obstacles = [None; MAXIMUM_NUMBER_OF_SHARED_OBSTACLES];
+        obstacles[0] = Some(SharedObstacle::new(obstacle, deviation));
+        ObstacleMessage {
+            player_number,
+            pose: Pose2::default(),
+            status: RobotStatus::default(),
+            obstacles,
+            ball_search_hotspot: None,
+        }
+    }
+
+    #[test]
+    fn close_observations_are_fused_towards_more_certain_sender() {
+        let certain = message(PlayerNumber::Two, 0.1, point![1.0, 0.0]);
+        let uncertain = message(PlayerNumber::Three, 1.0, point![1.2, 0.0]);
+
+        let fused = fuse_obstacles([certain, uncertain].into_iter(), 0.5, 0.0);
+
+        assert_eq!(fused.len(), 1);
+        assert!(fused[0].position.x() < 1.1);
+        assert_relative_eq!(fused[0].position.y(), 0.0);
+    }
+
+    #[test]
+    fn distant_observations_are_kept_separate() {
+        let first = message(PlayerNumber::Two, 1.0, point![1.0, 0.0]);
+        let second = message(PlayerNumber::Three, 1.0, point![-1.0, 0.0]);
+
+        let fused = fuse_obstacles([first, second].into_iter(), 0.5, 0.0);
+
+        assert_eq!(fused.len(), 2);
+    }
+}
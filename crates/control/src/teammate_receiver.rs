@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use framework::{MainOutput, PerceptionInput};
+use spl_network_messages::{HulkMessage, PlayerNumber};
+use types::{
+    cycle_time::CycleTime, messages::IncomingMessage, players::Players, teammate::Teammate,
+};
+
+use crate::team_ball_receiver::get_spl_messages;
+
+#[derive(Deserialize, Serialize)]
+pub struct TeammateReceiver {
+    teammates: Players<Option<Teammate>>,
+}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    cycle_time: Input<CycleTime, "cycle_time">,
+    network_message: PerceptionInput<Option<IncomingMessage>, "SplNetwork", "filtered_message?">,
+
+    maximum_age: Parameter<Duration, "teammate_receiver.maximum_age">,
+    player_number: Parameter<PlayerNumber, "player_number">,
+}
+
+#[context]
+pub struct MainOutputs {
+    pub teammates: MainOutput<Vec<Teammate>>,
+}
+
+impl TeammateReceiver {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {
+            teammates: Players::default(),
+        })
+    }
+
+    pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
+        for (time, message) in get_spl_messages(&context.network_message.persistent) {
+            let (player_number, pose, status) = match message {
+                HulkMessage::Striker(message) => {
+                    (message.player_number, message.pose, message.status)
+                }
+                HulkMessage::Loser(message) => {
+                    (message.player_number, message.pose, message.status)
+                }
+                HulkMessage::Obstacles(message) => {
+                    (message.player_number, message.pose, message.status)
+                }
+                HulkMessage::VisualReferee(_) => continue,
+            };
+            self.teammates[player_number] = Some(Teammate {
+                player_number,
+                pose,
+                status,
+                last_received: time,
+            });
+        }
+
+        let now = context.cycle_time.start_time;
+        let teammates: Vec<_> = self
+            .teammates
+            .iter()
+            .filter_map(|(_player_number, teammate)| *teammate)
+            .filter(|teammate| teammate.player_number != *context.player_number)
+            .filter(|teammate| {
+                now.duration_since(teammate.last_received)
+                    .is_ok_and(|age| age < *context.maximum_age)
+            })
+            .collect();
+
+        Ok(MainOutputs {
+            teammates: teammates.into(),
+        })
+    }
+}
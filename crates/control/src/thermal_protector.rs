@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use framework::MainOutput;
+use types::{
+    joints::{Joints, JointsName},
+    parameters::ThermalProtectionParameters,
+    sensor_data::SensorData,
+    thermal_protection::{ThermalLevel, ThermalProtection},
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct ThermalProtector {
+    filtered_currents: Joints<f32>,
+}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    sensor_data: Input<SensorData, "sensor_data">,
+
+    parameters: Parameter<ThermalProtectionParameters, "thermal_protection">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub thermal_protection: MainOutput<ThermalProtection>,
+}
+
+impl ThermalProtector {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {
+            filtered_currents: Joints::default(),
+        })
+    }
+
+    pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
+        let parameters = context.parameters;
+        let mut thermal_protection = ThermalProtection::default();
+
+        for (joint, current) in context.sensor_data.currents.enumerate() {
+            let filtered_current = &mut self.filtered_currents[joint];
+            *filtered_current += parameters.current_low_pass_factor * (current - *filtered_current);
+
+            let time_to_limit = predict_time_to_limit(
+                context.sensor_data.temperature_sensors[joint],
+                *filtered_current,
+                parameters,
+            );
+            let level = match time_to_limit {
+                Some(time_to_limit) if time_to_limit < parameters.critical_time_to_limit => {
+                    ThermalLevel::Critical
+                }
+                Some(time_to_limit) if time_to_limit < parameters.warning_time_to_limit => {
+                    ThermalLevel::Warning
+                }
+                _ => ThermalLevel::Normal,
+            };
+
+            thermal_protection.time_to_limit[joint] = time_to_limit;
+            thermal_protection.joint_levels[joint] = level;
+            thermal_protection.stiffness_factors[joint] = match level {
+                ThermalLevel::Normal => 1.0,
+                ThermalLevel::Warning => parameters.warning_stiffness_factor,
+                ThermalLevel::Critical => parameters.critical_stiffness_factor,
+            };
+            thermal_protection.level = thermal_protection.level.max(level);
+            if matches!(joint, JointsName::LeftLeg(_) | JointsName::RightLeg(_))
+                && level >= ThermalLevel::Warning
+            {
+                thermal_protection.limit_walk_speed = true;
+            }
+        }
+
+        if thermal_protection.level == ThermalLevel::Critical {
+            thermal_protection.time_to_reach_kick_position_penalty =
+                parameters.time_to_reach_kick_position_penalty;
+        }
+
+        Ok(MainOutputs {
+            thermal_protection: thermal_protection.into(),
+        })
+    }
+}
+
+/// Predicts when a joint reaches the temperature limit if the current stays constant, assuming it
+/// is heated proportional to the squared current and cools down proportional to the difference to
+/// the ambient temperature
+fn predict_time_to_limit(
+    temperature: f32,
+    current: f32,
+    parameters: &ThermalProtectionParameters,
+) -> Option<Duration> {
+    if temperature >= parameters.temperature_limit {
+        return Some(Duration::ZERO);
+    }
+    let heating = parameters.heating_coefficient * current.powi(2);
+    let seconds = if parameters.cooling_coefficient > f32::EPSILON {
+        let steady_state_temperature =
+            parameters.ambient_temperature + heating / parameters.cooling_coefficient;
+        if steady_state_temperature <= parameters.temperature_limit {
+            return None;
+        }
+        ((steady_state_temperature - temperature)
+            / (steady_state_temperature - parameters.temperature_limit))
+            .ln()
+            / parameters.cooling_coefficient
+    } else {
+        if heating <= f32::EPSILON {
+            return None;
+        }
+        (parameters.temperature_limit - temperature) / heating
+    };
+    // invalid temperature or current readings must not panic
+    Duration::try_from_secs_f32(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_to_limit_follows_the_thermal_model() {
+        let parameters = ThermalProtectionParameters {
+            temperature_limit: 76.0,
+            ambient_temperature: 30.0,
+            heating_coefficient: 0.05,
+            cooling_coefficient: 0.001,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            predict_time_to_limit(80.0, 0.0, &parameters),
+            Some(Duration::ZERO)
+        );
+        // steady state at 1 A is 80 °C, reached asymptotically
+        let time_to_limit = predict_time_to_limit(70.0, 1.0, &parameters).unwrap();
+        assert!((time_to_limit.as_secs_f32() - 916.3).abs() < 1.0);
+        // steady state at 0.5 A is 42.5 °C, below the limit
+        assert_eq!(predict_time_to_limit(70.0, 0.5, &parameters), None);
+        assert_eq!(predict_time_to_limit(f32::NAN, 1.0, &parameters), None);
+        assert_eq!(predict_time_to_limit(70.0, f32::NAN, &parameters), None);
+    }
+}
@@ -8,6 +8,7 @@ use linear_algebra::Vector2;
 use types::{
     dribble_path_plan::DribblePathPlan, motion_command::OrientationMode,
     parameters::BehaviorParameters, planned_path::PathSegment, stand_up::RemainingStandUpDuration,
+    thermal_protection::ThermalProtection,
 };
 
 #[derive(Deserialize, Serialize)]
@@ -17,6 +18,7 @@ use context_attribute::context;
 #[context]
 pub struct CycleContext {
     dribble_path_plan: Input<Option<DribblePathPlan>, "dribble_path_plan?">,
+    thermal_protection: Input<ThermalProtection, "thermal_protection">,
 
     configuration: Parameter<BehaviorParameters, "behavior">,
 
@@ -89,6 +91,11 @@ impl TimeToReachKickPosition {
             (*context.stand_up_front_estimated_remaining_duration).into(),
             (*context.stand_up_sitting_estimated_remaining_duration).into(),
             Some(turn_duration),
+            Some(
+                context
+                    .thermal_protection
+                    .time_to_reach_kick_position_penalty,
+            ),
         ]
         .into_iter()
         .flatten()
@@ -4,13 +4,16 @@ use serde::{Deserialize, Serialize};
 use context_attribute::context;
 use coordinate_systems::{Field, Ground, UpcomingSupport};
 use framework::MainOutput;
+use itertools::chain;
 use linear_algebra::{Isometry2, Point2};
 use spl_network_messages::PlayerNumber;
 use types::{
     ball_position::HypotheticalBallPosition,
+    ball_trajectory::BallTrajectory,
     calibration::CalibrationCommand,
     fall_state::FallState,
     filtered_game_controller_state::FilteredGameControllerState,
+    goal_threat::KeeperDive,
     kick_decision::KickDecision,
     obstacles::Obstacle,
     primary_state::PrimaryState,
@@ -31,6 +34,8 @@ pub struct CycleContext {
     hypothetical_ball_position:
         Input<Vec<HypotheticalBallPosition<Ground>>, "hypothetical_ball_positions">,
     rule_ball: Input<Option<BallState>, "rule_ball_state?">,
+    ball_trajectory: Input<Option<BallTrajectory>, "ball_trajectory?">,
+    keeper_dive: Input<Option<KeeperDive>, "keeper_dive?">,
     filtered_game_controller_state:
         Input<Option<FilteredGameControllerState>, "filtered_game_controller_state?">,
     ground_to_field: Input<Option<Isometry2<Ground, Field>>, "ground_to_field?">,
@@ -45,6 +50,7 @@ pub struct CycleContext {
     fall_state: Input<FallState, "fall_state">,
     has_ground_contact: Input<bool, "has_ground_contact">,
     obstacles: Input<Vec<Obstacle>, "obstacles">,
+    team_obstacles_unseen_by_us: Input<Vec<Obstacle>, "team_obstacles_unseen_by_us">,
     rule_obstacles: Input<Vec<RuleObstacle>, "rule_obstacles">,
     primary_state: Input<PrimaryState, "primary_state">,
     role: Input<Role, "role">,
@@ -77,8 +83,12 @@ impl WorldStateComposer {
         let world_state = WorldState {
             ball: context.ball.copied(),
             rule_ball: context.rule_ball.copied(),
+            ball_trajectory: context.ball_trajectory.cloned(),
+            keeper_dive: context.keeper_dive.copied(),
             suggested_search_position: context.suggested_search_position.copied(),
-            obstacles: context.obstacles.clone(),
+            obstacles: chain!(context.obstacles, context.team_obstacles_unseen_by_us)
+                .copied()
+                .collect(),
             rule_obstacles: context.rule_obstacles.clone(),
             position_of_interest: *context.position_of_interest,
             robot,
@@ -0,0 +1,85 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime},
+};
+
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct EventRecordingParameters {
+    /// Recorded frames are kept this long before an event
+    pub buffer_duration: Duration,
+    /// Recorded frames are written this long after an event
+    pub post_event_duration: Duration,
+}
+
+/// Ring buffer of recording frames which is only written to disk around events
+pub struct EventRecordingBuffer<Frame> {
+    parameters: EventRecordingParameters,
+    frames: VecDeque<(SystemTime, Frame)>,
+    record_until: Option<SystemTime>,
+}
+
+impl<Frame> EventRecordingBuffer<Frame> {
+    pub fn new(parameters: EventRecordingParameters) -> Self {
+        Self {
+            parameters,
+            frames: VecDeque::new(),
+            record_until: None,
+        }
+    }
+
+    /// Returns the frames to write, i.e. the buffered frames and the new one while an event is
+    /// active, nothing otherwise
+    pub fn push(
+        &mut self,
+        timestamp: SystemTime,
+        frame: Frame,
+        event_occurred: bool,
+    ) -> Vec<Frame> {
+        if event_occurred {
+            self.record_until = Some(timestamp + self.parameters.post_event_duration);
+        }
+        self.frames.push_back((timestamp, frame));
+        while self.frames.front().is_some_and(|(oldest_timestamp, _)| {
+            *oldest_timestamp + self.parameters.buffer_duration < timestamp
+        }) {
+            self.frames.pop_front();
+        }
+
+        if self
+            .record_until
+            .is_some_and(|record_until| timestamp <= record_until)
+        {
+            return self.flush();
+        }
+        Vec::new()
+    }
+
+    pub fn flush(&mut self) -> Vec<Frame> {
+        self.frames.drain(..).map(|(_, frame)| frame).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_around_events_are_written() {
+        let mut buffer = EventRecordingBuffer::new(EventRecordingParameters {
+            buffer_duration: Duration::from_secs(2),
+            post_event_duration: Duration::from_secs(1),
+        });
+        let at = |seconds| SystemTime::UNIX_EPOCH + Duration::from_secs(seconds);
+
+        for second in 0..5 {
+            assert!(buffer.push(at(second), second, false).is_empty());
+        }
+        assert_eq!(buffer.push(at(5), 5, true), vec![3, 4, 5]);
+        assert_eq!(buffer.push(at(6), 6, false), vec![6]);
+        assert!(buffer.push(at(7), 7, false).is_empty());
+        assert!(buffer.push(at(8), 8, false).is_empty());
+        assert_eq!(buffer.flush(), vec![7, 8]);
+    }
+}
@@ -1,23 +1,29 @@
 mod additional_output;
+mod event_recording;
 mod future_queue;
 mod historic_databases;
 mod historic_input;
 mod main_output;
+mod node_thread_pool;
 mod panic;
 mod parameters;
 mod perception_databases;
 mod perception_input;
+mod recording_format;
 mod recording_index;
 mod recording_trigger;
 
 pub use additional_output::{should_be_filled, AdditionalOutput};
+pub use event_recording::{EventRecordingBuffer, EventRecordingParameters};
 pub use future_queue::{future_queue, Consumer, Item, Producer, Update, Updates};
 pub use historic_databases::HistoricDatabases;
 pub use historic_input::HistoricInput;
 pub use main_output::MainOutput;
+pub use node_thread_pool::NodeThreadPool;
 pub use panic::deserialize_not_implemented;
 pub use parameters::Parameters;
 pub use perception_databases::PerceptionDatabases;
 pub use perception_input::PerceptionInput;
+pub use recording_format::{Compression, RecordingHeader, RecordingWriter};
 pub use recording_index::{RecordingFrame, RecordingIndex, Timing};
 pub use recording_trigger::RecordingTrigger;
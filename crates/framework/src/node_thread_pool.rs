@@ -0,0 +1,27 @@
+use color_eyre::{eyre::WrapErr, Result};
+use rayon::{Scope, ThreadPool, ThreadPoolBuilder};
+
+/// Threads on which a cycler executes independent nodes concurrently
+pub struct NodeThreadPool {
+    thread_pool: ThreadPool,
+}
+
+impl NodeThreadPool {
+    pub fn new(cycler_instance: &str, number_of_threads: usize) -> Result<Self> {
+        let cycler_instance = cycler_instance.to_string();
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(number_of_threads)
+            .thread_name(move |index| format!("{cycler_instance}Nodes{index}"))
+            .build()
+            .wrap_err("failed to build node thread pool")?;
+        Ok(Self { thread_pool })
+    }
+
+    /// Runs `operation` on the current thread, nodes spawned into the scope run on the pool
+    pub fn scope<'scope, Operation, Return>(&self, operation: Operation) -> Return
+    where
+        Operation: FnOnce(&Scope<'scope>) -> Return,
+    {
+        self.thread_pool.in_place_scope(operation)
+    }
+}
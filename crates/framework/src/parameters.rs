@@ -2,10 +2,17 @@ use std::{collections::HashMap, path::PathBuf};
 
 use serde::Deserialize;
 
+use crate::{Compression, EventRecordingParameters};
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct Parameters {
     pub communication_addresses: Option<String>,
     pub recording_intervals: HashMap<String, usize>,
+    #[serde(default)]
+    pub recording_compression: Compression,
+    /// Only record the seconds around events instead of continuously
+    #[serde(default)]
+    pub event_recording: Option<EventRecordingParameters>,
     pub hardware_parameters: PathBuf,
     pub parameters_directory: PathBuf,
 }
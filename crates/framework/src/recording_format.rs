@@ -0,0 +1,138 @@
+//! Version 2 recording files start with [`RECORDING_FILE_MAGIC`] and a bincode encoded
+//! [`RecordingHeader`], followed by the frames. Each frame is stored like in version 1 recordings
+//! (timestamp, duration, data length and data), its data optionally being compressed. When a
+//! recording is finished, an index of all frames is appended, followed by the offset of that index
+//! and [`RECORDING_INDEX_MAGIC`]. Recordings which were not finished (e.g. because the robot lost
+//! power) have no index and are scanned frame by frame instead.
+
+use std::{
+    io::{self, Write},
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::Timing;
+
+pub const RECORDING_FILE_MAGIC: [u8; 8] = *b"HULKREC2";
+pub const RECORDING_INDEX_MAGIC: [u8; 8] = *b"HULKIDX2";
+/// Index offset (u64) followed by [`RECORDING_INDEX_MAGIC`]
+pub const RECORDING_FOOTER_LENGTH: u64 = 16;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RecordingHeader {
+    pub cycler_instance: String,
+    pub git_commit: Option<String>,
+    pub body_id: String,
+    pub head_id: String,
+    /// Hash of the nodes and their contexts of the recorded cycler, recordings with a different
+    /// hash were most likely recorded with incompatible types
+    pub schema_hash: u64,
+    pub compression: Compression,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd {
+        level: i32,
+    },
+}
+
+impl Compression {
+    pub fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Zstd { level } => zstd::encode_all(data, *level),
+        }
+    }
+
+    pub fn decompress(&self, data: Vec<u8>) -> io::Result<Vec<u8>> {
+        match self {
+            Compression::None => Ok(data),
+            Compression::Zstd { .. } => zstd::decode_all(data.as_slice()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub(crate) struct IndexEntry {
+    pub timing: Timing,
+    pub offset: u64,
+    pub header_length: u64,
+    pub length: u64,
+}
+
+pub struct RecordingWriter<W> {
+    writer: W,
+    compression: Compression,
+    offset: u64,
+    index: Vec<IndexEntry>,
+}
+
+impl<W: Write> RecordingWriter<W> {
+    pub fn new(mut writer: W, header: &RecordingHeader) -> Result<Self> {
+        let mut file_header = RECORDING_FILE_MAGIC.to_vec();
+        bincode::serialize_into(&mut file_header, header)
+            .wrap_err("failed to serialize recording header")?;
+        writer
+            .write_all(&file_header)
+            .wrap_err("failed to write recording header")?;
+        Ok(Self {
+            writer,
+            compression: header.compression,
+            offset: file_header.len() as u64,
+            index: Vec::new(),
+        })
+    }
+
+    pub fn write_frame(
+        &mut self,
+        timestamp: SystemTime,
+        duration: Duration,
+        data: &[u8],
+    ) -> Result<()> {
+        let data = self
+            .compression
+            .compress(data)
+            .wrap_err("failed to compress frame")?;
+        let mut frame_header = Vec::new();
+        bincode::serialize_into(&mut frame_header, &timestamp)
+            .wrap_err("failed to serialize timestamp")?;
+        bincode::serialize_into(&mut frame_header, &duration)
+            .wrap_err("failed to serialize duration")?;
+        bincode::serialize_into(&mut frame_header, &data.len())
+            .wrap_err("failed to serialize data length")?;
+        self.writer
+            .write_all(&frame_header)
+            .and_then(|()| self.writer.write_all(&data))
+            .wrap_err("failed to write frame")?;
+
+        self.index.push(IndexEntry {
+            timing: Timing {
+                timestamp,
+                duration,
+            },
+            offset: self.offset,
+            header_length: frame_header.len() as u64,
+            length: data.len() as u64,
+        });
+        self.offset += (frame_header.len() + data.len()) as u64;
+        Ok(())
+    }
+
+    /// Appends the seek index, without it readers have to scan all frames
+    pub fn finish(mut self) -> Result<W> {
+        let mut footer = Vec::new();
+        bincode::serialize_into(&mut footer, &self.index).wrap_err("failed to serialize index")?;
+        footer.extend_from_slice(&self.offset.to_le_bytes());
+        footer.extend_from_slice(&RECORDING_INDEX_MAGIC);
+        self.writer
+            .write_all(&footer)
+            .wrap_err("failed to write index")?;
+        self.writer.flush().wrap_err("failed to flush recording")?;
+        Ok(self.writer)
+    }
+}
@@ -7,10 +7,17 @@ use std::{
 
 use bincode::{deserialize_from, Error};
 use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+
+use crate::recording_format::{
+    IndexEntry, RecordingHeader, RECORDING_FILE_MAGIC, RECORDING_FOOTER_LENGTH,
+    RECORDING_INDEX_MAGIC,
+};
 
 #[derive(Debug)]
 pub struct RecordingIndex {
     file: File,
+    header: Option<RecordingHeader>,
     frames: Vec<RecordingFrameMetadata>,
 }
 
@@ -22,8 +29,6 @@ impl RecordingIndex {
     }
 
     fn collect_frames(mut recording_file: File) -> color_eyre::Result<Self> {
-        let mut frames = Vec::new();
-
         recording_file
             .seek(SeekFrom::End(0))
             .wrap_err("failed to seek to end of file")?;
@@ -32,62 +37,49 @@ impl RecordingIndex {
             .wrap_err("failed to get stream position of end of file")?;
         recording_file.rewind().wrap_err("failed to rewind file")?;
 
-        let mut offset = 0;
-        while offset < file_length {
-            let Some(timestamp) =
-                end_of_file_error_as_option(deserialize_from(&mut recording_file))
-                    .wrap_err("failed to deserialize timestamp")?
-            else {
-                eprintln!("unexpected end of file of recording file while deserializing timestamp");
-                break;
-            };
-            let Some(duration) = end_of_file_error_as_option(deserialize_from(&mut recording_file))
-                .wrap_err("failed to deserialize duration")?
-            else {
-                eprintln!("unexpected end of file of recording file while deserializing duration");
-                break;
-            };
-            let Some(length) = end_of_file_error_as_option(deserialize_from(&mut recording_file))
-                .wrap_err("failed to deserialize data length")?
-            else {
-                eprintln!("unexpected end of file of recording file while deserializing length");
-                break;
-            };
-            let header_length = recording_file
-                .stream_position()
-                .wrap_err("failed to get stream position")?
-                - offset;
-            if let Err(error) = recording_file.seek(SeekFrom::Current(length as i64)) {
-                eprintln!("failed to seek to end of data: {error}");
-                break;
-            }
-
-            if offset + header_length + length as u64 > file_length {
-                eprintln!("unexpected end of file of recording file");
-                break;
-            }
-            frames.push(RecordingFrameMetadata {
-                timing: Timing {
-                    timestamp,
-                    duration,
-                },
-                offset: offset.try_into().unwrap(),
-                header_offset: header_length.try_into().unwrap(),
-                length,
+        let mut magic = [0; RECORDING_FILE_MAGIC.len()];
+        let is_version_2 = file_length >= magic.len() as u64
+            && recording_file.read_exact(&mut magic).is_ok()
+            && magic == RECORDING_FILE_MAGIC;
+        if !is_version_2 {
+            recording_file.rewind().wrap_err("failed to rewind file")?;
+            let frames = scan_frames(&mut recording_file, 0, file_length)?;
+            recording_file.rewind().wrap_err("failed to rewind file")?;
+            return Ok(Self {
+                file: recording_file,
+                header: None,
+                frames,
             });
-            offset = recording_file
-                .stream_position()
-                .wrap_err("failed to get stream position")?;
         }
 
+        let header: RecordingHeader = deserialize_from(&mut recording_file)
+            .wrap_err("failed to deserialize recording header")?;
+        let frames_start = recording_file
+            .stream_position()
+            .wrap_err("failed to get stream position")?;
+        let frames = match read_index(&mut recording_file, file_length)? {
+            Some(frames) => frames,
+            None => {
+                recording_file
+                    .seek(SeekFrom::Start(frames_start))
+                    .wrap_err("failed to seek to first frame")?;
+                scan_frames(&mut recording_file, frames_start, file_length)?
+            }
+        };
         recording_file.rewind().wrap_err("failed to rewind file")?;
 
         Ok(Self {
             file: recording_file,
+            header: Some(header),
             frames,
         })
     }
 
+    /// Recordings of version 1 have no header
+    pub fn header(&self) -> Option<&RecordingHeader> {
+        self.header.as_ref()
+    }
+
     pub fn number_of_frames(&self) -> usize {
         self.frames.len()
     }
@@ -115,6 +107,12 @@ impl RecordingIndex {
         self.file
             .read_exact(&mut data)
             .wrap_err("failed to read from recording file")?;
+        if let Some(header) = &self.header {
+            data = header
+                .compression
+                .decompress(data)
+                .wrap_err("failed to decompress frame")?;
+        }
         Ok(Some(RecordingFrame {
             timing: frame.timing,
             data,
@@ -148,12 +146,109 @@ pub struct RecordingFrame {
     pub data: Vec<u8>,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct Timing {
     pub timestamp: SystemTime,
     pub duration: Duration,
 }
 
+/// Collects frames one after another, stopping at the first incomplete frame
+fn scan_frames(
+    recording_file: &mut File,
+    start: u64,
+    end: u64,
+) -> color_eyre::Result<Vec<RecordingFrameMetadata>> {
+    let mut frames = Vec::new();
+    let mut offset = start;
+    while offset < end {
+        let Some(timestamp) = end_of_file_error_as_option(deserialize_from(&mut *recording_file))
+            .wrap_err("failed to deserialize timestamp")?
+        else {
+            eprintln!("unexpected end of file of recording file while deserializing timestamp");
+            break;
+        };
+        let Some(duration) = end_of_file_error_as_option(deserialize_from(&mut *recording_file))
+            .wrap_err("failed to deserialize duration")?
+        else {
+            eprintln!("unexpected end of file of recording file while deserializing duration");
+            break;
+        };
+        let Some(length) = end_of_file_error_as_option(deserialize_from(&mut *recording_file))
+            .wrap_err("failed to deserialize data length")?
+        else {
+            eprintln!("unexpected end of file of recording file while deserializing length");
+            break;
+        };
+        let header_length = recording_file
+            .stream_position()
+            .wrap_err("failed to get stream position")?
+            - offset;
+        if let Err(error) = recording_file.seek(SeekFrom::Current(length as i64)) {
+            eprintln!("failed to seek to end of data: {error}");
+            break;
+        }
+
+        if offset + header_length + length as u64 > end {
+            eprintln!("unexpected end of file of recording file");
+            break;
+        }
+        frames.push(RecordingFrameMetadata {
+            timing: Timing {
+                timestamp,
+                duration,
+            },
+            offset: offset.try_into().unwrap(),
+            header_offset: header_length.try_into().unwrap(),
+            length,
+        });
+        offset = recording_file
+            .stream_position()
+            .wrap_err("failed to get stream position")?;
+    }
+    Ok(frames)
+}
+
+/// Reads the index footer of a finished version 2 recording
+fn read_index(
+    recording_file: &mut File,
+    file_length: u64,
+) -> color_eyre::Result<Option<Vec<RecordingFrameMetadata>>> {
+    if file_length < RECORDING_FOOTER_LENGTH {
+        return Ok(None);
+    }
+    recording_file
+        .seek(SeekFrom::End(-(RECORDING_FOOTER_LENGTH as i64)))
+        .wrap_err("failed to seek to footer")?;
+    let mut footer = [0; RECORDING_FOOTER_LENGTH as usize];
+    recording_file
+        .read_exact(&mut footer)
+        .wrap_err("failed to read footer")?;
+    let (index_offset, magic) = footer.split_at(8);
+    if magic != RECORDING_INDEX_MAGIC {
+        return Ok(None);
+    }
+    let index_offset = u64::from_le_bytes(index_offset.try_into().unwrap());
+    if index_offset > file_length - RECORDING_FOOTER_LENGTH {
+        return Ok(None);
+    }
+    recording_file
+        .seek(SeekFrom::Start(index_offset))
+        .wrap_err("failed to seek to index")?;
+    let index: Vec<IndexEntry> =
+        deserialize_from(recording_file).wrap_err("failed to deserialize index")?;
+    Ok(Some(
+        index
+            .into_iter()
+            .map(|entry| RecordingFrameMetadata {
+                timing: entry.timing,
+                offset: entry.offset.try_into().unwrap(),
+                header_offset: entry.header_length.try_into().unwrap(),
+                length: entry.length.try_into().unwrap(),
+            })
+            .collect(),
+    ))
+}
+
 fn end_of_file_error_as_option<T>(result: Result<T, Error>) -> Result<Option<T>, Error> {
     result.map(Some).or_else(|error| {
         if let bincode::ErrorKind::Io(ref error) = *error {
@@ -164,3 +259,91 @@ fn end_of_file_error_as_option<T>(result: Result<T, Error>) -> Result<Option<T>,
         Err(error)
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use crate::{Compression, RecordingHeader, RecordingWriter};
+
+    use super::*;
+
+    fn frames() -> Vec<(SystemTime, Duration, Vec<u8>)> {
+        (0..5)
+            .map(|index| {
+                (
+                    SystemTime::UNIX_EPOCH + Duration::from_millis(12 * index),
+                    Duration::from_millis(index),
+                    vec![index as u8; 100 * index as usize],
+                )
+            })
+            .collect()
+    }
+
+    fn assert_frames_are_readable(index: &mut RecordingIndex) {
+        assert_eq!(index.number_of_frames(), 5);
+        for (timestamp, duration, data) in frames() {
+            let frame = index.find_latest_frame_up_to(timestamp).unwrap().unwrap();
+            assert_eq!(frame.timing.timestamp, timestamp);
+            assert_eq!(frame.timing.duration, duration);
+            assert_eq!(frame.data, data);
+        }
+    }
+
+    fn write_version_2(file: &mut File, compression: Compression, finish: bool) -> RecordingHeader {
+        let header = RecordingHeader {
+            cycler_instance: "Control".to_string(),
+            git_commit: Some("0123abc".to_string()),
+            compression,
+            ..Default::default()
+        };
+        let mut writer = RecordingWriter::new(&mut *file, &header).unwrap();
+        for (timestamp, duration, data) in frames() {
+            writer.write_frame(timestamp, duration, &data).unwrap();
+        }
+        if finish {
+            writer.finish().unwrap();
+        }
+        header
+    }
+
+    #[test]
+    fn version_1_recordings_are_read_without_header() {
+        let mut file = tempfile::tempfile().unwrap();
+        for (timestamp, duration, data) in frames() {
+            bincode::serialize_into(&mut file, &timestamp).unwrap();
+            bincode::serialize_into(&mut file, &duration).unwrap();
+            bincode::serialize_into(&mut file, &data.len()).unwrap();
+            file.write_all(&data).unwrap();
+        }
+
+        let mut index = RecordingIndex::collect_frames(file).unwrap();
+
+        assert!(index.header().is_none());
+        assert_frames_are_readable(&mut index);
+    }
+
+    #[test]
+    fn finished_version_2_recordings_are_read_from_index() {
+        for compression in [Compression::None, Compression::Zstd { level: 3 }] {
+            let mut file = tempfile::tempfile().unwrap();
+            let header = write_version_2(&mut file, compression, true);
+
+            let mut index = RecordingIndex::collect_frames(file).unwrap();
+
+            assert_eq!(index.header(), Some(&header));
+            assert_frames_are_readable(&mut index);
+        }
+    }
+
+    #[test]
+    fn unfinished_version_2_recordings_are_scanned() {
+        let mut file = tempfile::tempfile().unwrap();
+        let header = write_version_2(&mut file, Compression::Zstd { level: 1 }, false);
+
+        let mut index = RecordingIndex::collect_frames(file).unwrap();
+
+        assert_eq!(index.header(), Some(&header));
+        assert_frames_are_readable(&mut index);
+    }
+}
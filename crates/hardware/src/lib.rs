@@ -9,6 +9,7 @@ use types::{
     joints::Joints,
     led::Leds,
     messages::{IncomingMessage, OutgoingMessage},
+    recording_event::RecordingEvent,
     samples::Samples,
     sensor_data::SensorData,
     ycbcr422_image::YCbCr422Image,
@@ -47,6 +48,10 @@ pub trait PathsInterface {
 pub trait RecordingInterface {
     fn should_record(&self) -> bool;
     fn set_whether_to_record(&self, enable: bool);
+    /// Keeps the recording ring buffer when event triggered recording is enabled
+    fn trigger_recording_event(&self, event: RecordingEvent);
+    /// Takes all events triggered since the last call
+    fn take_recording_events(&self) -> Vec<RecordingEvent>;
 }
 
 pub trait SensorInterface {
@@ -1,14 +1,18 @@
+use std::{path::Path, process::Command};
+
 use code_generation::{generate, write_to_file::WriteToFile, ExecutionMode};
 use color_eyre::eyre::{Result, WrapErr};
 use hulk_manifest::collect_hulk_cyclers;
 use source_analyzer::{pretty::to_string_pretty, structs::Structs};
 
 fn main() -> Result<()> {
-    let cyclers = collect_hulk_cyclers("..")?;
+    let cyclers = collect_hulk_cyclers("..", None)?;
     for path in cyclers.watch_paths() {
         println!("cargo:rerun-if-changed={}", path.display());
     }
 
+    emit_git_commit();
+
     println!();
     println!("{}", to_string_pretty(&cyclers)?);
 
@@ -17,3 +21,21 @@ fn main() -> Result<()> {
         .write_to_file("generated_code.rs")
         .wrap_err("failed to write generated code to file")
 }
+
+/// Recordings store the commit they were recorded with, a missing git repository is no error
+fn emit_git_commit() {
+    for path in ["../../.git/HEAD", "../../.git/refs/heads"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+    let Ok(output) = Command::new("git").args(["rev-parse", "HEAD"]).output() else {
+        return;
+    };
+    if output.status.success() {
+        println!(
+            "cargo:rustc-env=HULK_GIT_COMMIT={}",
+            String::from_utf8_lossy(&output.stdout).trim()
+        );
+    }
+}
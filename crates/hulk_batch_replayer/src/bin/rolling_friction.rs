@@ -0,0 +1,184 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{stdin, BufRead, BufReader},
+    path::PathBuf,
+};
+
+use ball_filter::trajectory::{fit_rolling_friction, RollingObservation};
+use clap::Parser;
+use color_eyre::{
+    eyre::{bail, ContextCompat, Result, WrapErr},
+    install,
+};
+use coordinate_systems::{Field, Ground};
+use linear_algebra::{Isometry2, Point2};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use types::ball_position::BallPosition;
+
+const BALL_POSITION_PATH: &str = "Control.main_outputs.ball_position";
+const GROUND_TO_FIELD_PATH: &str = "Control.main_outputs.ground_to_field";
+/// Ball movements of consecutive intervals pointing into more different directions are bounces
+/// or kicks
+const MINIMUM_DIRECTION_COSINE: f32 = 0.95;
+
+/// Fits the rolling friction of the ball trajectory prediction to ball positions dumped by the
+/// batch replayer with `--path Control.main_outputs.ball_position --path
+/// Control.main_outputs.ground_to_field`.
+///
+/// Speeds are estimated from position differences in field coordinates instead of the filtered
+/// velocity, which already contains the velocity decay of the ball filter.
+#[derive(Parser, Debug)]
+#[clap(name = "rolling_friction")]
+struct CommandlineArguments {
+    /// JSON lines written by the batch replayer, defaults to stdin
+    input: Option<PathBuf>,
+    /// Time over which ball positions are differentiated to estimate the speed in seconds
+    #[arg(long, default_value_t = 0.2)]
+    interval: f64,
+    /// Ball speeds below this are ignored because the ball may already rest
+    #[arg(long, default_value_t = 0.2)]
+    minimum_speed: f32,
+}
+
+#[derive(Deserialize)]
+struct Line {
+    recording: String,
+    timestamp: f64,
+    outputs: BTreeMap<String, Value>,
+}
+
+impl Line {
+    fn output<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let value = self
+            .outputs
+            .get(path)
+            .wrap_err_with(|| format!("line does not contain `{path}`"))?;
+        serde_json::from_value(value.clone()).wrap_err_with(|| format!("failed to parse `{path}`"))
+    }
+}
+
+fn main() -> Result<()> {
+    install()?;
+
+    let arguments = CommandlineArguments::parse();
+    let reader: Box<dyn BufRead> = match &arguments.input {
+        Some(input) => Box::new(BufReader::new(
+            File::open(input).wrap_err("failed to open input file")?,
+        )),
+        None => Box::new(stdin().lock()),
+    };
+
+    let mut positions_per_recording: BTreeMap<String, Vec<(f64, Option<Point2<Field>>)>> =
+        BTreeMap::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.wrap_err("failed to read input")?;
+        let line: Line = serde_json::from_str(&line)
+            .wrap_err_with(|| format!("failed to parse line {}", index + 1))?;
+        let ball_position: Option<BallPosition<Ground>> = line
+            .output(BALL_POSITION_PATH)
+            .wrap_err_with(|| format!("invalid line {}", index + 1))?;
+        let ground_to_field: Option<Isometry2<Ground, Field>> =
+            line.output(GROUND_TO_FIELD_PATH)
+                .wrap_err_with(|| format!("invalid line {}", index + 1))?;
+        positions_per_recording
+            .entry(line.recording)
+            .or_default()
+            .push((
+                line.timestamp,
+                ball_position
+                    .zip(ground_to_field)
+                    .map(|(ball_position, ground_to_field)| {
+                        ground_to_field * ball_position.position
+                    }),
+            ));
+    }
+
+    let observations: Vec<_> = positions_per_recording
+        .into_values()
+        .flat_map(|mut positions| {
+            positions.sort_by(|a, b| a.0.total_cmp(&b.0));
+            observations_of_recording(&positions, arguments.interval, arguments.minimum_speed)
+        })
+        .collect();
+    let Some(friction) = fit_rolling_friction(&observations) else {
+        bail!(
+            "{} observations of a rolling ball are not enough to fit the friction",
+            observations.len()
+        );
+    };
+
+    eprintln!("fitted {} observations", observations.len());
+    println!(
+        "{:#}",
+        json!({
+            "ball_trajectory": {
+                "rolling_deceleration": friction.rolling_deceleration,
+                "velocity_drag": friction.velocity_drag,
+            }
+        })
+    );
+    Ok(())
+}
+
+/// Estimates the speed in two consecutive intervals from the position differences and the
+/// deceleration from their change
+fn observations_of_recording(
+    positions: &[(f64, Option<Point2<Field>>)],
+    interval: f64,
+    minimum_speed: f32,
+) -> Vec<RollingObservation> {
+    let index_after_interval = |start: usize| {
+        let start_time = positions[start].0;
+        positions[start..]
+            .iter()
+            .position(|(time, _)| time - start_time >= interval)
+            .map(|offset| start + offset)
+    };
+    let mut observations = Vec::new();
+    for start in 0..positions.len() {
+        let Some(middle) = index_after_interval(start) else {
+            break;
+        };
+        let Some(end) = index_after_interval(middle) else {
+            break;
+        };
+        let (start_time, middle_time, end_time) =
+            (positions[start].0, positions[middle].0, positions[end].0);
+        // gaps in the recording would hide bounces and kicks
+        if middle_time - start_time > 2.0 * interval || end_time - middle_time > 2.0 * interval {
+            continue;
+        }
+        if positions[start..=end]
+            .iter()
+            .any(|(_, position)| position.is_none())
+        {
+            continue;
+        }
+        let (Some(start_position), Some(middle_position), Some(end_position)) =
+            (positions[start].1, positions[middle].1, positions[end].1)
+        else {
+            continue;
+        };
+        let first_movement = middle_position - start_position;
+        let second_movement = end_position - middle_position;
+        let first_speed = first_movement.norm() / (middle_time - start_time) as f32;
+        let second_speed = second_movement.norm() / (end_time - middle_time) as f32;
+        if first_speed.min(second_speed) < minimum_speed {
+            continue;
+        }
+        let direction_cosine =
+            first_movement.dot(&second_movement) / (first_movement.norm() * second_movement.norm());
+        if direction_cosine < MINIMUM_DIRECTION_COSINE {
+            continue;
+        }
+        // the speeds are averages over the intervals and therefore belong to their centers
+        let elapsed = (end_time - start_time) / 2.0;
+        observations.push(RollingObservation {
+            speed: (first_speed + second_speed) / 2.0,
+            deceleration: (first_speed - second_speed) / elapsed as f32,
+        });
+    }
+    observations
+}
@@ -0,0 +1,77 @@
+use hardware::{
+    ActuatorInterface, NetworkInterface, PathsInterface, RecordingInterface, SpeakerInterface,
+};
+
+use color_eyre::eyre::Result;
+
+use hula_types::hardware::Paths;
+use types::{
+    audio::SpeakerRequest,
+    joints::Joints,
+    led::Leds,
+    messages::{IncomingMessage, OutgoingMessage},
+    recording_event::RecordingEvent,
+};
+
+pub trait HardwareInterface:
+    ActuatorInterface + NetworkInterface + PathsInterface + RecordingInterface + SpeakerInterface
+{
+}
+
+pub struct BatchReplayerHardwareInterface;
+
+/// `write_to_actuators` is a noop during replay
+impl ActuatorInterface for BatchReplayerHardwareInterface {
+    fn write_to_actuators(
+        &self,
+        _positions: Joints<f32>,
+        _stiffnesses: Joints<f32>,
+        _leds: Leds,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// `read_from_network` is only executed in setup nodes, which are not executed during replay
+/// `write_to_network` is a noop during replay
+impl NetworkInterface for BatchReplayerHardwareInterface {
+    fn read_from_network(&self) -> Result<IncomingMessage> {
+        panic!("failed to read from network during replay")
+    }
+
+    fn write_to_network(&self, _message: OutgoingMessage) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// recording is not supported for replaying
+impl RecordingInterface for BatchReplayerHardwareInterface {
+    fn should_record(&self) -> bool {
+        false
+    }
+
+    fn set_whether_to_record(&self, _enable: bool) {}
+
+    fn trigger_recording_event(&self, _event: RecordingEvent) {}
+
+    fn take_recording_events(&self) -> Vec<RecordingEvent> {
+        Vec::new()
+    }
+}
+
+/// batch replay does not produce speaker outputs
+impl SpeakerInterface for BatchReplayerHardwareInterface {
+    fn write_to_speakers(&self, _request: SpeakerRequest) {}
+}
+
+impl PathsInterface for BatchReplayerHardwareInterface {
+    fn get_paths(&self) -> Paths {
+        Paths {
+            motions: "etc/motions".into(),
+            neural_networks: "etc/neural_networks".into(),
+            sounds: "etc/sounds".into(),
+        }
+    }
+}
+
+impl HardwareInterface for BatchReplayerHardwareInterface {}
@@ -0,0 +1,249 @@
+#![recursion_limit = "256"]
+
+use std::{
+    fs::{read_dir, File},
+    io::{stdout, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use clap::Parser;
+use color_eyre::{
+    eyre::{bail, Result, WrapErr},
+    install,
+};
+
+use hula_types::hardware::Ids;
+
+use crate::{
+    execution::Replayer,
+    hardware_interface::{BatchReplayerHardwareInterface, HardwareInterface},
+    output::{OutputFormat, OutputWriter},
+    replay::replay_cycler,
+};
+
+mod hardware_interface;
+mod output;
+mod replay;
+
+include!(concat!(env!("OUT_DIR"), "/generated_code.rs"));
+
+const CYCLER_INSTANCES: [&str; 5] = [
+    "Audio",
+    "Control",
+    "SplNetwork",
+    "VisionTop",
+    "VisionBottom",
+];
+
+#[derive(Parser, Debug)]
+#[clap(name = "batch_replayer")]
+struct CommandlineArguments {
+    /// Recording directories or directories which are searched recursively for recordings
+    #[arg(required = true)]
+    recordings: Vec<PathBuf>,
+    /// Output to dump, prefixed with the cycler instance, e.g. `Control.main_outputs.ground_to_field`
+    #[arg(long = "path", short, required = true)]
+    paths: Vec<String>,
+    /// Parameter directory used for all recordings, defaults to the parameters of each recording
+    #[arg(long)]
+    parameters_directory: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = OutputFormat::JsonLines)]
+    format: OutputFormat,
+    /// File to write to, defaults to stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    install()?;
+
+    let arguments = CommandlineArguments::parse();
+
+    for path in &arguments.paths {
+        if !CYCLER_INSTANCES.contains(&cycler_of(path)) {
+            bail!("`{path}` does not start with one of the cycler instances {CYCLER_INSTANCES:?}");
+        }
+    }
+
+    let mut recordings = Vec::new();
+    for path in &arguments.recordings {
+        collect_recordings(path, &mut recordings)
+            .wrap_err_with(|| format!("failed to search {} for recordings", path.display()))?;
+    }
+    if recordings.is_empty() {
+        bail!("no recordings found");
+    }
+
+    let writer: Box<dyn Write> = match &arguments.output {
+        Some(output) => Box::new(BufWriter::new(
+            File::create(output).wrap_err("failed to create output file")?,
+        )),
+        None => Box::new(BufWriter::new(stdout().lock())),
+    };
+    let mut output_writer = OutputWriter::new(writer, arguments.format, arguments.paths.clone())?;
+
+    let mut number_of_failed_frames = 0;
+    for recording in &recordings {
+        number_of_failed_frames += replay_recording(recording, &arguments, &mut output_writer)
+            .wrap_err_with(|| format!("failed to replay {}", recording.display()))?;
+    }
+    output_writer.flush()?;
+
+    if number_of_failed_frames > 0 {
+        bail!(
+            "nodes failed in {number_of_failed_frames} frames of {} recordings",
+            recordings.len()
+        );
+    }
+    Ok(())
+}
+
+fn replay_recording(
+    recording: &Path,
+    arguments: &CommandlineArguments,
+    output_writer: &mut OutputWriter<impl Write>,
+) -> Result<usize> {
+    let ids = Ids {
+        body_id: "replayer".into(),
+        head_id: "replayer".into(),
+    };
+    let parameters_directory = arguments
+        .parameters_directory
+        .clone()
+        .unwrap_or_else(|| recording.to_path_buf());
+    let mut replayer = Replayer::new(
+        Arc::new(BatchReplayerHardwareInterface),
+        parameters_directory,
+        ids,
+        recording,
+    )
+    .wrap_err("failed to create replayer")?;
+    let recording_name = recording.display().to_string();
+
+    let mut number_of_failed_frames = 0;
+    for cycler_name in CYCLER_INSTANCES {
+        let paths: Vec<_> = arguments
+            .paths
+            .iter()
+            .filter(|path| cycler_of(path) == cycler_name)
+            .cloned()
+            .collect();
+        if paths.is_empty() {
+            continue;
+        }
+        let subscriptions = paths.iter().filter_map(|path| {
+            let path = path.strip_prefix(cycler_name)?.strip_prefix('.')?;
+            path.starts_with("additional_outputs")
+                .then(|| path.to_string())
+        });
+
+        number_of_failed_frames += match cycler_name {
+            "Audio" => {
+                replayer
+                    .audio_subscriptions_sender
+                    .borrow_mut()
+                    .extend(subscriptions);
+                let receiver = replayer.audio_receiver();
+                replay_cycler(
+                    &mut replayer,
+                    &recording_name,
+                    cycler_name,
+                    &paths,
+                    receiver,
+                    output_writer,
+                )?
+            }
+            "Control" => {
+                replayer
+                    .control_subscriptions_sender
+                    .borrow_mut()
+                    .extend(subscriptions);
+                let receiver = replayer.control_receiver();
+                replay_cycler(
+                    &mut replayer,
+                    &recording_name,
+                    cycler_name,
+                    &paths,
+                    receiver,
+                    output_writer,
+                )?
+            }
+            "SplNetwork" => {
+                replayer
+                    .spl_network_subscriptions_sender
+                    .borrow_mut()
+                    .extend(subscriptions);
+                let receiver = replayer.spl_network_receiver();
+                replay_cycler(
+                    &mut replayer,
+                    &recording_name,
+                    cycler_name,
+                    &paths,
+                    receiver,
+                    output_writer,
+                )?
+            }
+            "VisionTop" => {
+                replayer
+                    .vision_top_subscriptions_sender
+                    .borrow_mut()
+                    .extend(subscriptions);
+                let receiver = replayer.vision_top_receiver();
+                replay_cycler(
+                    &mut replayer,
+                    &recording_name,
+                    cycler_name,
+                    &paths,
+                    receiver,
+                    output_writer,
+                )?
+            }
+            "VisionBottom" => {
+                replayer
+                    .vision_bottom_subscriptions_sender
+                    .borrow_mut()
+                    .extend(subscriptions);
+                let receiver = replayer.vision_bottom_receiver();
+                replay_cycler(
+                    &mut replayer,
+                    &recording_name,
+                    cycler_name,
+                    &paths,
+                    receiver,
+                    output_writer,
+                )?
+            }
+            _ => unreachable!("paths were validated against the cycler instances"),
+        };
+    }
+
+    Ok(number_of_failed_frames)
+}
+
+fn cycler_of(path: &str) -> &str {
+    path.split('.').next().unwrap_or_default()
+}
+
+/// A recording is a directory containing the `.bincode` files written by the cyclers
+fn collect_recordings(path: &Path, recordings: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries: Vec<_> = read_dir(path)
+        .wrap_err_with(|| format!("failed to read {}", path.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    entries.sort();
+
+    if entries.iter().any(|entry| {
+        entry
+            .extension()
+            .is_some_and(|extension| extension == "bincode")
+    }) {
+        recordings.push(path.to_path_buf());
+        return Ok(());
+    }
+    for entry in entries.iter().filter(|entry| entry.is_dir()) {
+        collect_recordings(entry, recordings)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use std::{
+    io::Write,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::ValueEnum;
+use color_eyre::{eyre::WrapErr, Result};
+use serde_json::{json, Map, Value};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum OutputFormat {
+    JsonLines,
+    Csv,
+}
+
+/// One replayed frame of one cycler with the values of all requested outputs of that cycler
+pub struct Row<'a> {
+    pub recording: &'a str,
+    pub cycler: &'a str,
+    pub timestamp: SystemTime,
+    /// Pairs of the requested path (including the cycler prefix) and its serialized value,
+    /// `None` if the output was not available in this frame
+    pub values: Vec<(&'a str, Option<Value>)>,
+}
+
+pub struct OutputWriter<W> {
+    writer: W,
+    format: OutputFormat,
+    columns: Vec<String>,
+}
+
+impl<W: Write> OutputWriter<W> {
+    pub fn new(mut writer: W, format: OutputFormat, columns: Vec<String>) -> Result<Self> {
+        if let OutputFormat::Csv = format {
+            let header: Vec<_> = ["recording", "cycler", "timestamp"]
+                .into_iter()
+                .chain(columns.iter().map(String::as_str))
+                .map(escape_csv_field)
+                .collect();
+            writeln!(writer, "{}", header.join(",")).wrap_err("failed to write CSV header")?;
+        }
+        Ok(Self {
+            writer,
+            format,
+            columns,
+        })
+    }
+
+    pub fn write_row(&mut self, row: Row) -> Result<()> {
+        let timestamp = row
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        match self.format {
+            OutputFormat::JsonLines => {
+                let outputs: Map<String, Value> = row
+                    .values
+                    .into_iter()
+                    .map(|(path, value)| (path.to_string(), value.unwrap_or(Value::Null)))
+                    .collect();
+                let line = json!({
+                    "recording": row.recording,
+                    "cycler": row.cycler,
+                    "timestamp": timestamp,
+                    "outputs": outputs,
+                });
+                writeln!(self.writer, "{line}").wrap_err("failed to write JSON line")
+            }
+            OutputFormat::Csv => {
+                let fields: Vec<_> = [
+                    escape_csv_field(row.recording),
+                    escape_csv_field(row.cycler),
+                    timestamp.to_string(),
+                ]
+                .into_iter()
+                .chain(self.columns.iter().map(|column| {
+                    row.values
+                        .iter()
+                        .find(|(path, _)| path == column)
+                        .and_then(|(_, value)| value.as_ref())
+                        .map(|value| escape_csv_field(&value.to_string()))
+                        .unwrap_or_default()
+                }))
+                .collect();
+                writeln!(self.writer, "{}", fields.join(",")).wrap_err("failed to write CSV row")
+            }
+        }
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().wrap_err("failed to flush output")
+    }
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_rows_are_aligned_to_columns_and_escaped() {
+        let mut buffer = Vec::new();
+        let columns = vec![
+            "Control.main_outputs.ground_to_field".to_string(),
+            "Control.main_outputs.is_localization_converged".to_string(),
+        ];
+        let mut writer = OutputWriter::new(&mut buffer, OutputFormat::Csv, columns).unwrap();
+        writer
+            .write_row(Row {
+                recording: "logs/10.1.24.32",
+                cycler: "Control",
+                timestamp: UNIX_EPOCH,
+                values: vec![
+                    (
+                        "Control.main_outputs.is_localization_converged",
+                        Some(json!(true)),
+                    ),
+                    (
+                        "Control.main_outputs.ground_to_field",
+                        Some(json!([1.0, 2.0])),
+                    ),
+                ],
+            })
+            .unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        let mut lines = output.lines();
+        assert_eq!(
+            lines.next(),
+            Some("recording,cycler,timestamp,Control.main_outputs.ground_to_field,Control.main_outputs.is_localization_converged")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("logs/10.1.24.32,Control,0,\"[1.0,2.0]\",true")
+        );
+    }
+
+    #[test]
+    fn missing_outputs_are_null_in_json_lines() {
+        let mut buffer = Vec::new();
+        let mut writer = OutputWriter::new(&mut buffer, OutputFormat::JsonLines, vec![]).unwrap();
+        writer
+            .write_row(Row {
+                recording: "recording",
+                cycler: "VisionTop",
+                timestamp: UNIX_EPOCH,
+                values: vec![("VisionTop.main_outputs.balls", None)],
+            })
+            .unwrap();
+
+        let line: Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(line["outputs"]["VisionTop.main_outputs.balls"], Value::Null);
+        assert_eq!(line["cycler"], "VisionTop");
+    }
+}
@@ -0,0 +1,87 @@
+use std::{io::Write, time::SystemTime};
+
+use color_eyre::{
+    eyre::{ContextCompat, WrapErr},
+    Result,
+};
+use path_serde::PathSerialize;
+
+use buffered_watch::Receiver;
+
+use crate::{
+    execution::Replayer,
+    hardware_interface::BatchReplayerHardwareInterface,
+    output::{OutputWriter, Row},
+};
+
+/// Replays every recorded frame of a cycler and writes the requested outputs after each frame.
+///
+/// Returns the number of frames in which a node failed. Failing frames are reported and skipped
+/// so that one broken frame does not hide the results of the remaining recording.
+pub fn replay_cycler<W, D>(
+    replayer: &mut Replayer<BatchReplayerHardwareInterface>,
+    recording_name: &str,
+    cycler_name: &str,
+    paths: &[String],
+    mut receiver: Receiver<(SystemTime, D)>,
+    output_writer: &mut OutputWriter<W>,
+) -> Result<usize>
+where
+    W: Write,
+    D: PathSerialize,
+{
+    let unknown_indices_error_message =
+        format!("could not find recording indices for `{cycler_name}`");
+
+    let timings: Vec<_> = replayer
+        .get_recording_indices()
+        .get(cycler_name)
+        .wrap_err_with(|| unknown_indices_error_message.clone())?
+        .iter()
+        .collect();
+
+    let mut number_of_failed_frames = 0;
+    for timing in timings {
+        let frame = replayer
+            .get_recording_indices_mut()
+            .get_mut(cycler_name)
+            .wrap_err_with(|| unknown_indices_error_message.clone())?
+            .find_latest_frame_up_to(timing.timestamp)
+            .wrap_err("failed to find latest frame")?;
+        let Some(frame) = frame else {
+            continue;
+        };
+
+        if let Err(error) = replayer.replay(cycler_name, frame.timing.timestamp, &frame.data) {
+            eprintln!(
+                "{recording_name}: {cycler_name} at {:?}: {error:#}",
+                frame.timing.timestamp
+            );
+            number_of_failed_frames += 1;
+            continue;
+        }
+
+        let (_, database) = &*receiver.borrow_and_mark_as_seen();
+        let values = paths
+            .iter()
+            .map(|path| {
+                let path_in_database = path
+                    .strip_prefix(cycler_name)
+                    .and_then(|path| path.strip_prefix('.'))
+                    .unwrap_or(path);
+                let value = database
+                    .serialize_path(path_in_database, serde_json::value::Serializer)
+                    .ok();
+                (path.as_str(), value)
+            })
+            .collect();
+        output_writer.write_row(Row {
+            recording: recording_name,
+            cycler: cycler_name,
+            timestamp: frame.timing.timestamp,
+            values,
+        })?;
+    }
+
+    Ok(number_of_failed_frames)
+}
@@ -10,6 +10,7 @@ use types::{
     joints::Joints,
     led::Leds,
     messages::{IncomingMessage, OutgoingMessage},
+    recording_event::RecordingEvent,
 };
 
 pub trait HardwareInterface:
@@ -50,6 +51,12 @@ impl RecordingInterface for ExtractorHardwareInterface {
     }
 
     fn set_whether_to_record(&self, _enable: bool) {}
+
+    fn trigger_recording_event(&self, _event: RecordingEvent) {}
+
+    fn take_recording_events(&self) -> Vec<RecordingEvent> {
+        Vec::new()
+    }
 }
 
 /// imagine does not produce speaker outputs
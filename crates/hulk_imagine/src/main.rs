@@ -48,6 +48,7 @@ struct CommandlineArguments {
 }
 
 fn main() -> Result<()> {
+    env_logger::init();
     install()?;
 
     let arguments = CommandlineArguments::parse();
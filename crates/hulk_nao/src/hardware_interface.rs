@@ -1,4 +1,5 @@
 use std::{
+    mem::take,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -31,6 +32,7 @@ use types::{
     joints::Joints,
     led::Leds,
     messages::{IncomingMessage, OutgoingMessage},
+    recording_event::RecordingEvent,
     samples::Samples,
     sensor_data::SensorData,
     ycbcr422_image::YCbCr422Image,
@@ -63,6 +65,7 @@ pub struct HardwareInterface {
     camera_top: Camera,
     camera_bottom: Camera,
     enable_recording: AtomicBool,
+    recording_events: Mutex<Vec<RecordingEvent>>,
     keep_running: CancellationToken,
 }
 
@@ -104,6 +107,7 @@ impl HardwareInterface {
             .wrap_err("failed to initialize bottom camera")?,
 
             enable_recording: AtomicBool::new(false),
+            recording_events: Default::default(),
             keep_running,
         })
     }
@@ -177,6 +181,14 @@ impl RecordingInterface for HardwareInterface {
     fn set_whether_to_record(&self, enable: bool) {
         self.enable_recording.store(enable, Ordering::SeqCst)
     }
+
+    fn trigger_recording_event(&self, event: RecordingEvent) {
+        self.recording_events.lock().push(event);
+    }
+
+    fn take_recording_events(&self) -> Vec<RecordingEvent> {
+        take(&mut *self.recording_events.lock())
+    }
 }
 
 impl SensorInterface for HardwareInterface {
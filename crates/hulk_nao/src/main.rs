@@ -91,5 +91,7 @@ fn main() -> Result<()> {
         ids,
         keep_running,
         framework_parameters.recording_intervals,
+        framework_parameters.recording_compression,
+        framework_parameters.event_recording,
     )
 }
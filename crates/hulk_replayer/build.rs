@@ -4,12 +4,12 @@ use hulk_manifest::collect_hulk_cyclers;
 use source_analyzer::{pretty::to_string_pretty, structs::Structs};
 
 fn main() -> Result<()> {
-    #[allow(unused_mut)] // must not be mut if "with_detection" feature is disabled
-    let mut cyclers = collect_hulk_cyclers("..")?;
-    #[cfg(not(feature = "with_object_detection"))]
-    cyclers
-        .cyclers
-        .retain(|cycler| cycler.name != "ObjectDetection");
+    let variant = if cfg!(feature = "with_object_detection") {
+        "replayer_with_object_detection"
+    } else {
+        "replayer"
+    };
+    let cyclers = collect_hulk_cyclers("..", Some(variant))?;
     for path in cyclers.watch_paths() {
         println!("cargo:rerun-if-changed={}", path.display());
     }
@@ -23,6 +23,7 @@ use types::{
     joints::Joints,
     led::Leds,
     messages::{IncomingMessage, OutgoingMessage},
+    recording_event::RecordingEvent,
     samples::Samples,
     sensor_data::SensorData,
     ycbcr422_image::YCbCr422Image,
@@ -102,6 +103,12 @@ impl RecordingInterface for ReplayerHardwareInterface {
     }
 
     fn set_whether_to_record(&self, _enable: bool) {}
+
+    fn trigger_recording_event(&self, _event: RecordingEvent) {}
+
+    fn take_recording_events(&self) -> Vec<RecordingEvent> {
+        Vec::new()
+    }
 }
 
 impl SensorInterface for ReplayerHardwareInterface {
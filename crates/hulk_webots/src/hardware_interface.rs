@@ -1,4 +1,5 @@
 use std::{
+    mem::take,
     str::from_utf8,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -11,6 +12,7 @@ use color_eyre::{
     eyre::{bail, eyre, Error, WrapErr},
     Result,
 };
+use parking_lot::Mutex;
 use serde::Deserialize;
 use tokio_util::sync::CancellationToken;
 use webots::Robot;
@@ -31,6 +33,7 @@ use types::{
     joints::Joints,
     led::Leds,
     messages::{IncomingMessage, OutgoingMessage},
+    recording_event::RecordingEvent,
     samples::Samples,
     sensor_data::SensorData,
     ycbcr422_image::YCbCr422Image,
@@ -67,6 +70,7 @@ pub struct HardwareInterface {
     spl_network_endpoint: Endpoint,
     async_runtime: Runtime,
     enable_recording: AtomicBool,
+    recording_events: Mutex<Vec<RecordingEvent>>,
     keep_running: CancellationToken,
     simulator_audio_synchronization: Barrier,
 }
@@ -96,6 +100,7 @@ impl HardwareInterface {
                 .wrap_err("failed to initialize SPL network")?,
             async_runtime: runtime,
             enable_recording: AtomicBool::new(false),
+            recording_events: Default::default(),
             keep_running,
             simulator_audio_synchronization: Barrier::new(2),
         })
@@ -359,6 +364,14 @@ impl RecordingInterface for HardwareInterface {
     fn set_whether_to_record(&self, enable: bool) {
         self.enable_recording.store(enable, Ordering::SeqCst)
     }
+
+    fn trigger_recording_event(&self, event: RecordingEvent) {
+        self.recording_events.lock().push(event);
+    }
+
+    fn take_recording_events(&self) -> Vec<RecordingEvent> {
+        take(&mut *self.recording_events.lock())
+    }
 }
 
 impl SensorInterface for HardwareInterface {
@@ -401,6 +414,7 @@ impl SensorInterface for HardwareInterface {
             touch_sensors,
             temperature_sensors,
             currents,
+            battery: None,
         })
     }
 }
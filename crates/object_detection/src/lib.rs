@@ -1,3 +1,4 @@
 pub mod pose_detection;
 pub mod pose_filter;
 pub mod pose_interpretation;
+pub mod robot_detection;
@@ -0,0 +1,450 @@
+use std::time::{Duration, SystemTime};
+
+use color_eyre::{
+    eyre::{bail, eyre, Context, ContextCompat},
+    Result,
+};
+use itertools::Itertools;
+use ndarray::ArrayView2;
+use openvino::{
+    CompiledModel, Core, DeviceType, ElementType, InferenceError::GeneralError, Tensor,
+};
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use coordinate_systems::Pixel;
+use framework::{deserialize_not_implemented, AdditionalOutput, MainOutput};
+use geometry::rectangle::Rectangle;
+use hardware::PathsInterface;
+use linear_algebra::{point, vector};
+use projection::{camera_matrix::CameraMatrix, Projection};
+use spl_network_messages::{TeamColor, TeamState};
+use types::{
+    bounding_box::BoundingBox, color::Rgb, detected_robots::DetectedRobot,
+    game_controller_state::GameControllerState, ycbcr422_image::YCbCr422Image,
+};
+
+const EXPECTED_OUTPUT_NAME: &str = "detections";
+const NUMBER_OF_VALUES_PER_DETECTION: usize = 5;
+
+const JERSEY_COLORS: [(TeamColor, Rgb); 10] = [
+    (TeamColor::Blue, Rgb::new(0, 60, 200)),
+    (TeamColor::Red, Rgb::new(200, 20, 20)),
+    (TeamColor::Yellow, Rgb::new(230, 210, 0)),
+    (TeamColor::Black, Rgb::new(20, 20, 20)),
+    (TeamColor::White, Rgb::new(235, 235, 235)),
+    (TeamColor::Green, Rgb::new(0, 150, 40)),
+    (TeamColor::Orange, Rgb::new(240, 110, 0)),
+    (TeamColor::Purple, Rgb::new(120, 30, 160)),
+    (TeamColor::Brown, Rgb::new(110, 70, 30)),
+    (TeamColor::Gray, Rgb::new(128, 128, 128)),
+];
+
+#[derive(Deserialize, Serialize)]
+pub struct RobotDetection {
+    #[serde(skip, default = "deserialize_not_implemented")]
+    network: Option<CompiledModel>,
+}
+
+#[context]
+pub struct CreationContext {
+    hardware_interface: HardwareInterface,
+
+    enable: Parameter<bool, "robot_detection.enable">,
+}
+
+#[context]
+pub struct CycleContext {
+    robot_detection_inference_duration:
+        AdditionalOutput<Duration, "robot_detection_inference_duration">,
+
+    image: Input<YCbCr422Image, "image">,
+    camera_matrix: Input<Option<CameraMatrix>, "camera_matrix?">,
+    game_controller_state: Input<Option<GameControllerState>, "Control", "game_controller_state?">,
+
+    enable: Parameter<bool, "robot_detection.enable">,
+    maximum_intersection_over_union:
+        Parameter<f32, "robot_detection.maximum_intersection_over_union">,
+    maximum_jersey_color_distance: Parameter<f32, "robot_detection.maximum_jersey_color_distance">,
+    minimum_bounding_box_confidence:
+        Parameter<f32, "robot_detection.minimum_bounding_box_confidence">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub detected_robots: MainOutput<Vec<DetectedRobot>>,
+}
+
+impl RobotDetection {
+    pub fn new(context: CreationContext<impl PathsInterface>) -> Result<Self> {
+        // The network is only loaded when enabled at startup, robots without the model keep running
+        let network = if *context.enable {
+            let paths = context.hardware_interface.get_paths();
+            Some(load_network(
+                &paths.neural_networks.join("robot-detection-ov.xml"),
+            )?)
+        } else {
+            None
+        };
+
+        Ok(Self { network })
+    }
+
+    pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        let Some(network) = self.network.as_mut().filter(|_| *context.enable) else {
+            return Ok(MainOutputs::default());
+        };
+
+        let earlier = SystemTime::now();
+        let candidates = detect(
+            network,
+            context.image,
+            *context.minimum_bounding_box_confidence,
+        )?;
+        context
+            .robot_detection_inference_duration
+            .fill_if_subscribed(|| {
+                SystemTime::now()
+                    .duration_since(earlier)
+                    .expect("time ran backwards")
+            });
+
+        let detected_robots: Vec<_> =
+            non_maximum_suppression(candidates, *context.maximum_intersection_over_union)
+                .into_iter()
+                .map(|bounding_box| {
+                    let jersey_color = classify_jersey_color(
+                        context.image,
+                        &bounding_box,
+                        *context.maximum_jersey_color_distance,
+                    );
+                    let is_teammate = jersey_color.as_ref().and_then(|jersey_color| {
+                        let game_controller_state = context.game_controller_state?;
+                        is_wearing_team_colors(&game_controller_state.hulks_team, jersey_color)
+                            .then_some(true)
+                            .or(is_wearing_team_colors(
+                                &game_controller_state.opponent_team,
+                                jersey_color,
+                            )
+                            .then_some(false))
+                    });
+                    let ground_position = context.camera_matrix.and_then(|camera_matrix| {
+                        let area = bounding_box.area;
+                        let foot_point = point![(area.min.x() + area.max.x()) / 2.0, area.max.y()];
+                        camera_matrix.pixel_to_ground(foot_point).ok()
+                    });
+                    DetectedRobot {
+                        bounding_box,
+                        jersey_color,
+                        is_teammate,
+                        ground_position,
+                    }
+                })
+                .collect();
+
+        Ok(MainOutputs {
+            detected_robots: detected_robots.into(),
+        })
+    }
+}
+
+fn load_network(model_path: &std::path::Path) -> Result<CompiledModel> {
+    let weights_path = model_path.with_extension("bin");
+
+    let mut core = Core::new()?;
+    let network = core
+        .read_model_from_file(
+            model_path
+                .to_str()
+                .wrap_err("failed to get detection model path")?,
+            weights_path
+                .to_str()
+                .wrap_err("failed to get detection weights path")?,
+        )
+        .map_err(|error| match error {
+            GeneralError => eyre!("{error}: possible incomplete OpenVino installation"),
+            _ => eyre!("{error}: failed to create detection network"),
+        })?;
+
+    let number_of_inputs = network
+        .get_inputs_len()
+        .wrap_err("failed to get number of inputs")?;
+    let output_name = network.get_output_by_index(0)?.get_name()?;
+    if number_of_inputs != 1 || output_name != EXPECTED_OUTPUT_NAME {
+        bail!(
+            "expected exactly one input and output name to be '{}'",
+            EXPECTED_OUTPUT_NAME
+        );
+    }
+
+    Ok(core.compile_model(&network, DeviceType::CPU)?)
+}
+
+fn detect(
+    network: &mut CompiledModel,
+    image: &YCbCr422Image,
+    minimum_bounding_box_confidence: f32,
+) -> Result<Vec<BoundingBox>> {
+    let input_shape = network.get_input()?.get_shape()?;
+    let &[_batch, _channels, input_height, input_width] = input_shape.get_dimensions() else {
+        bail!("expected input of shape [batch, channels, height, width]");
+    };
+    let (input_width, input_height) = (input_width as usize, input_height as usize);
+
+    let mut tensor = Tensor::new(ElementType::F32, &input_shape)?;
+    load_into_scratchpad(tensor.get_data_mut()?, image, input_width, input_height);
+
+    let mut infer_request = network.create_infer_request()?;
+    infer_request.set_input_tensor(&tensor)?;
+    infer_request.infer()?;
+
+    let prediction = infer_request.get_output_tensor_by_index(0)?;
+    let prediction = prediction.get_data::<f32>()?;
+    let number_of_detections = prediction.len() / NUMBER_OF_VALUES_PER_DETECTION;
+    let prediction = ArrayView2::from_shape(
+        (NUMBER_OF_VALUES_PER_DETECTION, number_of_detections),
+        prediction,
+    )?;
+
+    let scale_x = image.width() as f32 / input_width as f32;
+    let scale_y = image.height() as f32 / input_height as f32;
+    Ok(decode_detections(
+        prediction,
+        scale_x,
+        scale_y,
+        minimum_bounding_box_confidence,
+    ))
+}
+
+fn load_into_scratchpad(
+    scratchpad: &mut [f32],
+    image: &YCbCr422Image,
+    input_width: usize,
+    input_height: usize,
+) {
+    let stride = input_width * input_height;
+    let mut scratchpad_index = 0;
+    for y in 0..input_height {
+        let image_y = (y * image.height() as usize / input_height) as u32;
+        for x in 0..input_width {
+            let image_x = (x * image.width() as usize / input_width) as u32;
+            let pixel: Rgb = image.at(image_x, image_y).into();
+
+            scratchpad[scratchpad_index] = pixel.red as f32 / 255.;
+            scratchpad[scratchpad_index + stride] = pixel.green as f32 / 255.;
+            scratchpad[scratchpad_index + 2 * stride] = pixel.blue as f32 / 255.;
+
+            scratchpad_index += 1;
+        }
+    }
+}
+
+fn decode_detections(
+    prediction: ArrayView2<f32>,
+    scale_x: f32,
+    scale_y: f32,
+    minimum_bounding_box_confidence: f32,
+) -> Vec<BoundingBox> {
+    prediction
+        .columns()
+        .into_iter()
+        .filter_map(|column| {
+            let confidence = column[4];
+            if confidence < minimum_bounding_box_confidence {
+                return None;
+            }
+            let center = point![column[0] * scale_x, column[1] * scale_y];
+            let size = vector![column[2] * scale_x, column[3] * scale_y];
+            Some(BoundingBox {
+                area: Rectangle::<Pixel>::new_with_center_and_size(center, size),
+                confidence,
+            })
+        })
+        .collect()
+}
+
+fn non_maximum_suppression(
+    mut candidates: Vec<BoundingBox>,
+    maximum_intersection_over_union: f32,
+) -> Vec<BoundingBox> {
+    let mut detections = Vec::new();
+    candidates.sort_unstable_by(|left, right| left.confidence.total_cmp(&right.confidence));
+
+    while let Some(detection) = candidates.pop() {
+        candidates = candidates
+            .into_iter()
+            .filter(|candidate| {
+                detection.intersection_over_union(candidate) < maximum_intersection_over_union
+            })
+            .collect_vec();
+
+        detections.push(detection)
+    }
+
+    detections
+}
+
+fn classify_jersey_color(
+    image: &YCbCr422Image,
+    bounding_box: &BoundingBox,
+    maximum_jersey_color_distance: f32,
+) -> Option<TeamColor> {
+    // The jersey covers the upper torso, below the head and between the arms
+    let area = bounding_box.area;
+    let size = area.max - area.min;
+    let left = (area.min.x() + 0.3 * size.x()).max(0.0) as u32;
+    let right = (area.max.x() - 0.3 * size.x()).max(0.0) as u32;
+    let top = (area.min.y() + 0.2 * size.y()).max(0.0) as u32;
+    let bottom = (area.min.y() + 0.5 * size.y()).max(0.0) as u32;
+
+    let samples = (top..bottom)
+        .step_by(2)
+        .cartesian_product((left..right).step_by(2))
+        .filter_map(|(y, x)| image.try_at(x, y))
+        .map(Rgb::from)
+        .collect_vec();
+    if samples.is_empty() {
+        return None;
+    }
+    let number_of_samples = samples.len() as f32;
+    let (red, green, blue) = samples.iter().fold((0.0, 0.0, 0.0), |sum, pixel| {
+        (
+            sum.0 + pixel.red as f32,
+            sum.1 + pixel.green as f32,
+            sum.2 + pixel.blue as f32,
+        )
+    });
+    let mean = [
+        red / number_of_samples,
+        green / number_of_samples,
+        blue / number_of_samples,
+    ];
+
+    JERSEY_COLORS
+        .iter()
+        .map(|(team_color, reference)| {
+            let distance = ((mean[0] - reference.red as f32).powi(2)
+                + (mean[1] - reference.green as f32).powi(2)
+                + (mean[2] - reference.blue as f32).powi(2))
+            .sqrt();
+            (team_color, distance)
+        })
+        .min_by(|(_, left), (_, right)| left.total_cmp(right))
+        .filter(|(_, distance)| *distance <= maximum_jersey_color_distance)
+        .map(|(team_color, _)| team_color.clone())
+}
+
+fn is_wearing_team_colors(team: &TeamState, jersey_color: &TeamColor) -> bool {
+    [&team.field_player_color, &team.goal_keeper_color]
+        .into_iter()
+        .any(|team_color| team_color == jersey_color)
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Array2;
+    use types::color::YCbCr444;
+
+    use super::*;
+
+    fn image_with_jersey(jersey: Rgb) -> YCbCr422Image {
+        let (width, height) = (64, 64);
+        let background = YCbCr444::from(Rgb::new(0, 120, 0));
+        let jersey = YCbCr444::from(jersey);
+        let buffer = (0..height)
+            .flat_map(|y| {
+                (0..width / 2).map(move |x| {
+                    let in_torso = (20..44).contains(&(x * 2)) && (16..32).contains(&y);
+                    let pixel = if in_torso { jersey } else { background };
+                    [pixel, pixel].into()
+                })
+            })
+            .collect();
+        YCbCr422Image::from_ycbcr_buffer(width / 2, height, buffer)
+    }
+
+    #[test]
+    fn detections_below_confidence_are_dropped_and_rescaled() {
+        let prediction = Array2::from_shape_vec(
+            (NUMBER_OF_VALUES_PER_DETECTION, 2),
+            vec![10.0, 20.0, 10.0, 20.0, 4.0, 4.0, 8.0, 8.0, 0.9, 0.1],
+        )
+        .unwrap();
+
+        let detections = decode_detections(prediction.view(), 2.0, 3.0, 0.5);
+
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].area.min, point![16.0, 18.0]);
+        assert_eq!(detections[0].area.max, point![24.0, 42.0]);
+    }
+
+    #[test]
+    fn overlapping_detections_are_suppressed() {
+        let area =
+            Rectangle::<Pixel>::new_with_center_and_size(point![10.0, 10.0], vector![4.0, 8.0]);
+        let candidates = vec![
+            BoundingBox {
+                area,
+                confidence: 0.6,
+            },
+            BoundingBox {
+                area,
+                confidence: 0.9,
+            },
+        ];
+
+        let detections = non_maximum_suppression(candidates, 0.5);
+
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].confidence, 0.9);
+    }
+
+    #[test]
+    fn jersey_color_is_classified_from_torso() {
+        let image = image_with_jersey(Rgb::new(10, 60, 210));
+        let bounding_box = BoundingBox {
+            area: Rectangle {
+                min: point![0.0, 0.0],
+                max: point![64.0, 64.0],
+            },
+            confidence: 1.0,
+        };
+
+        assert_eq!(
+            classify_jersey_color(&image, &bounding_box, 60.0),
+            Some(TeamColor::Blue)
+        );
+        assert_eq!(
+            classify_jersey_color(&image_with_jersey(Rgb::PINK), &bounding_box, 60.0),
+            None
+        );
+    }
+
+    /// Runs inference on the CPU with a fixture network which reports a fixed detection over the
+    /// torso and a weaker overlapping one, exercising preprocessing, decoding and suppression
+    #[test]
+    fn fixture_network_detects_robot_on_cpu() -> Result<()> {
+        let model_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/robot-detection-fixture-ov.xml");
+        let image = image_with_jersey(Rgb::new(10, 60, 210));
+
+        let mut network = load_network(&model_path)?;
+        let detections = non_maximum_suppression(detect(&mut network, &image, 0.5)?, 0.45);
+
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].confidence, 0.9);
+        assert_eq!(
+            detections[0].area,
+            Rectangle {
+                min: point![20.0, 16.0],
+                max: point![44.0, 32.0],
+            }
+        );
+        assert_eq!(
+            classify_jersey_color(&image, &detections[0], 60.0),
+            Some(TeamColor::Blue)
+        );
+        Ok(())
+    }
+}
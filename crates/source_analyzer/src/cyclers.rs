@@ -10,14 +10,17 @@ use toposort_scc::IndexGraph;
 use crate::{
     contexts::Field,
     error::Error,
-    manifest::{CyclerManifest, FrameworkManifest},
-    node::Node,
+    manifest::{CyclerManifest, FrameworkManifest, SupervisionPolicy},
+    node::{Node, NodeName},
 };
 
 pub type CyclerName = String;
 pub type InstanceName = String;
 pub type OutputName = String;
 
+/// Main output of every cycler listing the supervised nodes which failed in the current cycle
+pub const DEGRADED_NODES_OUTPUT: &str = "degraded_nodes";
+
 #[derive(Debug)]
 pub struct Cyclers {
     pub cyclers: Vec<Cycler>,
@@ -92,6 +95,8 @@ pub struct Cycler {
     pub setup_nodes: Vec<Node>,
     pub cycle_nodes: Vec<Node>,
     pub execution_time_warning_threshold: Option<Duration>,
+    pub parallel_node_threads: Option<usize>,
+    pub supervision_policies: BTreeMap<NodeName, SupervisionPolicy>,
 }
 
 impl Cycler {
@@ -111,14 +116,28 @@ impl Cycler {
             .iter()
             .map(|specification| Node::try_from_node_name(specification, root))
             .collect::<Result<Vec<_>, _>>()?;
+        let supervision_policies = cycler_manifest
+            .setup_nodes
+            .iter()
+            .zip(setup_nodes.iter())
+            .chain(cycler_manifest.nodes.iter().zip(cycle_nodes.iter()))
+            .map(|(specification, node)| {
+                (
+                    node.name.clone(),
+                    cycler_manifest.supervision_policy(specification),
+                )
+            })
+            .collect();
 
         let mut cycler = Self {
-            name: cycler_manifest.name.to_string(),
+            name: cycler_manifest.name,
             kind: cycler_manifest.kind,
             instances,
             setup_nodes,
             cycle_nodes,
             execution_time_warning_threshold: cycler_manifest.execution_time_warning_threshold,
+            parallel_node_threads: cycler_manifest.parallel_node_threads,
+            supervision_policies,
         };
         cycler.sort_nodes()?;
 
@@ -140,13 +159,18 @@ impl Cycler {
                     })
             })
             .collect();
+        let framework_output_names = BTreeSet::from([DEGRADED_NODES_OUTPUT.to_string()]);
         let sorted_setup_nodes = sort_nodes(
             &self.setup_nodes,
             &output_name_to_setup_node,
-            &BTreeSet::new(),
+            &framework_output_names,
         )?;
 
-        let setup_output_names = output_name_to_setup_node.keys().cloned().collect();
+        let setup_output_names = output_name_to_setup_node
+            .keys()
+            .cloned()
+            .chain(framework_output_names)
+            .collect();
         let output_to_node: BTreeMap<_, _> = self
             .cycle_nodes
             .iter()
@@ -172,6 +196,79 @@ impl Cycler {
     pub fn iter_nodes(&self) -> impl Iterator<Item = &Node> {
         self.setup_nodes.iter().chain(self.cycle_nodes.iter())
     }
+
+    /// Groups the sorted cycle nodes into stages which are executed one after another.
+    ///
+    /// Nodes of the same stage neither depend on outputs of each other nor share cycler state or
+    /// additional outputs and can therefore be executed concurrently.
+    pub fn cycle_node_stages(&self) -> Vec<Vec<&Node>> {
+        let mut stages: Vec<Vec<&Node>> = Vec::new();
+        let mut output_to_stage = BTreeMap::new();
+        let mut cycler_state_to_stage = BTreeMap::new();
+        let mut additional_output_to_stage = BTreeMap::new();
+        for node in &self.cycle_nodes {
+            let stage = node
+                .contexts
+                .cycle_context
+                .iter()
+                .filter_map(|field| match field {
+                    Field::HistoricInput { path, .. }
+                    | Field::Input {
+                        path,
+                        cycler_instance: None,
+                        ..
+                    }
+                    | Field::RequiredInput {
+                        path,
+                        cycler_instance: None,
+                        ..
+                    } => output_to_stage.get(&path.segments.first()?.name),
+                    Field::CyclerState { path, .. } => {
+                        cycler_state_to_stage.get(&path.segments.first()?.name)
+                    }
+                    Field::AdditionalOutput { path, .. } => {
+                        additional_output_to_stage.get(&path.segments.first()?.name)
+                    }
+                    _ => None,
+                })
+                .map(|stage| stage + 1)
+                .max()
+                .unwrap_or(0);
+            for field in &node.contexts.main_outputs {
+                if let Field::MainOutput { name, .. } = field {
+                    output_to_stage.insert(name.to_string(), stage);
+                }
+            }
+            for field in &node.contexts.cycle_context {
+                let (field_to_stage, path) = match field {
+                    Field::CyclerState { path, .. } => (&mut cycler_state_to_stage, path),
+                    Field::AdditionalOutput { path, .. } => (&mut additional_output_to_stage, path),
+                    _ => continue,
+                };
+                if let Some(segment) = path.segments.first() {
+                    field_to_stage.insert(segment.name.clone(), stage);
+                }
+            }
+            if stage == stages.len() {
+                stages.push(Vec::new());
+            }
+            stages[stage].push(node);
+        }
+        stages
+    }
+
+    pub fn supervision_policy(&self, node: &Node) -> SupervisionPolicy {
+        self.supervision_policies
+            .get(&node.name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Nodes whose errors are isolated instead of terminating the process
+    pub fn iter_supervised_nodes(&self) -> impl Iterator<Item = &Node> {
+        self.iter_nodes()
+            .filter(|node| self.supervision_policy(node) != SupervisionPolicy::Abort)
+    }
 }
 
 pub fn generate_dependency_graph(
@@ -246,3 +343,105 @@ fn sort_nodes(
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_file;
+
+    use crate::contexts::Contexts;
+
+    use super::*;
+
+    fn node(name: &str, source: &str) -> Node {
+        let file = parse_file(source).unwrap();
+        Node {
+            name: name.to_string(),
+            module: syn::parse_str(&format!("control::{}", name.to_lowercase())).unwrap(),
+            file_path: format!("control/src/{}.rs", name.to_lowercase()).into(),
+            contexts: Contexts::try_from_file(&file).unwrap(),
+        }
+    }
+
+    fn cycler_with_nodes(cycle_nodes: Vec<Node>) -> Cycler {
+        Cycler {
+            name: "Control".to_string(),
+            kind: CyclerKind::RealTime,
+            instances: vec!["".to_string()],
+            setup_nodes: vec![],
+            cycle_nodes,
+            execution_time_warning_threshold: None,
+            parallel_node_threads: Some(2),
+            supervision_policies: BTreeMap::new(),
+        }
+    }
+
+    fn stage_names(cycler: &Cycler) -> Vec<Vec<&str>> {
+        cycler
+            .cycle_node_stages()
+            .into_iter()
+            .map(|stage| stage.into_iter().map(|node| node.name.as_str()).collect())
+            .collect()
+    }
+
+    fn producer(name: &str, output: &str) -> Node {
+        node(
+            name,
+            &format!(
+                "
+                use framework::MainOutput;
+                #[context] pub struct CreationContext {{}}
+                #[context] pub struct CycleContext {{}}
+                #[context] pub struct MainOutputs {{ pub {output}: MainOutput<f32> }}
+                "
+            ),
+        )
+    }
+
+    fn node_with_cycle_context(name: &str, cycle_context: &str) -> Node {
+        node(
+            name,
+            &format!(
+                "
+                use framework::{{AdditionalOutput, CyclerState, Input}};
+                #[context] pub struct CreationContext {{}}
+                #[context] pub struct CycleContext {{ {cycle_context} }}
+                #[context] pub struct MainOutputs {{}}
+                "
+            ),
+        )
+    }
+
+    #[test]
+    fn dependent_nodes_are_placed_in_later_stages() {
+        let cycler = cycler_with_nodes(vec![
+            producer("A", "a"),
+            producer("B", "b"),
+            node_with_cycle_context("C", r#"a: Input<f32, "a">"#),
+            node_with_cycle_context("D", r#"b: Input<f32, "b">"#),
+        ]);
+
+        assert_eq!(stage_names(&cycler), vec![vec!["A", "B"], vec!["C", "D"]]);
+    }
+
+    #[test]
+    fn nodes_sharing_cycler_state_are_placed_in_different_stages() {
+        let cycler = cycler_with_nodes(vec![
+            node_with_cycle_context("A", r#"state: CyclerState<f32, "state">"#),
+            node_with_cycle_context("B", r#"other: CyclerState<f32, "other">"#),
+            node_with_cycle_context("C", r#"state: CyclerState<f32, "state">"#),
+        ]);
+
+        assert_eq!(stage_names(&cycler), vec![vec!["A", "B"], vec!["C"]]);
+    }
+
+    #[test]
+    fn nodes_writing_the_same_additional_output_are_placed_in_different_stages() {
+        let cycler = cycler_with_nodes(vec![
+            node_with_cycle_context("A", r#"debug: AdditionalOutput<f32, "debug.a">"#),
+            node_with_cycle_context("B", r#"other: AdditionalOutput<f32, "other">"#),
+            node_with_cycle_context("C", r#"debug: AdditionalOutput<f32, "debug.c">"#),
+        ]);
+
+        assert_eq!(stage_names(&cycler), vec![vec!["A", "B"], vec!["C"]]);
+    }
+}
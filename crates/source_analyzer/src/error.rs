@@ -21,6 +21,33 @@ pub enum Error {
     InvalidModulePath,
     #[error("`{node}` requires output `{output}`, but it is never produced")]
     MissingOutput { node: String, output: String },
+    #[error("failed to parse manifest: {0}")]
+    ManifestParse(toml::de::Error),
+    #[error("unknown manifest variant `{variant}`, available variants: {}", .available.join(", "))]
+    UnknownVariant {
+        variant: String,
+        available: Vec<String>,
+    },
+    #[error(
+        "manifest variant `{variant}` modifies cycler `{cycler}`, but the cycler does not exist"
+    )]
+    UnknownCyclerInVariant { cycler: String, variant: String },
+    #[error("manifest variant `{variant}` removes node `{node}` from cycler `{cycler}`, but the node is not part of the cycler")]
+    UnknownNodeInVariant {
+        node: String,
+        cycler: String,
+        variant: String,
+    },
+    #[error("cycler `{0}` is declared more than once")]
+    DuplicateCycler(String),
+    #[error("cycler `{0}` has no instances")]
+    NoInstances(String),
+    #[error("node `{node}` is listed more than once in cycler `{cycler}`")]
+    DuplicateNode { node: String, cycler: String },
+    #[error(
+        "supervision policy for `{node}` in `{cycler}`, but the node is not part of the cycler"
+    )]
+    UnknownSupervisedNode { node: String, cycler: String },
     #[error("failed to sort nodes, circular dependencies detected: {}", .0.first().map(|cycle| cycle.join(", ")).unwrap_or("failed to determine loop".to_string()))]
     CircularDependency(Vec<Vec<String>>),
 }
@@ -1,20 +1,344 @@
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
-use serde::Deserialize;
+use serde::{de::Error as _, Deserialize, Deserializer};
 
-use crate::cyclers::CyclerKind;
+use crate::{cyclers::CyclerKind, error::Error};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FrameworkManifest {
     pub cyclers: Vec<CyclerManifest>,
+    /// Modifications of the cyclers for individual targets, e.g. the replayer
+    #[serde(default)]
+    pub variants: BTreeMap<String, VariantManifest>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CyclerManifest {
-    pub name: &'static str,
+    pub name: String,
     pub kind: CyclerKind,
-    pub instances: Vec<&'static str>,
-    pub setup_nodes: Vec<&'static str>,
-    pub nodes: Vec<&'static str>,
+    pub instances: Vec<String>,
+    pub setup_nodes: Vec<String>,
+    pub nodes: Vec<String>,
+    /// Given in fractional seconds, e.g. `0.033` for 33 ms
+    #[serde(default, deserialize_with = "deserialize_optional_seconds")]
     pub execution_time_warning_threshold: Option<Duration>,
+    /// Number of threads executing independent nodes concurrently, nodes run sequentially if `None`
+    pub parallel_node_threads: Option<usize>,
+    /// Policy of all nodes of this cycler which are not listed in `supervision_policies`
+    #[serde(default)]
+    pub default_supervision_policy: SupervisionPolicy,
+    #[serde(default)]
+    pub supervision_policies: BTreeMap<String, SupervisionPolicy>,
+}
+
+impl CyclerManifest {
+    /// Policy of the node given by its specification, e.g. `control::led_status`
+    pub fn supervision_policy(&self, node: &str) -> SupervisionPolicy {
+        self.supervision_policies
+            .get(node)
+            .copied()
+            .unwrap_or(self.default_supervision_policy)
+    }
+}
+
+/// How the cycler reacts when the `cycle` of a node returns an error
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq)]
+pub enum SupervisionPolicy {
+    /// Stop all cyclers and terminate the process
+    #[default]
+    Abort,
+    /// Skip the node and keep the main outputs of its last successful cycle
+    ReuseLastOutputs,
+    /// Skip the node and write default main outputs, like for missing required inputs
+    UseDefaults,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VariantManifest {
+    #[serde(default)]
+    pub remove_cyclers: Vec<String>,
+    #[serde(default)]
+    pub cyclers: BTreeMap<String, CyclerVariantManifest>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CyclerVariantManifest {
+    #[serde(default)]
+    pub remove_setup_nodes: Vec<String>,
+    #[serde(default)]
+    pub add_setup_nodes: Vec<String>,
+    #[serde(default)]
+    pub remove_nodes: Vec<String>,
+    #[serde(default)]
+    pub add_nodes: Vec<String>,
+    /// Added to or replacing the supervision policies of the cycler
+    #[serde(default)]
+    pub supervision_policies: BTreeMap<String, SupervisionPolicy>,
+}
+
+impl FrameworkManifest {
+    /// Parses a TOML manifest and applies the modifications of `variant` to its cyclers
+    pub fn try_from_toml(toml: &str, variant: Option<&str>) -> Result<Self, Error> {
+        let mut manifest: Self = toml::from_str(toml).map_err(Error::ManifestParse)?;
+        if let Some(variant) = variant {
+            manifest.apply_variant(variant)?;
+        }
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    fn apply_variant(&mut self, variant_name: &str) -> Result<(), Error> {
+        let variant = self
+            .variants
+            .remove(variant_name)
+            .ok_or_else(|| Error::UnknownVariant {
+                variant: variant_name.to_string(),
+                available: self.variants.keys().cloned().collect(),
+            })?;
+        let unknown_cycler = |cycler: &String| Error::UnknownCyclerInVariant {
+            cycler: cycler.clone(),
+            variant: variant_name.to_string(),
+        };
+
+        for cycler_name in &variant.remove_cyclers {
+            let index = self
+                .cyclers
+                .iter()
+                .position(|cycler| &cycler.name == cycler_name)
+                .ok_or_else(|| unknown_cycler(cycler_name))?;
+            self.cyclers.remove(index);
+        }
+        for (cycler_name, cycler_variant) in variant.cyclers {
+            let cycler = self
+                .cyclers
+                .iter_mut()
+                .find(|cycler| cycler.name == cycler_name)
+                .ok_or_else(|| unknown_cycler(&cycler_name))?;
+            let unknown_node = |node: &String| Error::UnknownNodeInVariant {
+                node: node.clone(),
+                cycler: cycler_name.clone(),
+                variant: variant_name.to_string(),
+            };
+            for node in &cycler_variant.remove_setup_nodes {
+                remove_node(&mut cycler.setup_nodes, node).ok_or_else(|| unknown_node(node))?;
+                cycler.supervision_policies.remove(node);
+            }
+            for node in &cycler_variant.remove_nodes {
+                remove_node(&mut cycler.nodes, node).ok_or_else(|| unknown_node(node))?;
+                cycler.supervision_policies.remove(node);
+            }
+            cycler.setup_nodes.extend(cycler_variant.add_setup_nodes);
+            cycler.nodes.extend(cycler_variant.add_nodes);
+            cycler
+                .supervision_policies
+                .extend(cycler_variant.supervision_policies);
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        for (index, cycler) in self.cyclers.iter().enumerate() {
+            if self.cyclers[..index]
+                .iter()
+                .any(|other| other.name == cycler.name)
+            {
+                return Err(Error::DuplicateCycler(cycler.name.clone()));
+            }
+            if cycler.instances.is_empty() {
+                return Err(Error::NoInstances(cycler.name.clone()));
+            }
+            let nodes: Vec<_> = cycler.setup_nodes.iter().chain(&cycler.nodes).collect();
+            for (index, node) in nodes.iter().enumerate() {
+                if nodes[..index].contains(node) {
+                    return Err(Error::DuplicateNode {
+                        node: node.to_string(),
+                        cycler: cycler.name.clone(),
+                    });
+                }
+            }
+            if let Some(unknown_node) = cycler
+                .supervision_policies
+                .keys()
+                .find(|supervised_node| !nodes.contains(supervised_node))
+            {
+                return Err(Error::UnknownSupervisedNode {
+                    node: unknown_node.clone(),
+                    cycler: cycler.name.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn deserialize_optional_seconds<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let seconds = f64::deserialize(deserializer)?;
+    Duration::try_from_secs_f64(seconds)
+        .map(Some)
+        .map_err(D::Error::custom)
+}
+
+fn remove_node(nodes: &mut Vec<String>, node: &str) -> Option<()> {
+    let index = nodes.iter().position(|candidate| candidate == node)?;
+    nodes.remove(index);
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+        [[cyclers]]
+        name = "Control"
+        kind = "RealTime"
+        instances = [""]
+        setup_nodes = ["control::sensor_data_receiver"]
+        nodes = ["control::behavior::node", "control::led_status"]
+        execution_time_warning_threshold = 0.25
+        supervision_policies = { "control::led_status" = "UseDefaults" }
+
+        [[cyclers]]
+        name = "Audio"
+        kind = "Perception"
+        instances = [""]
+        setup_nodes = ["audio::microphone_recorder"]
+        nodes = ["audio::whistle_detection"]
+
+        [variants.simulator]
+        remove_cyclers = ["Audio"]
+
+        [variants.simulator.cyclers.Control]
+        remove_setup_nodes = ["control::sensor_data_receiver"]
+        add_setup_nodes = ["crate::fake_data"]
+        remove_nodes = ["control::led_status"]
+    "#;
+
+    #[test]
+    fn manifest_without_variant_contains_all_cyclers() {
+        let manifest = FrameworkManifest::try_from_toml(MANIFEST, None).unwrap();
+
+        assert_eq!(manifest.cyclers.len(), 2);
+        let control = &manifest.cyclers[0];
+        assert_eq!(control.kind, CyclerKind::RealTime);
+        assert_eq!(
+            control.execution_time_warning_threshold,
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(control.default_supervision_policy, SupervisionPolicy::Abort);
+        assert_eq!(
+            control.supervision_policies["control::led_status"],
+            SupervisionPolicy::UseDefaults
+        );
+        assert_eq!(manifest.cyclers[1].parallel_node_threads, None);
+    }
+
+    #[test]
+    fn unlisted_nodes_get_default_supervision_policy() {
+        let manifest = MANIFEST.replace(
+            r#"supervision_policies = { "control::led_status" = "UseDefaults" }"#,
+            r#"supervision_policies = { "control::led_status" = "UseDefaults" }
+        default_supervision_policy = "ReuseLastOutputs""#,
+        );
+        let manifest = FrameworkManifest::try_from_toml(&manifest, None).unwrap();
+
+        let control = &manifest.cyclers[0];
+        assert_eq!(
+            control.supervision_policy("control::led_status"),
+            SupervisionPolicy::UseDefaults
+        );
+        assert_eq!(
+            control.supervision_policy("control::behavior::node"),
+            SupervisionPolicy::ReuseLastOutputs
+        );
+        assert_eq!(
+            manifest.cyclers[1].supervision_policy("audio::whistle_detection"),
+            SupervisionPolicy::Abort
+        );
+    }
+
+    #[test]
+    fn variant_overrides_supervision_policies() {
+        let manifest = MANIFEST.replace(
+            r#"remove_nodes = ["control::led_status"]"#,
+            r#"supervision_policies = { "control::led_status" = "ReuseLastOutputs" }"#,
+        );
+        let manifest = FrameworkManifest::try_from_toml(&manifest, Some("simulator")).unwrap();
+
+        assert_eq!(
+            manifest.cyclers[0].supervision_policy("control::led_status"),
+            SupervisionPolicy::ReuseLastOutputs
+        );
+    }
+
+    #[test]
+    fn supervision_policy_of_unknown_node_is_rejected() {
+        let manifest = MANIFEST.replace(
+            r#"supervision_policies = { "control::led_status" = "UseDefaults" }"#,
+            r#"supervision_policies = { "control::localization" = "UseDefaults" }"#,
+        );
+
+        let error = FrameworkManifest::try_from_toml(&manifest, None).unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::UnknownSupervisedNode { node, cycler }
+                if node == "control::localization" && cycler == "Control"
+        ));
+    }
+
+    #[test]
+    fn variant_adds_and_removes_nodes() {
+        let manifest = FrameworkManifest::try_from_toml(MANIFEST, Some("simulator")).unwrap();
+
+        assert_eq!(manifest.cyclers.len(), 1);
+        let control = &manifest.cyclers[0];
+        assert_eq!(control.setup_nodes, ["crate::fake_data"]);
+        assert_eq!(control.nodes, ["control::behavior::node"]);
+        assert!(control.supervision_policies.is_empty());
+    }
+
+    #[test]
+    fn unknown_variant_is_rejected() {
+        let error = FrameworkManifest::try_from_toml(MANIFEST, Some("webots")).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "unknown manifest variant `webots`, available variants: simulator"
+        );
+    }
+
+    #[test]
+    fn removing_unknown_node_is_rejected() {
+        let manifest = MANIFEST.replace(
+            r#"remove_nodes = ["control::led_status"]"#,
+            r#"remove_nodes = ["control::localization"]"#,
+        );
+
+        let error = FrameworkManifest::try_from_toml(&manifest, Some("simulator")).unwrap_err();
+
+        assert!(matches!(error, Error::UnknownNodeInVariant { .. }));
+    }
+
+    #[test]
+    fn duplicate_nodes_are_rejected() {
+        let manifest = MANIFEST.replace(
+            r#"nodes = ["audio::whistle_detection"]"#,
+            r#"nodes = ["audio::whistle_detection", "audio::whistle_detection"]"#,
+        );
+
+        let error = FrameworkManifest::try_from_toml(&manifest, None).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "node `audio::whistle_detection` is listed more than once in cycler `Audio`"
+        );
+    }
 }
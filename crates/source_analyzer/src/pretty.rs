@@ -3,6 +3,7 @@ use std::fmt::{self, Write};
 use crate::{
     contexts::{Contexts, Field},
     cyclers::{Cycler, Cyclers},
+    manifest::SupervisionPolicy,
     node::Node,
 };
 
@@ -35,17 +36,30 @@ impl ToWriterPretty for Cycler {
         for setup_node in &self.setup_nodes {
             write!(writer, "  ")?;
             setup_node.to_writer_pretty(writer)?;
-            writeln!(writer, " (setup)")?;
+            write!(writer, " (setup)")?;
+            self.supervision_policy(setup_node)
+                .to_writer_pretty(writer)?;
+            writeln!(writer)?;
         }
         for node in &self.cycle_nodes {
             write!(writer, "  ")?;
             node.to_writer_pretty(writer)?;
+            self.supervision_policy(node).to_writer_pretty(writer)?;
             writeln!(writer)?;
         }
         Ok(())
     }
 }
 
+impl ToWriterPretty for SupervisionPolicy {
+    fn to_writer_pretty(&self, writer: &mut impl Write) -> fmt::Result {
+        match self {
+            SupervisionPolicy::Abort => Ok(()),
+            policy => write!(writer, " (supervised: {policy:?})"),
+        }
+    }
+}
+
 impl ToWriterPretty for Cyclers {
     fn to_writer_pretty(&self, writer: &mut impl Write) -> fmt::Result {
         for cycler in &self.cyclers {
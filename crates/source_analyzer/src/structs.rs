@@ -13,7 +13,7 @@ use thiserror::Error;
 
 use crate::{
     contexts::Field,
-    cyclers::{CyclerName, Cyclers},
+    cyclers::{CyclerName, Cyclers, DEGRADED_NODES_OUTPUT},
     path::Path,
     struct_hierarchy::{HierarchyError, InsertionRule, StructHierarchy},
 };
@@ -63,6 +63,41 @@ impl Structs {
                     })?;
             }
 
+            for node in cycler.iter_supervised_nodes() {
+                cycler_structs
+                    .supervised_nodes
+                    .insert([
+                        InsertionRule::InsertField {
+                            name: node.name.to_case(Case::Snake),
+                        },
+                        InsertionRule::AppendDataType {
+                            data_type: Type::Verbatim(
+                                quote! { types::node_supervision::NodeSupervisionState },
+                            ),
+                        },
+                    ])
+                    .map_err(|source| Error::Hierarchy {
+                        node: node.name.clone(),
+                        cycler: cycler.name.clone(),
+                        source,
+                    })?;
+            }
+            cycler_structs
+                .main_outputs
+                .insert([
+                    InsertionRule::InsertField {
+                        name: DEGRADED_NODES_OUTPUT.to_string(),
+                    },
+                    InsertionRule::AppendDataType {
+                        data_type: Type::Verbatim(quote! { std::vec::Vec<std::string::String> }),
+                    },
+                ])
+                .map_err(|source| Error::Hierarchy {
+                    node: DEGRADED_NODES_OUTPUT.to_string(),
+                    cycler: cycler.name.clone(),
+                    source,
+                })?;
+
             for node in cycler.iter_nodes() {
                 for field in node.contexts.main_outputs.iter() {
                     add_main_outputs(field, cycler_structs);
@@ -183,6 +218,7 @@ pub struct CyclerStructs {
     pub additional_outputs: StructHierarchy,
     pub cycler_state: StructHierarchy,
     pub cycle_times: StructHierarchy,
+    pub supervised_nodes: StructHierarchy,
 }
 
 fn path_to_insertion_rules<'path>(
@@ -5,6 +5,7 @@ use std::{
 
 use log::warn;
 use serde::Deserialize;
+use spl_network_messages::HulkMessage;
 use thiserror::Error;
 use tokio::{net::UdpSocket, select};
 use types::messages::{IncomingMessage, OutgoingMessage};
@@ -65,15 +66,11 @@ impl Endpoint {
                 },
                 result = self.spl_socket.recv_from(&mut spl_buffer) => {
                     let (received_bytes, _address) = result.map_err(Error::ReadError)?;
-                    match bincode::deserialize(&spl_buffer[0..received_bytes]) {
-                        Ok(parsed_message) => {
-                            break Ok(IncomingMessage::Spl(parsed_message));
-                        }
-                        Err(error) => {
-                            warn!("Failed to parse SPL message (will be discarded): {error:?}");
-                            continue;
-                        }
-                    }
+                    let message = match HulkMessage::try_from(&spl_buffer[0..received_bytes]) {
+                        Ok(parsed_message) => IncomingMessage::Spl(parsed_message),
+                        Err(rejection) => IncomingMessage::RejectedSpl(rejection),
+                    };
+                    break Ok(message);
                 }
             }
         }
@@ -86,7 +83,7 @@ impl Endpoint {
                 self.send_game_controller_visual_referee_message(destination, message)
                     .await;
             }
-            OutgoingMessage::Spl(message) => match bincode::serialize(&message) {
+            OutgoingMessage::Spl(message) => match Vec::<u8>::try_from(message) {
                 Ok(message) => {
                     if let Err(error) = self
                         .spl_socket
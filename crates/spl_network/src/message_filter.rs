@@ -1,8 +1,12 @@
 use color_eyre::{eyre::Ok, Result};
 use context_attribute::context;
+use coordinate_systems::Field;
 use framework::MainOutput;
+use linear_algebra::Point2;
 use serde::{Deserialize, Serialize};
-use spl_network_messages::{HulkMessage, PlayerNumber, StrikerMessage, VisualRefereeMessage};
+use spl_network_messages::{
+    HulkMessage, LoserMessage, ObstacleMessage, PlayerNumber, StrikerMessage, VisualRefereeMessage,
+};
 use types::messages::IncomingMessage;
 
 #[derive(Deserialize, Serialize)]
@@ -20,6 +24,7 @@ pub struct CycleContext {
 #[context]
 pub struct MainOutputs {
     pub filtered_message: MainOutput<Option<IncomingMessage>>,
+    pub teammate_ball_search_hotspot: MainOutput<Option<Point2<Field>>>,
 }
 
 impl MessageFilter {
@@ -36,12 +41,59 @@ impl MessageFilter {
                 message @ (HulkMessage::Striker(StrikerMessage { player_number, .. })
                 | HulkMessage::VisualReferee(VisualRefereeMessage {
                     player_number, ..
-                })),
+                })
+                | HulkMessage::Obstacles(ObstacleMessage { player_number, .. })),
             ) if player_number != context.player_number => Some(IncomingMessage::Spl(*message)),
             _ => None,
         };
+        // Loser messages are only forwarded for their hotspot, other nodes ignore them on purpose
+        let teammate_ball_search_hotspot = match context.message {
+            IncomingMessage::Spl(
+                message @ (HulkMessage::Loser(LoserMessage { player_number, .. })
+                | HulkMessage::Obstacles(ObstacleMessage { player_number, .. })),
+            ) if player_number != context.player_number => message
+                .ball_search_hotspot()
+                .map(|hotspot| hotspot.position()),
+            _ => None,
+        };
         Ok(MainOutputs {
             filtered_message: message.into(),
+            teammate_ball_search_hotspot: teammate_ball_search_hotspot.into(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use linear_algebra::point;
+    use spl_network_messages::BallSearchHotspot;
+
+    use super::*;
+
+    fn filter(message: HulkMessage, own_player_number: PlayerNumber) -> MainOutputs {
+        let message = IncomingMessage::Spl(message);
+        MessageFilter {}
+            .cycle(CycleContext::new(&message, &own_player_number))
+            .unwrap()
+    }
+
+    #[test]
+    fn only_hotspot_of_teammate_loser_messages_is_forwarded() {
+        let message = HulkMessage::Loser(LoserMessage {
+            player_number: PlayerNumber::Three,
+            ball_search_hotspot: Some(BallSearchHotspot::new(point![1.0, -2.0])),
+            ..Default::default()
+        });
+
+        let outputs = filter(message, PlayerNumber::Five);
+        assert!(outputs.filtered_message.value.is_none());
+        assert_eq!(
+            outputs.teammate_ball_search_hotspot.value,
+            Some(point![1.0, -2.0])
+        );
+
+        let outputs = filter(message, PlayerNumber::Three);
+        assert!(outputs.filtered_message.value.is_none());
+        assert!(outputs.teammate_ball_search_hotspot.value.is_none());
+    }
+}
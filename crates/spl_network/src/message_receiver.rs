@@ -1,12 +1,16 @@
 use color_eyre::{eyre::WrapErr, Result};
 use context_attribute::context;
-use framework::MainOutput;
+use framework::{AdditionalOutput, MainOutput};
 use hardware::NetworkInterface;
 use serde::{Deserialize, Serialize};
+use spl_network_messages::HulkMessageRejection;
 use types::messages::IncomingMessage;
 
 #[derive(Deserialize, Serialize)]
-pub struct MessageReceiver {}
+pub struct MessageReceiver {
+    number_of_rejected_spl_messages: usize,
+    last_spl_message_rejection: Option<HulkMessageRejection>,
+}
 
 #[context]
 pub struct CreationContext {}
@@ -14,6 +18,10 @@ pub struct CreationContext {}
 #[context]
 pub struct CycleContext {
     hardware_interface: HardwareInterface,
+
+    number_of_rejected_spl_messages: AdditionalOutput<usize, "number_of_rejected_spl_messages">,
+    last_spl_message_rejection:
+        AdditionalOutput<Option<HulkMessageRejection>, "last_spl_message_rejection">,
 }
 
 #[context]
@@ -23,15 +31,33 @@ pub struct MainOutputs {
 
 impl MessageReceiver {
     pub fn new(_context: CreationContext) -> Result<Self> {
-        Ok(Self {})
+        Ok(Self {
+            number_of_rejected_spl_messages: 0,
+            last_spl_message_rejection: None,
+        })
     }
 
-    pub fn cycle(&mut self, context: CycleContext<impl NetworkInterface>) -> Result<MainOutputs> {
+    pub fn cycle(
+        &mut self,
+        mut context: CycleContext<impl NetworkInterface>,
+    ) -> Result<MainOutputs> {
         let message = context
             .hardware_interface
             .read_from_network()
             .wrap_err("failed to read from network")?;
 
+        if let IncomingMessage::RejectedSpl(rejection) = message {
+            self.number_of_rejected_spl_messages += 1;
+            self.last_spl_message_rejection = Some(rejection);
+        }
+
+        context
+            .number_of_rejected_spl_messages
+            .fill_if_subscribed(|| self.number_of_rejected_spl_messages);
+        context
+            .last_spl_message_rejection
+            .fill_if_subscribed(|| self.last_spl_message_rejection);
+
         Ok(MainOutputs {
             message: message.into(),
         })
@@ -382,6 +382,7 @@ pub enum SubState {
     Clone,
     Copy,
     Debug,
+    Default,
     Deserialize,
     Eq,
     PartialEq,
@@ -391,6 +392,7 @@ pub enum SubState {
     PathIntrospect,
 )]
 pub enum Half {
+    #[default]
     First,
     Second,
 }
@@ -420,7 +422,17 @@ pub struct TeamState {
     pub players: Vec<Player>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect)]
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
 pub enum TeamColor {
     Blue,
     Red,
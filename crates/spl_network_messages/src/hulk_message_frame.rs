@@ -0,0 +1,191 @@
+use std::mem::size_of;
+
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{HulkMessage, HULKS_TEAM_NUMBER};
+
+pub const HULK_MESSAGE_HEADER: [u8; 4] = *b"HULK";
+/// Increment this whenever the serialized layout of `HulkMessage` changes
+pub const HULK_MESSAGE_VERSION: u8 = 4;
+
+/// Layout (little endian): header, team number, version, payload length, payload checksum
+const FRAME_HEADER_SIZE: usize =
+    size_of::<[u8; 4]>() + size_of::<u8>() + size_of::<u8>() + size_of::<u16>() + size_of::<u32>();
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    Error,
+    Hash,
+    PartialEq,
+    PathDeserialize,
+    PathIntrospect,
+    PathSerialize,
+    Serialize,
+)]
+pub enum HulkMessageRejection {
+    #[error("buffer too small")]
+    BufferTooSmall,
+    #[error("unexpected header")]
+    UnexpectedHeader,
+    #[error("unexpected team number {team_number} != {HULKS_TEAM_NUMBER}")]
+    ForeignTeam { team_number: u8 },
+    #[error("incompatible version {version} != {HULK_MESSAGE_VERSION}")]
+    IncompatibleVersion { version: u8 },
+    #[error("payload length {announced} does not match received {received}")]
+    LengthMismatch { announced: usize, received: usize },
+    #[error("checksum mismatch")]
+    ChecksumMismatch,
+    #[error("failed to deserialize payload")]
+    MalformedPayload,
+}
+
+impl TryFrom<&[u8]> for HulkMessage {
+    type Error = HulkMessageRejection;
+
+    fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
+        if buffer.len() < FRAME_HEADER_SIZE {
+            return Err(HulkMessageRejection::BufferTooSmall);
+        }
+        let (header, payload) = buffer.split_at(FRAME_HEADER_SIZE);
+        if header[0..4] != HULK_MESSAGE_HEADER {
+            return Err(HulkMessageRejection::UnexpectedHeader);
+        }
+        let team_number = header[4];
+        if team_number != HULKS_TEAM_NUMBER {
+            return Err(HulkMessageRejection::ForeignTeam { team_number });
+        }
+        let version = header[5];
+        if version != HULK_MESSAGE_VERSION {
+            return Err(HulkMessageRejection::IncompatibleVersion { version });
+        }
+        let announced = u16::from_le_bytes([header[6], header[7]]) as usize;
+        if announced != payload.len() {
+            return Err(HulkMessageRejection::LengthMismatch {
+                announced,
+                received: payload.len(),
+            });
+        }
+        let checksum = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+        if checksum != crc32fast::hash(payload) {
+            return Err(HulkMessageRejection::ChecksumMismatch);
+        }
+        bincode::deserialize(payload).map_err(|_| HulkMessageRejection::MalformedPayload)
+    }
+}
+
+impl TryFrom<HulkMessage> for Vec<u8> {
+    type Error = bincode::Error;
+
+    fn try_from(message: HulkMessage) -> Result<Self, Self::Error> {
+        let payload = bincode::serialize(&message)?;
+        let payload_length = u16::try_from(payload.len()).map_err(|_| {
+            bincode::ErrorKind::Custom(format!("payload too large: {} bytes", payload.len()))
+        })?;
+
+        let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len());
+        frame.extend_from_slice(&HULK_MESSAGE_HEADER);
+        frame.push(HULKS_TEAM_NUMBER);
+        frame.push(HULK_MESSAGE_VERSION);
+        frame.extend_from_slice(&payload_length.to_le_bytes());
+        frame.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+        frame.extend_from_slice(&payload);
+        Ok(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use linear_algebra::Pose2;
+
+    use crate::{LoserMessage, PlayerNumber, RobotStatus};
+
+    use super::*;
+
+    fn loser_frame() -> Vec<u8> {
+        HulkMessage::Loser(LoserMessage {
+            player_number: PlayerNumber::Three,
+            pose: Pose2::default(),
+            status: RobotStatus::default(),
+            ball_search_hotspot: None,
+        })
+        .try_into()
+        .unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let frame = loser_frame();
+        let message = HulkMessage::try_from(frame.as_slice()).unwrap();
+
+        assert!(matches!(
+            message,
+            HulkMessage::Loser(LoserMessage {
+                player_number: PlayerNumber::Three,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn unframed_message_is_rejected() {
+        let legacy = bincode::serialize(&HulkMessage::default()).unwrap();
+
+        assert_eq!(
+            HulkMessage::try_from(legacy.as_slice()).unwrap_err(),
+            HulkMessageRejection::UnexpectedHeader
+        );
+    }
+
+    #[test]
+    fn foreign_team_is_rejected() {
+        let mut frame = loser_frame();
+        frame[4] = 42;
+
+        assert_eq!(
+            HulkMessage::try_from(frame.as_slice()).unwrap_err(),
+            HulkMessageRejection::ForeignTeam { team_number: 42 }
+        );
+    }
+
+    #[test]
+    fn incompatible_version_is_rejected() {
+        let mut frame = loser_frame();
+        frame[5] = HULK_MESSAGE_VERSION + 1;
+
+        assert_eq!(
+            HulkMessage::try_from(frame.as_slice()).unwrap_err(),
+            HulkMessageRejection::IncompatibleVersion {
+                version: HULK_MESSAGE_VERSION + 1
+            }
+        );
+    }
+
+    #[test]
+    fn truncated_frame_is_rejected() {
+        let frame = loser_frame();
+        let truncated = &frame[..frame.len() - 1];
+
+        assert!(matches!(
+            HulkMessage::try_from(truncated).unwrap_err(),
+            HulkMessageRejection::LengthMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn corrupted_payload_is_rejected() {
+        let mut frame = loser_frame();
+        let last = frame.len() - 1;
+        frame[last] ^= 0xff;
+
+        assert_eq!(
+            HulkMessage::try_from(frame.as_slice()).unwrap_err(),
+            HulkMessageRejection::ChecksumMismatch
+        );
+    }
+}
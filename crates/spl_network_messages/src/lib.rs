@@ -1,6 +1,7 @@
 mod bindings;
 mod game_controller_return_message;
 mod game_controller_state_message;
+mod hulk_message_frame;
 
 use std::{
     fmt::{self, Display, Formatter},
@@ -8,7 +9,7 @@ use std::{
 };
 
 use coordinate_systems::Field;
-use linear_algebra::{Point2, Pose2};
+use linear_algebra::{point, Point2, Pose2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
 
@@ -17,12 +18,14 @@ pub use game_controller_state_message::{
     GameControllerStateMessage, GamePhase, GameState, Half, Penalty, PenaltyShoot, Player,
     SubState, Team, TeamColor, TeamState,
 };
+pub use hulk_message_frame::{HulkMessageRejection, HULK_MESSAGE_HEADER, HULK_MESSAGE_VERSION};
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum HulkMessage {
     Striker(StrikerMessage),
     Loser(LoserMessage),
     VisualReferee(VisualRefereeMessage),
+    Obstacles(ObstacleMessage),
 }
 
 impl Default for HulkMessage {
@@ -31,10 +34,27 @@ impl Default for HulkMessage {
     }
 }
 
+impl HulkMessage {
+    pub fn ball_search_hotspot(&self) -> Option<BallSearchHotspot> {
+        match self {
+            HulkMessage::Loser(LoserMessage {
+                ball_search_hotspot,
+                ..
+            })
+            | HulkMessage::Obstacles(ObstacleMessage {
+                ball_search_hotspot,
+                ..
+            }) => *ball_search_hotspot,
+            HulkMessage::Striker(_) | HulkMessage::VisualReferee(_) => None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
 pub struct StrikerMessage {
     pub player_number: PlayerNumber,
     pub pose: Pose2<Field>,
+    pub status: RobotStatus,
     pub ball_position: BallPosition<Field>,
     pub time_to_reach_kick_position: Duration,
 }
@@ -43,6 +63,8 @@ pub struct StrikerMessage {
 pub struct LoserMessage {
     pub player_number: PlayerNumber,
     pub pose: Pose2<Field>,
+    pub status: RobotStatus,
+    pub ball_search_hotspot: Option<BallSearchHotspot>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
@@ -50,6 +72,91 @@ pub struct VisualRefereeMessage {
     pub player_number: PlayerNumber,
 }
 
+/// Limited such that the obstacle message including the ball search hotspot fits into 128 bytes
+pub const MAXIMUM_NUMBER_OF_SHARED_OBSTACLES: usize = 4;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct ObstacleMessage {
+    pub player_number: PlayerNumber,
+    pub pose: Pose2<Field>,
+    pub status: RobotStatus,
+    pub obstacles: [Option<SharedObstacle>; MAXIMUM_NUMBER_OF_SHARED_OBSTACLES],
+    pub ball_search_hotspot: Option<BallSearchHotspot>,
+}
+
+/// Obstacle position with its standard deviation quantized to centimeters, which includes the
+/// sender's localization uncertainty
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SharedObstacle {
+    pub position: Point2<Field>,
+    deviation_centimeters: u8,
+}
+
+impl SharedObstacle {
+    pub fn new(position: Point2<Field>, deviation: f32) -> Self {
+        Self {
+            position,
+            deviation_centimeters: (deviation * 100.0)
+                .round()
+                .clamp(u8::MIN.into(), u8::MAX.into()) as u8,
+        }
+    }
+
+    pub fn deviation(&self) -> f32 {
+        f32::from(self.deviation_centimeters) / 100.0
+    }
+}
+
+/// State of the sending robot which teammates use to assign roles
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    PartialEq,
+    PathDeserialize,
+    PathIntrospect,
+    PathSerialize,
+    Serialize,
+)]
+pub struct RobotStatus {
+    pub fallen: bool,
+    /// Charge between 0 and 1, `None` if the robot has no battery sensor
+    pub battery_charge: Option<f32>,
+    /// Standard deviation of the position estimate in meters
+    pub localization_uncertainty: f32,
+}
+
+/// Most likely ball position of the sender's ball search heatmap, quantized to decimeters to fit
+/// into the obstacle message
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BallSearchHotspot {
+    x_decimeters: i8,
+    y_decimeters: i8,
+}
+
+impl BallSearchHotspot {
+    pub fn new(position: Point2<Field>) -> Self {
+        let quantize = |meters: f32| {
+            (meters * 10.0)
+                .round()
+                .clamp(i8::MIN.into(), i8::MAX.into()) as i8
+        };
+        Self {
+            x_decimeters: quantize(position.x()),
+            y_decimeters: quantize(position.y()),
+        }
+    }
+
+    pub fn position(&self) -> Point2<Field> {
+        point![
+            f32::from(self.x_decimeters) / 10.0,
+            f32::from(self.y_decimeters) / 10.0
+        ]
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -123,13 +230,18 @@ mod tests {
         let test_message = HulkMessage::Striker(StrikerMessage {
             player_number: PlayerNumber::Seven,
             pose: Pose2::default(),
+            status: RobotStatus {
+                fallen: true,
+                battery_charge: Some(1.0),
+                localization_uncertainty: f32::MAX,
+            },
             ball_position: BallPosition {
                 position: Point::origin(),
                 age: Duration::MAX,
             },
             time_to_reach_kick_position: Duration::MAX,
         });
-        assert!(bincode::serialize(&test_message).unwrap().len() <= 128)
+        assert!(Vec::<u8>::try_from(test_message).unwrap().len() <= 128)
     }
 
     #[test]
@@ -137,8 +249,51 @@ mod tests {
         let test_message = HulkMessage::Loser(LoserMessage {
             player_number: PlayerNumber::Seven,
             pose: Pose2::default(),
+            status: RobotStatus::default(),
+            ball_search_hotspot: Some(BallSearchHotspot::default()),
+        });
+        assert!(Vec::<u8>::try_from(test_message).unwrap().len() <= 128)
+    }
+
+    #[test]
+    fn hulk_obstacle_message_size() {
+        let test_message = HulkMessage::Obstacles(ObstacleMessage {
+            player_number: PlayerNumber::Seven,
+            pose: Pose2::default(),
+            status: RobotStatus {
+                fallen: true,
+                battery_charge: Some(1.0),
+                localization_uncertainty: f32::MAX,
+            },
+            obstacles: [Some(SharedObstacle::new(Point::origin(), f32::MAX));
+                MAXIMUM_NUMBER_OF_SHARED_OBSTACLES],
+            ball_search_hotspot: Some(BallSearchHotspot::default()),
         });
-        assert!(bincode::serialize(&test_message).unwrap().len() <= 128)
+        assert!(Vec::<u8>::try_from(test_message).unwrap().len() <= 128)
+    }
+
+    #[test]
+    fn ball_search_hotspot_is_quantized_to_decimeters() {
+        let hotspot = BallSearchHotspot::new(point![2.34, -2.06]);
+
+        assert_eq!(hotspot.position(), point![2.3, -2.1]);
+        assert_eq!(
+            BallSearchHotspot::new(point![20.0, -20.0]).position(),
+            point![12.7, -12.8]
+        );
+    }
+
+    #[test]
+    fn shared_obstacle_deviation_is_quantized_to_centimeters() {
+        assert_eq!(
+            SharedObstacle::new(Point::origin(), 0.123).deviation(),
+            0.12
+        );
+        assert_eq!(SharedObstacle::new(Point::origin(), 5.0).deviation(), 2.55);
+        assert_eq!(
+            SharedObstacle::new(Point::origin(), f32::NAN).deviation(),
+            0.0
+        );
     }
 
     #[test]
@@ -146,6 +301,6 @@ mod tests {
         let test_message = HulkMessage::VisualReferee(VisualRefereeMessage {
             player_number: PlayerNumber::Four,
         });
-        assert!(bincode::serialize(&test_message).unwrap().len() <= 128)
+        assert!(Vec::<u8>::try_from(test_message).unwrap().len() <= 128)
     }
 }
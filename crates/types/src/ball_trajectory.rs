@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use coordinate_systems::Ground;
+use linear_algebra::{Point2, Vector2};
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct PredictedBallState {
+    /// Time since the start of the prediction
+    pub time: Duration,
+    pub position: Point2<Ground>,
+    pub velocity: Vector2<Ground>,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct BallTrajectory {
+    /// Sampled states from the current ball state until the ball rests or the prediction ends
+    pub states: Vec<PredictedBallState>,
+    pub rest_position: Point2<Ground>,
+    /// `None` if the ball is still rolling at the end of the prediction horizon
+    pub time_to_rest: Option<Duration>,
+}
+
+impl BallTrajectory {
+    /// First state in which the ball crosses the line at `x` coming from larger `x` values
+    pub fn first_crossing_of_x(&self, x: f32) -> Option<PredictedBallState> {
+        self.states.windows(2).find_map(|window| {
+            let (start, end) = (window[0], window[1]);
+            if start.position.x() < x || end.position.x() >= x {
+                return None;
+            }
+            let factor = (start.position.x() - x) / (start.position.x() - end.position.x());
+            Some(interpolate(start, end, factor))
+        })
+    }
+
+    /// State in which the ball passes closest to `point`
+    pub fn closest_approach(&self, point: Point2<Ground>) -> Option<PredictedBallState> {
+        if let [state] = self.states.as_slice() {
+            return Some(*state);
+        }
+        self.states
+            .windows(2)
+            .map(|window| {
+                let (start, end) = (window[0], window[1]);
+                let segment = end.position - start.position;
+                let factor = if segment.norm_squared() > f32::EPSILON {
+                    ((point - start.position).dot(&segment) / segment.norm_squared())
+                        .clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                interpolate(start, end, factor)
+            })
+            .min_by(|a, b| {
+                (a.position - point)
+                    .norm_squared()
+                    .total_cmp(&(b.position - point).norm_squared())
+            })
+    }
+}
+
+fn interpolate(
+    start: PredictedBallState,
+    end: PredictedBallState,
+    factor: f32,
+) -> PredictedBallState {
+    PredictedBallState {
+        time: start.time + (end.time - start.time).mul_f32(factor),
+        position: start.position + (end.position - start.position) * factor,
+        velocity: start.velocity + (end.velocity - start.velocity) * factor,
+    }
+}
@@ -0,0 +1,22 @@
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+
+/// Outcome of one action the behavior tried in a cycle, in the order of the priority list
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct ActionDecision {
+    pub action: Action,
+    /// Why the action did not apply, `None` if it was selected
+    pub rejection_reason: Option<String>,
+}
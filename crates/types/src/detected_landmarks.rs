@@ -0,0 +1,25 @@
+use linear_algebra::Point2;
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+use coordinate_systems::Ground;
+
+use crate::color::Rgb;
+
+#[derive(
+    Default, Clone, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct DetectedLandmarks {
+    pub penalty_marks: Vec<Point2<Ground>>,
+    pub goal_posts: Vec<DetectedGoalPost>,
+}
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct DetectedGoalPost {
+    /// Where the post touches the ground
+    pub position: Point2<Ground>,
+    /// Mean color of the post, used to tell the two goals apart on fields with colored goals
+    pub color: Rgb,
+}
@@ -0,0 +1,16 @@
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+use coordinate_systems::Ground;
+use linear_algebra::Point2;
+use spl_network_messages::TeamColor;
+
+use crate::bounding_box::BoundingBox;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect)]
+pub struct DetectedRobot {
+    pub bounding_box: BoundingBox,
+    pub jersey_color: Option<TeamColor>,
+    pub is_teammate: Option<bool>,
+    pub ground_position: Option<Point2<Ground>>,
+}
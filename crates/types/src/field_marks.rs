@@ -20,6 +20,10 @@ pub enum FieldMark {
         center: Point2<Field>,
         radius: f32,
     },
+    Point {
+        point: Point2<Field>,
+        kind: PointMarkKind,
+    },
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -28,6 +32,32 @@ pub enum Direction {
     PositiveY,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PointMarkKind {
+    PenaltyMark,
+    GoalPost { goal: Option<Goal> },
+}
+
+impl PointMarkKind {
+    /// Goal posts of an unknown goal may correspond to the posts of either goal
+    pub fn is_compatible_with(self, reference: PointMarkKind) -> bool {
+        match (self, reference) {
+            (PointMarkKind::PenaltyMark, PointMarkKind::PenaltyMark) => true,
+            (
+                PointMarkKind::GoalPost { goal: measured },
+                PointMarkKind::GoalPost { goal: reference },
+            ) => measured.is_none() || reference.is_none() || measured == reference,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Goal {
+    Own,
+    Opponent,
+}
+
 impl FieldMark {
     pub fn to_correspondence_points(self, measured_line: LineSegment<Field>) -> Correspondences {
         match self {
@@ -138,6 +168,24 @@ impl FieldMark {
                     reference_direction,
                 }
             }
+            FieldMark::Point { point, kind: _ } => {
+                // Point measurements are degenerate lines, they carry no direction
+                let direction = Vector2::x_axis();
+                Correspondences {
+                    correspondence_points: (
+                        CorrespondencePoints {
+                            measured: measured_line.0,
+                            reference: point,
+                        },
+                        CorrespondencePoints {
+                            measured: measured_line.1,
+                            reference: point,
+                        },
+                    ),
+                    measured_direction: direction,
+                    reference_direction: direction,
+                }
+            }
         }
     }
 }
@@ -416,5 +464,38 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
             ),
             direction: Direction::PositiveY,
         },
+        FieldMark::Point {
+            point: point![
+                -field_dimensions.length / 2.0 + field_dimensions.penalty_marker_distance,
+                0.0
+            ],
+            kind: PointMarkKind::PenaltyMark,
+        },
+        FieldMark::Point {
+            point: point![
+                field_dimensions.length / 2.0 - field_dimensions.penalty_marker_distance,
+                0.0
+            ],
+            kind: PointMarkKind::PenaltyMark,
+        },
+    ]
+    .into_iter()
+    .chain(goal_post_marks(field_dimensions))
+    .collect()
+}
+
+fn goal_post_marks(field_dimensions: &FieldDimensions) -> [FieldMark; 4] {
+    let goal_post_x = field_dimensions.length / 2.0 + field_dimensions.goal_post_diameter / 2.0;
+    let goal_post_y =
+        field_dimensions.goal_inner_width / 2.0 + field_dimensions.goal_post_diameter / 2.0;
+    let goal_post = |x: f32, y: f32, goal: Goal| FieldMark::Point {
+        point: point![x, y],
+        kind: PointMarkKind::GoalPost { goal: Some(goal) },
+    };
+    [
+        goal_post(-goal_post_x, -goal_post_y, Goal::Own),
+        goal_post(-goal_post_x, goal_post_y, Goal::Own),
+        goal_post(goal_post_x, -goal_post_y, Goal::Opponent),
+        goal_post(goal_post_x, goal_post_y, Goal::Opponent),
     ]
 }
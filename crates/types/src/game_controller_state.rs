@@ -2,7 +2,7 @@ use std::time::{Duration, SystemTime};
 
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
-use spl_network_messages::{GamePhase, GameState, Penalty, SubState, Team, TeamState};
+use spl_network_messages::{GamePhase, GameState, Half, Penalty, SubState, Team, TeamState};
 
 use crate::players::Players;
 
@@ -10,6 +10,7 @@ use crate::players::Players;
 pub struct GameControllerState {
     pub game_state: GameState,
     pub game_phase: GamePhase,
+    pub half: Half,
     pub remaining_time_in_half: Duration,
     pub kicking_team: Option<Team>,
     pub last_game_state_change: SystemTime,
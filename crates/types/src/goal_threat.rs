@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use coordinate_systems::{Field, Ground};
+use linear_algebra::Point2;
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Keeper motion to stop a shot, ordered from the least to the most disruptive
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum KeeperDive {
+    WideStance,
+    CenterJump,
+    JumpLeft,
+    JumpRight,
+}
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct KeeperDiveOption {
+    pub dive: KeeperDive,
+    /// Whether the motion covers its area before the ball arrives
+    pub is_reachable: bool,
+    /// Probability that the ball passes the keeper within the area covered by the motion
+    pub save_probability: f32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect)]
+pub struct GoalThreat {
+    /// Predicted point where the ball crosses the own goal line
+    pub goal_line_crossing: Point2<Field>,
+    pub time_to_goal_line: Duration,
+    /// Standard deviation of the crossing point along the goal line
+    pub goal_line_standard_deviation: f32,
+    /// Probability that the ball crosses the goal line between the posts
+    pub on_target_probability: f32,
+    /// Predicted point where the ball passes the lateral axis of the robot
+    pub robot_line_crossing: Option<Point2<Ground>>,
+    pub time_to_robot_line: Option<Duration>,
+    pub robot_line_standard_deviation: f32,
+    pub dive_options: Vec<KeeperDiveOption>,
+}
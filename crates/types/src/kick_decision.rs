@@ -2,6 +2,7 @@ use coordinate_systems::Ground;
 use linear_algebra::{Point2, Pose2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
+use spl_network_messages::PlayerNumber;
 
 use crate::{motion_command::KickVariant, support_foot::Side};
 
@@ -33,6 +34,8 @@ pub struct KickDecision {
     pub kicking_side: Side,
     pub kick_pose: Pose2<Ground>,
     pub strength: f32,
+    /// Teammate the ball is passed to, `None` for shots
+    pub receiver: Option<PlayerNumber>,
 }
 
 #[derive(
@@ -58,4 +61,21 @@ pub struct DecisionParameters {
     pub angle_distance_weight: f32,
     pub closer_to_goal_threshold: f32,
     pub goal_accuracy_margin: f32,
+
+    pub enable_passing: bool,
+    pub pass_kick_strength: f32,
+    pub pass_minimum_distance: f32,
+    pub pass_maximum_distance: f32,
+    /// How much closer to the opponent goal the receiver has to be than the ball
+    pub pass_minimum_progress: f32,
+    /// Maximum angle between the orientation of the receiver and the direction to the ball
+    pub pass_receiver_maximum_angle_to_ball: f32,
+    /// Obstacles this close to the receiver are assumed to be the receiver itself
+    pub pass_receiver_clearance: f32,
+    /// Cost added to every pass when comparing it with shots, in meters of walking distance
+    pub pass_cost: f32,
+    /// Cost reduction per meter the receiver is closer to the opponent goal than the ball
+    pub pass_progress_weight: f32,
+    /// Cost of a receiver facing away from the ball by `pass_receiver_maximum_angle_to_ball`
+    pub pass_readiness_weight: f32,
 }
@@ -3,6 +3,7 @@ pub mod action;
 pub mod audio;
 pub mod ball_detection;
 pub mod ball_position;
+pub mod ball_trajectory;
 pub mod bounding_box;
 pub mod buttons;
 pub mod calibration;
@@ -10,7 +11,10 @@ pub mod camera_position;
 pub mod color;
 pub mod condition_input;
 pub mod cycle_time;
+pub mod decision_trace;
 pub mod detected_feet;
+pub mod detected_landmarks;
+pub mod detected_robots;
 pub mod dribble_path_plan;
 pub mod fall_state;
 pub mod field_border;
@@ -25,6 +29,7 @@ pub mod filtered_whistle;
 pub mod foot_bumper_obstacle;
 pub mod foot_bumper_values;
 pub mod game_controller_state;
+pub mod goal_threat;
 pub mod grayscale_image;
 pub mod image_segments;
 pub mod initial_look_around;
@@ -44,6 +49,7 @@ pub mod motion_command;
 pub mod motion_selection;
 pub mod motor_commands;
 pub mod multivariate_normal_distribution;
+pub mod node_supervision;
 pub mod obstacle_avoiding_arms;
 pub mod obstacle_filter;
 pub mod obstacles;
@@ -57,6 +63,7 @@ pub mod point_of_interest;
 pub mod pose_detection;
 pub mod pose_kinds;
 pub mod primary_state;
+pub mod recording_event;
 pub mod robot_dimensions;
 pub mod robot_kinematics;
 pub mod robot_masses;
@@ -67,9 +74,12 @@ pub mod sensor_data;
 pub mod sole_pressure;
 pub mod sonar_obstacle;
 pub mod sonar_values;
+pub mod spl_message_budget;
 pub mod stand_up;
 pub mod step;
 pub mod support_foot;
+pub mod teammate;
+pub mod thermal_protection;
 pub mod walk_command;
 pub mod whistle;
 pub mod world_state;
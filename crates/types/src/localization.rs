@@ -5,7 +5,9 @@ use coordinate_systems::{Field, Ground};
 use linear_algebra::{Isometry2, Point2, Pose2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 
-use crate::multivariate_normal_distribution::MultivariateNormalDistribution;
+use crate::{
+    field_marks::PointMarkKind, multivariate_normal_distribution::MultivariateNormalDistribution,
+};
 
 #[derive(
     Clone, Copy, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
@@ -19,6 +21,42 @@ pub struct Update {
     pub line_length_weight: f32,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum LocalizationMode {
+    #[default]
+    MultiHypothesis,
+    ParticleFilter,
+}
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct Particle {
+    pub pose: Pose2<Field>,
+    pub weight: f32,
+}
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct MeasuredPointMark {
+    pub position: Point2<Field>,
+    #[path_serde(leaf)]
+    pub kind: PointMarkKind,
+}
+
 #[derive(
     Clone, Copy, Debug, Serialize, Deserialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
@@ -3,12 +3,15 @@ use std::net::SocketAddr;
 use serde::{Deserialize, Serialize};
 
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
-use spl_network_messages::{GameControllerReturnMessage, GameControllerStateMessage, HulkMessage};
+use spl_network_messages::{
+    GameControllerReturnMessage, GameControllerStateMessage, HulkMessage, HulkMessageRejection,
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect)]
 pub enum IncomingMessage {
     GameController(SocketAddr, GameControllerStateMessage),
     Spl(HulkMessage),
+    RejectedSpl(HulkMessageRejection),
 }
 
 impl Default for IncomingMessage {
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Error statistics of a node whose errors are isolated by its supervision policy
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct NodeSupervisionState {
+    pub error_count: usize,
+    /// Whether the last cycle of the node failed
+    pub is_degraded: bool,
+    pub last_error: Option<String>,
+}
+
+impl NodeSupervisionState {
+    /// Records a failed cycle, returns `true` if the node was healthy before
+    pub fn record_error(&mut self, error: &impl Display) -> bool {
+        let was_healthy = !self.is_degraded;
+        self.error_count += 1;
+        self.is_degraded = true;
+        self.last_error = Some(format!("{error:#}"));
+        was_healthy
+    }
+
+    pub fn record_success(&mut self) {
+        self.is_degraded = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_first_error_after_success_is_reported() {
+        let mut state = NodeSupervisionState::default();
+
+        assert!(state.record_error(&"first"));
+        assert!(!state.record_error(&"second"));
+        assert_eq!(state.error_count, 2);
+        assert!(state.is_degraded);
+        assert_eq!(state.last_error.as_deref(), Some("second"));
+
+        state.record_success();
+        assert!(!state.is_degraded);
+        assert_eq!(state.error_count, 2);
+        assert!(state.record_error(&"third"));
+    }
+}
@@ -2,14 +2,28 @@ use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 
+use coordinate_systems::Ground;
+use linear_algebra::{point, vector, Point2, Vector2};
+
 use crate::{
     multivariate_normal_distribution::MultivariateNormalDistribution, obstacles::ObstacleKind,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Hypothesis {
-    pub state: MultivariateNormalDistribution<2>,
+    /// Position and velocity in ground coordinates
+    pub state: MultivariateNormalDistribution<4>,
     pub measurement_count: usize,
     pub last_update: SystemTime,
     pub obstacle_kind: ObstacleKind,
 }
+
+impl Hypothesis {
+    pub fn position(&self) -> Point2<Ground> {
+        point![self.state.mean.x, self.state.mean.y]
+    }
+
+    pub fn velocity(&self) -> Vector2<Ground> {
+        vector![self.state.mean.z, self.state.mean.w]
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use linear_algebra::Point2;
+use linear_algebra::{Point2, Vector2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 
 use coordinate_systems::Ground;
@@ -32,6 +32,8 @@ pub struct Obstacle {
     pub position: Point2<Ground>,
     pub radius_at_foot_height: f32,
     pub radius_at_hip_height: f32,
+    /// Estimated velocity, zero for static obstacles
+    pub velocity: Vector2<Ground>,
 }
 
 impl Obstacle {
@@ -41,6 +43,7 @@ impl Obstacle {
             position,
             radius_at_foot_height: radius,
             radius_at_hip_height: radius,
+            velocity: Vector2::zeros(),
         }
     }
 
@@ -54,6 +57,7 @@ impl Obstacle {
             position,
             radius_at_foot_height,
             radius_at_hip_height,
+            velocity: Vector2::zeros(),
         }
     }
 
@@ -63,6 +67,7 @@ impl Obstacle {
             position,
             radius_at_foot_height: radius,
             radius_at_hip_height: radius,
+            velocity: Vector2::zeros(),
         }
     }
 }
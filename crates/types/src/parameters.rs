@@ -5,11 +5,15 @@ use coordinate_systems::{Field, Ground, NormalizedPixel, Pixel};
 use linear_algebra::{Point2, Vector2};
 use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use serde::{Deserialize, Serialize};
+use spl_network_messages::{PlayerNumber, SubState, Team};
 
 use crate::{
+    action::Action,
+    color::Rgb,
     joints::head::HeadJoints,
     motion_command::{KickVariant, MotionCommand},
     roles::Role,
+    spl_message_budget::SplMessageKinds,
 };
 
 #[derive(
@@ -36,7 +40,38 @@ pub struct BehaviorParameters {
     pub search: SearchParameters,
     pub look_action: LookActionParameters,
     pub intercept_ball: InterceptBallParameters,
-    pub maximum_lookaround_duration: Duration,
+    pub receive_pass: ReceivePassParameters,
+    pub priority_list: Vec<BehaviorRule>,
+}
+
+/// Entry of the behavior priority list, the actions of all rules whose conditions hold are tried
+/// in order until one of them returns a motion command
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct BehaviorRule {
+    #[serde(default)]
+    pub conditions: Vec<BehaviorCondition>,
+    #[serde(default)]
+    pub actions: Vec<Action>,
+    /// Only the first of these rules whose conditions hold is applied after `actions`
+    #[serde(default)]
+    pub first_of: Vec<BehaviorRule>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum BehaviorCondition {
+    Role(Role),
+    PlayerNumber(PlayerNumber),
+    /// The robot is in Ready, Set or Playing for less than this duration
+    ActiveShorterThan(Duration),
+    LocalizationConverged,
+    NoGameControllerState,
+    Ready,
+    Playing,
+    BallIsFree,
+    PenaltyShootout,
+    SubState(SubState),
+    KickingTeam(Team),
+    Not(Box<BehaviorCondition>),
 }
 
 #[derive(
@@ -213,6 +248,19 @@ pub struct InterceptBallParameters {
     pub maximum_intercept_distance: f32,
 }
 
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct ReceivePassParameters {
+    pub enable: bool,
+    /// Added to the obstacle radius when checking whether the passing lane is free
+    pub obstacle_clearance: f32,
+    /// Obstacles closer to the ball are assumed to be the passing robot
+    pub minimum_obstacle_distance_to_ball: f32,
+    pub angle_step: f32,
+    pub maximum_angle_offset: f32,
+}
+
 #[derive(
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
@@ -226,6 +274,18 @@ pub struct PathPlanningParameters {
     pub robot_radius_at_foot_height: f32,
     pub robot_radius_at_hip_height: f32,
     pub half_rotation: Duration,
+    pub moving_obstacles: MovingObstaclesParameters,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct MovingObstaclesParameters {
+    pub enable: bool,
+    pub minimum_speed: f32,
+    pub time_horizon: Duration,
+    pub sample_interval: Duration,
+    pub arrival_time_hysteresis: Duration,
 }
 
 #[derive(
@@ -295,7 +355,35 @@ pub struct SplNetworkParameters {
     pub remaining_amount_of_messages_to_stop_sending: u16,
     pub silence_interval_between_messages: Duration,
     pub spl_striker_message_receive_timeout: Duration,
-    pub spl_striker_message_send_interval: Duration,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct RoleUtilityParameters {
+    pub walking_speed: f32,
+    pub stand_up_time: Duration,
+    /// Time to reach any role position for players whose pose is not shared with the team
+    pub unknown_pose_time: Duration,
+    /// Relative increase of the time to reach per meter of localization uncertainty
+    pub localization_uncertainty_factor: f32,
+    pub maximum_localization_uncertainty: f32,
+    /// Relative increase of the time to reach for an empty battery
+    pub empty_battery_factor: f32,
+    /// Weight of each optional role relative to the one before it, so that the most impaired
+    /// players take the least important roles
+    pub role_priority_decay: f32,
+    /// Utility added for keeping the previously assigned role, in seconds of time to reach
+    pub role_keeping_bonus: Duration,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct SplMessageBudgetParameters {
+    pub half_duration: Duration,
+    pub minimum_send_intervals: SplMessageKinds<Duration>,
+    pub priorities: SplMessageKinds<f32>,
 }
 
 #[derive(
@@ -354,6 +442,53 @@ pub struct BallDetectionParameters {
     pub noise_increase_distance_threshold: f32,
 }
 
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct ParticleFilterParameters {
+    pub number_of_particles: usize,
+    pub odometry_deviation: nalgebra::Vector3<f32>,
+    pub line_measurement_deviation: f32,
+    pub point_mark_measurement_deviation: f32,
+    pub unmatched_measurement_likelihood: f32,
+    pub slow_average_rate: f32,
+    pub fast_average_rate: f32,
+    pub maximum_converged_position_deviation: f32,
+    pub random_seed: u64,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct LandmarkDetectionParameters {
+    pub enable: bool,
+    pub minimum_luminance: u8,
+    pub maximum_detection_distance: f32,
+    pub allowed_penalty_mark_segment_length: Range<f32>,
+    pub maximum_penalty_mark_cluster_distance: f32,
+    pub maximum_penalty_mark_width: f32,
+    pub minimum_penalty_mark_samples: usize,
+    pub maximum_goal_post_cluster_distance: f32,
+    pub maximum_goal_post_width: f32,
+    pub minimum_goal_post_samples: usize,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct GoalPostColors {
+    pub own_goal_in_first_half: Rgb,
+    pub opponent_goal_in_first_half: Rgb,
+}
+
 #[derive(
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
@@ -381,6 +516,22 @@ pub struct BallFilterParameters {
     pub maximum_matching_cost_validity_penalty_factor: f32,
 }
 
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct BallTrajectoryParameters {
+    /// Speed independent deceleration caused by rolling resistance in m/s², calibrated per field
+    pub rolling_deceleration: f32,
+    /// Deceleration per speed in 1/s, calibrated per field
+    pub velocity_drag: f32,
+    /// Fraction of the normal velocity kept when bouncing off goal posts and robots
+    pub restitution: f32,
+    pub time_step: Duration,
+    pub prediction_horizon: Duration,
+    /// Speed below which the ball is considered to be at rest
+    pub resting_speed: f32,
+}
+
 #[derive(
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
@@ -389,16 +540,22 @@ pub struct ObstacleFilterParameters {
     pub network_robot_measurement_matching_distance: f32,
     pub sonar_goal_post_matching_distance: f32,
     pub feet_detection_measurement_matching_distance: f32,
+    pub robot_detection_measurement_matching_distance: f32,
     pub goal_post_measurement_matching_distance: f32,
     pub hypothesis_merge_distance: f32,
-    pub process_noise: nalgebra::Vector2<f32>,
+    /// Process noise accumulated per second
+    pub process_noise: nalgebra::Vector4<f32>,
+    /// Fraction of the velocity remaining after one second
+    pub velocity_decay: f32,
     pub feet_measurement_noise: nalgebra::Vector2<f32>,
     pub robot_measurement_noise: nalgebra::Vector2<f32>,
     pub sonar_measurement_noise: nalgebra::Vector2<f32>,
     pub network_robot_measurement_noise: nalgebra::Vector2<f32>,
     pub initial_covariance: nalgebra::Vector2<f32>,
+    pub initial_velocity_covariance: nalgebra::Vector2<f32>,
     pub measurement_count_threshold: usize,
     pub use_feet_detection_measurements: bool,
+    pub use_robot_detection_measurements: bool,
     pub use_sonar_measurements: bool,
     pub use_foot_bumper_measurements: bool,
     pub robot_obstacle_radius_at_hip_height: f32,
@@ -427,18 +584,37 @@ pub struct SearchSuggestorParameters {
     pub own_ball_weight: f32,
     pub team_ball_weight: f32,
     pub rule_ball_weight: f32,
+    /// Weight of the heatmap maxima teammates share during the ball search
+    pub team_hotspot_weight: f32,
+    /// Distance the own heatmap maximum has to move before it is shared again
+    pub hotspot_share_distance: f32,
 }
 
 #[derive(
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
+pub struct GoalThreatParameters {
+    /// Dives are only decided if the ball passes the robot within this time
+    pub maximum_time_to_robot_line: Duration,
+    pub position_standard_deviation: f32,
+    /// Uncertainty of the ball direction, scaled by the distance the ball travels
+    pub heading_standard_deviation: f32,
+    pub minimum_on_target_probability: f32,
+    /// The least disruptive reachable dive with at least this save probability is chosen
+    pub minimum_save_probability: f32,
+    pub wide_stance: KeeperDiveParameters,
+    pub center_jump: KeeperDiveParameters,
+    pub side_jump: KeeperDiveParameters,
+}
 
-pub struct KeeperMotionParameters {
-    pub action_radius_center: f32,
-    pub minimum_velocity: f32,
-    pub action_radius_left: f32,
-    pub maximum_ball_distance: f32,
-    pub minimum_ball_velocity: f32,
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct KeeperDiveParameters {
+    /// Lateral distance from the robot covered by the motion
+    pub reach: f32,
+    /// Time from the start of the motion until the reach is covered
+    pub reaction_time: Duration,
 }
 
 #[derive(
@@ -449,3 +625,32 @@ pub struct PenaltyShotDirectionParameters {
     pub minimum_velocity: f32,
     pub center_jump_trigger_radius: f32,
 }
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct RecordingEventDetectionParameters {
+    pub fall: bool,
+    pub role_change: bool,
+    pub penalty: bool,
+    pub localization_divergence: bool,
+}
+
+#[derive(
+    Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct ThermalProtectionParameters {
+    /// Temperature at which the joints start to reduce their stiffness on their own
+    pub temperature_limit: f32,
+    pub ambient_temperature: f32,
+    /// Temperature increase per second and squared ampere
+    pub heating_coefficient: f32,
+    /// Fraction of the difference to the ambient temperature lost per second
+    pub cooling_coefficient: f32,
+    pub current_low_pass_factor: f32,
+    pub warning_time_to_limit: Duration,
+    pub critical_time_to_limit: Duration,
+    pub warning_stiffness_factor: f32,
+    pub critical_stiffness_factor: f32,
+    pub time_to_reach_kick_position_penalty: Duration,
+}
@@ -0,0 +1,23 @@
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Moments worth keeping when only the seconds around events are recorded
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum RecordingEvent {
+    Fall,
+    RoleChange,
+    Penalty,
+    LocalizationDivergence,
+    NodeError,
+}
@@ -75,6 +75,24 @@ pub struct TouchSensors {
     pub right_hand_right: bool,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct Battery {
+    /// Charge between 0 and 1
+    pub charge: f32,
+    pub current: f32,
+    pub temperature: f32,
+}
+
 #[derive(
     Clone, Debug, Default, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
 )]
@@ -86,4 +104,5 @@ pub struct SensorData {
     pub touch_sensors: TouchSensors,
     pub temperature_sensors: Joints<f32>,
     pub currents: Joints<f32>,
+    pub battery: Option<Battery>,
 }
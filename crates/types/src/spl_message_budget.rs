@@ -0,0 +1,110 @@
+use std::{
+    ops::{Index, IndexMut},
+    time::{Duration, SystemTime},
+};
+
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    Hash,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum SplMessageKind {
+    Striker,
+    Loser,
+    Obstacles,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    PartialEq,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct SplMessageKinds<T> {
+    pub striker: T,
+    pub loser: T,
+    pub obstacles: T,
+}
+
+impl<T> Index<SplMessageKind> for SplMessageKinds<T> {
+    type Output = T;
+
+    fn index(&self, kind: SplMessageKind) -> &Self::Output {
+        match kind {
+            SplMessageKind::Striker => &self.striker,
+            SplMessageKind::Loser => &self.loser,
+            SplMessageKind::Obstacles => &self.obstacles,
+        }
+    }
+}
+
+impl<T> IndexMut<SplMessageKind> for SplMessageKinds<T> {
+    fn index_mut(&mut self, kind: SplMessageKind) -> &mut Self::Output {
+        match kind {
+            SplMessageKind::Striker => &mut self.striker,
+            SplMessageKind::Loser => &mut self.loser,
+            SplMessageKind::Obstacles => &mut self.obstacles,
+        }
+    }
+}
+
+/// Written by every node that sends SPL messages, read by the message budget planner
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct SentSplMessages {
+    pub last_sent: Option<SystemTime>,
+    pub last_sent_per_kind: SplMessageKinds<Option<SystemTime>>,
+}
+
+impl SentSplMessages {
+    pub fn record(&mut self, kind: SplMessageKind, time: SystemTime) {
+        self.last_sent = Some(time);
+        self.last_sent_per_kind[kind] = Some(time);
+    }
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub struct SplMessageBudgetForecast {
+    pub remaining_messages: u16,
+    pub usable_messages: u16,
+    pub remaining_game_time: Duration,
+    pub sustainable_team_interval: Option<Duration>,
+    pub send_intervals: SplMessageKinds<Option<Duration>>,
+    pub expected_messages_until_end_of_game: f32,
+}
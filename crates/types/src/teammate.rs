@@ -0,0 +1,19 @@
+use std::time::SystemTime;
+
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+use coordinate_systems::Field;
+use linear_algebra::Pose2;
+use spl_network_messages::{PlayerNumber, RobotStatus};
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect,
+)]
+pub struct Teammate {
+    pub player_number: PlayerNumber,
+    pub pose: Pose2<Field>,
+    pub status: RobotStatus,
+    /// Time of the last message which contained the pose of this teammate
+    pub last_received: SystemTime,
+}
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::joints::Joints;
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    PathSerialize,
+    PathDeserialize,
+    PathIntrospect,
+)]
+pub enum ThermalLevel {
+    #[default]
+    Normal,
+    Warning,
+    Critical,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PathSerialize, PathDeserialize, PathIntrospect)]
+pub struct ThermalProtection {
+    /// Level of the joint closest to its temperature limit
+    pub level: ThermalLevel,
+    pub joint_levels: Joints<ThermalLevel>,
+    /// Predicted time until each joint reaches the temperature limit, `None` if it is not
+    /// expected to reach it at the current load
+    pub time_to_limit: Joints<Option<Duration>>,
+    pub stiffness_factors: Joints<f32>,
+    pub limit_walk_speed: bool,
+    /// Added to the time to reach the kick position so that cooler teammates take over the
+    /// striker role
+    pub time_to_reach_kick_position_penalty: Duration,
+}
+
+impl Default for ThermalProtection {
+    fn default() -> Self {
+        Self {
+            level: ThermalLevel::Normal,
+            joint_levels: Joints::fill(ThermalLevel::Normal),
+            time_to_limit: Joints::fill(None),
+            stiffness_factors: Joints::fill(1.0),
+            limit_walk_speed: false,
+            time_to_reach_kick_position_penalty: Duration::ZERO,
+        }
+    }
+}
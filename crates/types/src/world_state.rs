@@ -8,17 +8,19 @@ use path_serde::{PathDeserialize, PathIntrospect, PathSerialize};
 use spl_network_messages::PlayerNumber;
 
 use crate::{
-    ball_position::HypotheticalBallPosition, calibration::CalibrationCommand,
-    fall_state::FallState, field_dimensions::Side,
-    filtered_game_controller_state::FilteredGameControllerState, kick_decision::KickDecision,
-    obstacles::Obstacle, penalty_shot_direction::PenaltyShotDirection, primary_state::PrimaryState,
-    roles::Role, rule_obstacles::RuleObstacle,
+    ball_position::HypotheticalBallPosition, ball_trajectory::BallTrajectory,
+    calibration::CalibrationCommand, fall_state::FallState, field_dimensions::Side,
+    filtered_game_controller_state::FilteredGameControllerState, goal_threat::KeeperDive,
+    kick_decision::KickDecision, obstacles::Obstacle, penalty_shot_direction::PenaltyShotDirection,
+    primary_state::PrimaryState, roles::Role, rule_obstacles::RuleObstacle,
 };
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PathSerialize, PathIntrospect)]
 pub struct WorldState {
     pub ball: Option<BallState>,
     pub rule_ball: Option<BallState>,
+    pub ball_trajectory: Option<BallTrajectory>,
+    pub keeper_dive: Option<KeeperDive>,
     pub hypothetical_ball_positions: Vec<HypotheticalBallPosition<Ground>>,
     pub filtered_game_controller_state: Option<FilteredGameControllerState>,
     pub obstacles: Vec<Obstacle>,
@@ -0,0 +1,358 @@
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use context_attribute::context;
+use coordinate_systems::Ground;
+use filtering::mean_clustering::MeanClustering;
+use framework::{AdditionalOutput, MainOutput};
+use linear_algebra::{distance, point, Point2};
+use projection::{camera_matrix::CameraMatrix, Projection};
+use types::{
+    ball_detection::BallPercept,
+    color::{Intensity, Rgb},
+    detected_feet::CountedCluster,
+    detected_landmarks::{DetectedGoalPost, DetectedLandmarks},
+    field_border::FieldBorder,
+    filtered_segments::FilteredSegments,
+    image_segments::{EdgeType, ImageSegments, ScanLine, Segment},
+    line_data::LineData,
+    parameters::LandmarkDetectionParameters,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct LandmarkDetection {}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    penalty_mark_candidates:
+        AdditionalOutput<Vec<Point2<Ground>>, "landmark_detection.penalty_mark_candidates">,
+    goal_post_candidates:
+        AdditionalOutput<Vec<Point2<Ground>>, "landmark_detection.goal_post_candidates">,
+
+    parameters: Parameter<LandmarkDetectionParameters, "landmark_detection.$cycler_instance">,
+
+    balls: RequiredInput<Option<Vec<BallPercept>>, "balls?">,
+    camera_matrix: RequiredInput<Option<CameraMatrix>, "camera_matrix?">,
+    field_border: Input<Option<FieldBorder>, "field_border?">,
+    filtered_segments: Input<FilteredSegments, "filtered_segments">,
+    image_segments: Input<ImageSegments, "image_segments">,
+    line_data: RequiredInput<Option<LineData>, "line_data?">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub detected_landmarks: MainOutput<DetectedLandmarks>,
+}
+
+impl LandmarkDetection {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {})
+    }
+
+    pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        let parameters = context.parameters;
+        if !parameters.enable {
+            return Ok(MainOutputs::default());
+        }
+
+        let penalty_mark_candidates = find_penalty_mark_candidates(
+            context.filtered_segments,
+            context.line_data,
+            context.balls,
+            context.camera_matrix,
+            parameters,
+        );
+        context
+            .penalty_mark_candidates
+            .fill_if_subscribed(|| penalty_mark_candidates.clone());
+        let penalty_marks = cluster_candidates(
+            penalty_mark_candidates
+                .into_iter()
+                .map(|position| (position, ())),
+            parameters.maximum_penalty_mark_cluster_distance,
+        )
+        .into_iter()
+        .filter(|(cluster, _)| {
+            cluster.samples >= parameters.minimum_penalty_mark_samples
+                && cluster_width(cluster) <= parameters.maximum_penalty_mark_width
+        })
+        .map(|(cluster, _)| cluster.mean)
+        .collect();
+
+        let goal_post_candidates = match context.field_border {
+            Some(field_border) => find_goal_post_candidates(
+                context.image_segments,
+                field_border,
+                context.camera_matrix,
+                parameters,
+            ),
+            None => Vec::new(),
+        };
+        context.goal_post_candidates.fill_if_subscribed(|| {
+            goal_post_candidates
+                .iter()
+                .map(|(position, _color)| *position)
+                .collect()
+        });
+        let goal_posts = cluster_candidates(
+            goal_post_candidates.into_iter(),
+            parameters.maximum_goal_post_cluster_distance,
+        )
+        .into_iter()
+        .filter(|(cluster, _)| {
+            cluster.samples >= parameters.minimum_goal_post_samples
+                && cluster_width(cluster) <= parameters.maximum_goal_post_width
+        })
+        .map(|(cluster, colors)| DetectedGoalPost {
+            position: cluster.mean,
+            color: mean_color(&colors),
+        })
+        .collect();
+
+        Ok(MainOutputs {
+            detected_landmarks: DetectedLandmarks {
+                penalty_marks,
+                goal_posts,
+            }
+            .into(),
+        })
+    }
+}
+
+fn find_penalty_mark_candidates(
+    filtered_segments: &FilteredSegments,
+    line_data: &LineData,
+    balls: &[BallPercept],
+    camera_matrix: &CameraMatrix,
+    parameters: &LandmarkDetectionParameters,
+) -> Vec<Point2<Ground>> {
+    filtered_segments
+        .scan_grid
+        .vertical_scan_lines
+        .iter()
+        .flat_map(|scan_line| {
+            scan_line.segments.iter().filter_map(move |segment| {
+                let is_bright_blob = segment.start_edge_type == EdgeType::Rising
+                    && segment.end_edge_type == EdgeType::Falling
+                    && segment.color.y >= parameters.minimum_luminance;
+                let is_on_line = line_data
+                    .used_segments
+                    .contains(&point![scan_line.position, segment.start]);
+                let is_on_ball = balls.iter().any(|ball| {
+                    ball.image_location
+                        .contains(point![scan_line.position as f32, segment.center() as f32])
+                });
+                if !is_bright_blob || is_on_line || is_on_ball {
+                    return None;
+                }
+                let start = camera_matrix
+                    .pixel_to_ground(point![scan_line.position as f32, segment.start as f32])
+                    .ok()?;
+                let end = camera_matrix
+                    .pixel_to_ground(point![scan_line.position as f32, segment.end as f32])
+                    .ok()?;
+                if !parameters
+                    .allowed_penalty_mark_segment_length
+                    .contains(&distance(start, end))
+                {
+                    return None;
+                }
+                let center = ((start.coords() + end.coords()) / 2.0).as_point();
+                (center.coords().norm() <= parameters.maximum_detection_distance).then_some(center)
+            })
+        })
+        .collect()
+}
+
+fn find_goal_post_candidates(
+    image_segments: &ImageSegments,
+    field_border: &FieldBorder,
+    camera_matrix: &CameraMatrix,
+    parameters: &LandmarkDetectionParameters,
+) -> Vec<(Point2<Ground>, Rgb)> {
+    image_segments
+        .scan_grid
+        .vertical_scan_lines
+        .iter()
+        .filter_map(|scan_line| {
+            let post = find_post_crossing_field_border(scan_line, field_border, parameters)?;
+            let foot = post.last()?;
+            let position = camera_matrix
+                .pixel_to_ground(point![scan_line.position as f32, foot.end as f32])
+                .ok()?;
+            if position.coords().norm() > parameters.maximum_detection_distance {
+                return None;
+            }
+            let colors: Vec<_> = post
+                .iter()
+                .map(|segment| Rgb::from(segment.color))
+                .collect();
+            Some((position, mean_color(&colors)))
+        })
+        .collect()
+}
+
+/// Goal posts are the only bright structures reaching from outside the field border onto the field
+fn find_post_crossing_field_border(
+    scan_line: &ScanLine,
+    field_border: &FieldBorder,
+    parameters: &LandmarkDetectionParameters,
+) -> Option<Vec<Segment>> {
+    let is_inside_field =
+        |y: u16| field_border.is_inside_field(point![scan_line.position as f32, y as f32]);
+    let mut run: Vec<Segment> = Vec::new();
+    for segment in &scan_line.segments {
+        let is_bright = segment.field_color == Intensity::Low
+            && segment.color.y >= parameters.minimum_luminance;
+        let continues_run = run.last().is_some_and(|last| last.end == segment.start);
+        if !is_bright {
+            if let (Some(first), Some(last)) = (run.first(), run.last()) {
+                if !is_inside_field(first.start) && is_inside_field(last.end) {
+                    return Some(run);
+                }
+            }
+            run.clear();
+            continue;
+        }
+        if !continues_run {
+            run.clear();
+        }
+        run.push(*segment);
+    }
+    None
+}
+
+fn cluster_candidates<T>(
+    candidates: impl Iterator<Item = (Point2<Ground>, T)>,
+    maximum_cluster_distance: f32,
+) -> Vec<(CountedCluster, Vec<T>)> {
+    let mut clusters: Vec<(CountedCluster, Vec<T>)> = Vec::new();
+    for (position, payload) in candidates {
+        let nearest_cluster = clusters
+            .iter_mut()
+            .map(|cluster| {
+                let distance = distance(cluster.0.mean, position);
+                (cluster, distance)
+            })
+            .filter(|(_, distance)| *distance < maximum_cluster_distance)
+            .min_by(|(_, left_distance), (_, right_distance)| {
+                left_distance.total_cmp(right_distance)
+            });
+        match nearest_cluster {
+            Some(((cluster, payloads), _)) => {
+                cluster.push(position);
+                payloads.push(payload);
+            }
+            None => clusters.push((
+                CountedCluster {
+                    mean: position,
+                    samples: 1,
+                    leftmost_point: position,
+                    rightmost_point: position,
+                },
+                vec![payload],
+            )),
+        }
+    }
+    clusters
+}
+
+fn cluster_width(cluster: &CountedCluster) -> f32 {
+    (cluster.rightmost_point.y() - cluster.leftmost_point.y()).abs()
+}
+
+fn mean_color(colors: &[Rgb]) -> Rgb {
+    if colors.is_empty() {
+        return Rgb::default();
+    }
+    let number_of_colors = colors.len() as f32;
+    let (red, green, blue) = colors.iter().fold((0.0, 0.0, 0.0), |sum, color| {
+        (
+            sum.0 + color.red as f32,
+            sum.1 + color.green as f32,
+            sum.2 + color.blue as f32,
+        )
+    });
+    Rgb::new(
+        (red / number_of_colors) as u8,
+        (green / number_of_colors) as u8,
+        (blue / number_of_colors) as u8,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use types::color::YCbCr444;
+
+    use super::*;
+
+    fn segment(start: u16, end: u16, luminance: u8, field_color: Intensity) -> Segment {
+        Segment {
+            start,
+            end,
+            start_edge_type: EdgeType::Rising,
+            end_edge_type: EdgeType::Falling,
+            color: YCbCr444::new(luminance, 128, 128),
+            field_color,
+        }
+    }
+
+    #[test]
+    fn bright_run_crossing_field_border_is_a_post() {
+        let field_border = FieldBorder {
+            border_lines: vec![geometry::line_segment::LineSegment(
+                point![0.0, 100.0],
+                point![640.0, 100.0],
+            )],
+        };
+        let parameters = LandmarkDetectionParameters {
+            minimum_luminance: 140,
+            ..Default::default()
+        };
+        let post = ScanLine {
+            position: 320,
+            segments: vec![
+                segment(0, 60, 50, Intensity::Low),
+                segment(60, 90, 200, Intensity::Low),
+                segment(90, 150, 210, Intensity::Low),
+                segment(150, 480, 90, Intensity::High),
+            ],
+        };
+        let line_on_field = ScanLine {
+            position: 336,
+            segments: vec![
+                segment(0, 120, 90, Intensity::High),
+                segment(120, 130, 220, Intensity::Low),
+                segment(130, 480, 90, Intensity::High),
+            ],
+        };
+
+        let run = find_post_crossing_field_border(&post, &field_border, &parameters)
+            .expect("post should be found");
+        assert_eq!(run.first().unwrap().start, 60);
+        assert_eq!(run.last().unwrap().end, 150);
+        assert!(
+            find_post_crossing_field_border(&line_on_field, &field_border, &parameters).is_none()
+        );
+    }
+
+    #[test]
+    fn candidates_are_clustered_with_their_payloads() {
+        let candidates = [
+            (point![2.0, 0.0], 1),
+            (point![2.02, 0.01], 2),
+            (point![3.0, 1.0], 3),
+        ];
+
+        let clusters = cluster_candidates(candidates.into_iter(), 0.1);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].0.samples, 2);
+        assert_eq!(clusters[0].1, vec![1, 2]);
+        assert_eq!(clusters[1].1, vec![3]);
+    }
+}
@@ -5,6 +5,7 @@ pub mod feet_detection;
 pub mod field_border_detection;
 pub mod image_receiver;
 pub mod image_segmenter;
+pub mod landmark_detection;
 pub mod limb_projector;
 pub mod line_detection;
 pub mod perspective_grid_candidates_provider;
@@ -47,6 +47,8 @@ pub struct Context<'a> {
     pub measured_joints: BodyJoints,
     pub robot_to_walk: Isometry3<Robot, Walk>,
     pub obstacle_avoiding_arms: &'a ArmCommands,
+    /// Scales the leg stiffnesses, e.g. to protect overheating joints
+    pub leg_stiffness_factor: f32,
 }
 
 pub trait WalkTransition {
@@ -142,7 +142,7 @@ impl WalkTransition for Catching {
 impl Catching {
     pub fn compute_commands(&self, context: &Context) -> MotorCommands<BodyJoints> {
         self.step.compute_joints(context).apply_stiffness(
-            context.parameters.stiffnesses.leg_stiffness_walk,
+            context.parameters.stiffnesses.leg_stiffness_walk * context.leg_stiffness_factor,
             context.parameters.stiffnesses.arm_stiffness,
         )
     }
@@ -169,7 +169,7 @@ impl Kicking {
             .compute_joints(context)
             .override_with_kick(context.kick_steps, &self.kick, &self.step)
             .apply_stiffness(
-                context.parameters.stiffnesses.leg_stiffness_walk,
+                context.parameters.stiffnesses.leg_stiffness_walk * context.leg_stiffness_factor,
                 context.parameters.stiffnesses.arm_stiffness,
             )
     }
@@ -93,7 +93,7 @@ impl WalkTransition for Starting {
 impl Starting {
     pub fn compute_commands(&self, context: &Context) -> MotorCommands<BodyJoints> {
         self.step.compute_joints(context).apply_stiffness(
-            context.parameters.stiffnesses.leg_stiffness_walk,
+            context.parameters.stiffnesses.leg_stiffness_walk * context.leg_stiffness_factor,
             context.parameters.stiffnesses.arm_stiffness,
         )
     }
@@ -156,7 +156,7 @@ impl WalkTransition for Walking {
 impl Walking {
     pub fn compute_commands(&self, context: &Context) -> MotorCommands<BodyJoints> {
         self.step.compute_joints(context).apply_stiffness(
-            context.parameters.stiffnesses.leg_stiffness_walk,
+            context.parameters.stiffnesses.leg_stiffness_walk * context.leg_stiffness_factor,
             context.parameters.stiffnesses.arm_stiffness,
         )
     }
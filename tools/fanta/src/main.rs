@@ -1,6 +1,9 @@
 use clap::Parser;
 use color_eyre::Result;
-use communication::client::Client;
+use communication::{
+    client::Client,
+    messages::{SubscriptionRate, UpdateMode},
+};
 use tokio::spawn;
 
 pub fn setup_logger() -> Result<(), fern::InitError> {
@@ -38,7 +41,13 @@ async fn main() -> Result<()> {
     let task = spawn(client.run());
     handle.connect().await;
 
-    let mut subscription = handle.subscribe_text(arguments.path).await;
+    let mut subscription = handle
+        .subscribe_text(
+            arguments.path,
+            SubscriptionRate::default(),
+            UpdateMode::Full,
+        )
+        .await;
 
     while let Ok(message) = subscription.receiver.recv().await {
         println!("{message:#?}");
@@ -3,7 +3,7 @@ use std::{collections::HashMap, path::absolute};
 use bevy::prelude::*;
 use communication::{
     client::{protocol::SubscriptionEvent, BinarySubscriptionHandle, Client, ClientHandle, Status},
-    messages::Path,
+    messages::{Path, SubscriptionRate},
 };
 use coordinate_systems::{Field, Ground, Robot};
 use geometry::line_segment::LineSegment;
@@ -169,7 +169,11 @@ struct Subscription<T> {
 
 impl<T> Subscription<T> {
     async fn new(communication: &ClientHandle, output: impl Into<Path>) -> Self {
-        let receiver = communication.subscribe_binary(output).await;
+        let rate = SubscriptionRate {
+            latest_only: true,
+            ..Default::default()
+        };
+        let receiver = communication.subscribe_binary(output, rate).await;
         Self {
             receiver,
             value: None,
@@ -37,6 +37,7 @@ mod heading {
 lazy_static! {
     pub static ref MANIFEST_PATHS: HashMap<&'static str, &'static str> = {
         HashMap::from([
+            ("batch_replayer", "crates/hulk_batch_replayer"),
             ("imagine", "crates/hulk_imagine"),
             ("nao", "crates/hulk_nao"),
             ("replayer", "crates/hulk_replayer"),
@@ -34,9 +34,9 @@ use log::{error, warn};
 use nao::Nao;
 use panel::Panel;
 use panels::{
-    BallCandidatePanel, BehaviorSimulatorPanel, EnumPlotPanel, ImageColorSelectPanel, ImagePanel,
-    ImageSegmentsPanel, LookAtPanel, ManualCalibrationPanel, MapPanel, ParameterPanel, PlotPanel,
-    RemotePanel, TextPanel, VisionTunerPanel,
+    BallCandidatePanel, BehaviorSimulatorPanel, DecisionTracePanel, EnumPlotPanel,
+    ImageColorSelectPanel, ImagePanel, ImageSegmentsPanel, LookAtPanel, ManualCalibrationPanel,
+    MapPanel, ParameterPanel, PlotPanel, RemotePanel, TextPanel, VisionTunerPanel,
 };
 use reachable_naos::ReachableNaos;
 use repository::{inspect_version::check_for_update, Repository};
@@ -133,6 +133,7 @@ fn main() -> Result<(), eframe::Error> {
 impl_selectable_panel!(
     BallCandidatePanel,
     BehaviorSimulatorPanel,
+    DecisionTracePanel,
     ImagePanel,
     ImageSegmentsPanel,
     LookAtPanel,
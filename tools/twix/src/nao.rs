@@ -1,4 +1,7 @@
-use std::time::{Duration, SystemTime};
+use std::{
+    num::NonZeroUsize,
+    time::{Duration, SystemTime},
+};
 
 use bincode::deserialize;
 use color_eyre::{
@@ -14,7 +17,7 @@ use tokio::{
 
 use communication::{
     client::{Client, ClientHandle, PathsEvent, Status},
-    messages::{Path, TextOrBinary},
+    messages::{Path, SubscriptionRate, TextOrBinary, UpdateMode},
 };
 use hula_types::hardware::Ids;
 use parameters::{directory::Scope, json::nest_value_at_path};
@@ -25,6 +28,38 @@ use crate::{
     value_buffer::{Buffer, BufferHandle, Datum},
 };
 
+/// Panels showing only the latest value do not need more updates than they can display
+const LATEST_VALUE_RATE: SubscriptionRate = SubscriptionRate {
+    minimum_interval: Some(Duration::from_millis(33)),
+    decimation: None,
+    latest_only: true,
+};
+
+/// Images are large and would saturate a Wi-Fi link at the rate of the vision cycler
+const IMAGE_RATE: SubscriptionRate = SubscriptionRate {
+    minimum_interval: Some(Duration::from_millis(100)),
+    decimation: None,
+    latest_only: true,
+};
+
+/// Values read as text can be large, e.g. the whole world state, so only changes are transmitted.
+/// The robot keeps a copy of the last sent value and diffs against it for every update, so this is
+/// only worth it for panels which show arbitrary outputs.
+fn json_update_mode() -> UpdateMode {
+    UpdateMode::Delta {
+        keyframe_interval: NonZeroUsize::new(100).unwrap(),
+    }
+}
+
+/// Buffered subscriptions, e.g. of plots, need every update
+fn rate_for_history(history: Duration) -> SubscriptionRate {
+    if history.is_zero() {
+        LATEST_VALUE_RATE
+    } else {
+        SubscriptionRate::default()
+    }
+}
+
 pub struct Nao {
     runtime: Runtime,
     client: ClientHandle,
@@ -85,7 +120,21 @@ impl Nao {
     }
 
     pub fn subscribe_json(&self, path: impl Into<Path>) -> BufferHandle<Value> {
-        self.subscribe_buffered_json(path, Duration::ZERO)
+        self.subscribe_text(
+            path.into(),
+            Duration::ZERO,
+            LATEST_VALUE_RATE,
+            UpdateMode::Full,
+        )
+    }
+
+    pub fn subscribe_json_deltas(&self, path: impl Into<Path>) -> BufferHandle<Value> {
+        self.subscribe_text(
+            path.into(),
+            Duration::ZERO,
+            LATEST_VALUE_RATE,
+            json_update_mode(),
+        )
     }
 
     pub fn subscribe_buffered_json(
@@ -93,12 +142,26 @@ impl Nao {
         path: impl Into<Path>,
         history: Duration,
     ) -> BufferHandle<Value> {
-        let path = path.into();
+        self.subscribe_text(
+            path.into(),
+            history,
+            rate_for_history(history),
+            UpdateMode::Full,
+        )
+    }
+
+    fn subscribe_text(
+        &self,
+        path: Path,
+        history: Duration,
+        rate: SubscriptionRate,
+        update_mode: UpdateMode,
+    ) -> BufferHandle<Value> {
         let _guard = self.runtime.enter();
         let (task, buffer) = Buffer::new(history);
         let client = self.client.clone();
         spawn(async move {
-            let subscription = client.subscribe_text(path).await;
+            let subscription = client.subscribe_text(path, rate, update_mode).await;
             task.map(subscription, |datum| -> Result<_, Report> {
                 let datum = datum.map_err(|error| eyre!("{error:#}"))?;
                 Ok(Datum {
@@ -117,7 +180,9 @@ impl Nao {
         let (task, buffer) = ChangeBuffer::new();
         let client = self.client.clone();
         spawn(async move {
-            let subscription = client.subscribe_text(path).await;
+            let subscription = client
+                .subscribe_text(path, SubscriptionRate::default(), UpdateMode::Full)
+                .await;
             task.map(subscription, |datum| -> Result<_, Report> {
                 let datum = datum.map_err(|error| eyre!("{error:#}"))?;
                 Ok(Change {
@@ -145,12 +210,30 @@ impl Nao {
     where
         for<'de> T: serde::Deserialize<'de> + Send + Sync + 'static,
     {
-        let path = path.into();
+        self.subscribe_binary(path.into(), history, rate_for_history(history))
+    }
+
+    pub fn subscribe_image<T>(&self, path: impl Into<Path>) -> BufferHandle<T>
+    where
+        for<'de> T: serde::Deserialize<'de> + Send + Sync + 'static,
+    {
+        self.subscribe_binary(path.into(), Duration::ZERO, IMAGE_RATE)
+    }
+
+    fn subscribe_binary<T>(
+        &self,
+        path: Path,
+        history: Duration,
+        rate: SubscriptionRate,
+    ) -> BufferHandle<T>
+    where
+        for<'de> T: serde::Deserialize<'de> + Send + Sync + 'static,
+    {
         let _guard = self.runtime.enter();
         let (task, buffer) = Buffer::new(history);
         let client = self.client.clone();
         spawn(async move {
-            let subscription = client.subscribe_binary(path).await;
+            let subscription = client.subscribe_binary(path, rate).await;
             task.map(subscription, |datum| -> Result<_, Report> {
                 let datum = datum.map_err(|error| eyre!("protocol: {error:#}"))?;
                 Ok(Datum {
@@ -48,7 +48,7 @@ impl Panel for BallCandidatePanel {
         let cycler_path = cycler.as_path();
         let ball_candidates =
             nao.subscribe_value(format!("{cycler_path}.additional_outputs.ball_candidates"));
-        let image = nao.subscribe_value(format!("{cycler_path}.main_outputs.image"));
+        let image = nao.subscribe_image(format!("{cycler_path}.main_outputs.image"));
         Self {
             nao,
             cycler,
@@ -125,7 +125,7 @@ impl BallCandidatePanel {
             .subscribe_value(format!("{cycler_path}.additional_outputs.ball_candidates"));
         self.image = self
             .nao
-            .subscribe_value(format!("{cycler_path}.main_outputs.image"));
+            .subscribe_image(format!("{cycler_path}.main_outputs.image"));
     }
 }
 
@@ -0,0 +1,92 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use eframe::egui::{Color32, Grid, Response, ScrollArea, Slider, Ui, Widget};
+use serde_json::{json, Value};
+
+use types::decision_trace::ActionDecision;
+
+use crate::{nao::Nao, panel::Panel, value_buffer::BufferHandle};
+
+const HISTORY: Duration = Duration::from_secs(10);
+
+pub struct DecisionTracePanel {
+    follow_latest: bool,
+    cycles_back: usize,
+    buffer: BufferHandle<Vec<ActionDecision>>,
+}
+
+impl Panel for DecisionTracePanel {
+    const NAME: &'static str = "Decision Trace";
+
+    fn new(nao: Arc<Nao>, value: Option<&Value>) -> Self {
+        let follow_latest = value
+            .and_then(|value| value.get("follow_latest"))
+            .and_then(|value| value.as_bool())
+            .unwrap_or(true);
+        let buffer =
+            nao.subscribe_buffered_value("Control.additional_outputs.decision_trace", HISTORY);
+        Self {
+            follow_latest,
+            cycles_back: 0,
+            buffer,
+        }
+    }
+
+    fn save(&self) -> Value {
+        json!({
+            "follow_latest": self.follow_latest,
+        })
+    }
+}
+
+impl Widget for &mut DecisionTracePanel {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let series = match self.buffer.get() {
+            Ok(series) => series,
+            Err(error) => return ui.colored_label(Color32::RED, format!("Error: {error}")),
+        };
+        if series.is_empty() {
+            return ui.label("no decision trace available");
+        }
+        let maximum_cycles_back = series.len() - 1;
+        if self.follow_latest {
+            self.cycles_back = 0;
+        }
+        self.cycles_back = self.cycles_back.min(maximum_cycles_back);
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.follow_latest, "Follow latest");
+            ui.add_enabled(
+                !self.follow_latest,
+                Slider::new(&mut self.cycles_back, 0..=maximum_cycles_back)
+                    .smart_aim(false)
+                    .text("Cycles back"),
+            );
+        });
+
+        let datum = &series[maximum_cycles_back - self.cycles_back];
+        let date: DateTime<Utc> = datum.timestamp.into();
+        ui.label(date.format("%T%.3f").to_string());
+
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                Grid::new("decision_trace")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for decision in &datum.value {
+                            ui.label(format!("{:?}", decision.action));
+                            match &decision.rejection_reason {
+                                Some(reason) => ui.label(reason),
+                                None => ui.colored_label(Color32::GREEN, "selected"),
+                            };
+                            ui.end_row();
+                        }
+                    })
+            })
+            .inner
+            .response
+    }
+}
@@ -61,10 +61,10 @@ impl Panel for ImagePanel {
 
         let image_buffer = if is_jpeg {
             let path = format!("{cycler_path}.main_outputs.image.jpeg");
-            RawOrJpeg::Jpeg(nao.subscribe_value(path))
+            RawOrJpeg::Jpeg(nao.subscribe_image(path))
         } else {
             let path = format!("{cycler_path}.main_outputs.image");
-            RawOrJpeg::Raw(nao.subscribe_value(path))
+            RawOrJpeg::Raw(nao.subscribe_image(path))
         };
 
         let overlays = Overlays::new(
@@ -187,12 +187,12 @@ impl ImagePanel {
         self.image_buffer = if jpeg {
             RawOrJpeg::Jpeg(
                 self.nao
-                    .subscribe_value(format!("{cycler_path}.main_outputs.image.jpeg")),
+                    .subscribe_image(format!("{cycler_path}.main_outputs.image.jpeg")),
             )
         } else {
             RawOrJpeg::Raw(
                 self.nao
-                    .subscribe_value(format!("{cycler_path}.main_outputs.image")),
+                    .subscribe_image(format!("{cycler_path}.main_outputs.image")),
             )
         };
     }
@@ -58,7 +58,7 @@ impl Panel for ImageColorSelectPanel {
                 VisionCycler::try_from(string).ok()
             })
             .unwrap_or(VisionCycler::Top);
-        let image = nao.subscribe_value(format!(
+        let image = nao.subscribe_image(format!(
             "{cycler_path}.main_outputs.image",
             cycler_path = cycler.as_path()
         ));
@@ -241,7 +241,7 @@ impl Widget for &mut ImageColorSelectPanel {
                 ui.horizontal(|ui| {
                     let mut cycler_selector = VisionCyclerSelector::new(&mut self.cycler);
                     if cycler_selector.ui(ui).changed() {
-                        self.image = self.nao.subscribe_value(format!(
+                        self.image = self.nao.subscribe_image(format!(
                             "{cycler_path}.main_outputs.image",
                             cycler_path = self.cycler.as_path()
                         ));
@@ -7,7 +7,7 @@ use eframe::{
 };
 
 use coordinate_systems::{Field, Ground};
-use linear_algebra::{IntoFramed, Isometry2, Point2};
+use linear_algebra::{IntoFramed, Isometry2, Point2, Pose2};
 use types::{
     ball_position::SimulatorBallState, field_dimensions::FieldDimensions,
     motion_command::MotionCommand, roles::Role,
@@ -27,6 +27,7 @@ pub struct BehaviorSimulator {
     motion_command: PlayersBufferHandle<MotionCommand>,
     head_yaw: PlayersBufferHandle<f32>,
     ball: BufferHandle<Option<SimulatorBallState>>,
+    opponents: BufferHandle<Vec<Pose2<Field>>>,
 }
 
 impl Layer<Field> for BehaviorSimulator {
@@ -58,12 +59,14 @@ impl Layer<Field> for BehaviorSimulator {
         )
         .unwrap();
         let ball = nao.subscribe_value("BehaviorSimulator.main_outputs.ball");
+        let opponents = nao.subscribe_value("BehaviorSimulator.main_outputs.opponents");
         Self {
             ground_to_field,
             role,
             motion_command,
             head_yaw: sensor_data,
             ball,
+            opponents,
         }
     }
 
@@ -155,6 +158,24 @@ impl Layer<Field> for BehaviorSimulator {
             );
         }
 
+        for opponent in self
+            .opponents
+            .get_last_value()
+            .wrap_err("opponents")?
+            .unwrap_or_default()
+        {
+            painter.pose(
+                opponent,
+                0.15,
+                0.25,
+                Color32::DARK_GRAY,
+                Stroke {
+                    width: 0.02,
+                    color: Color32::BLACK,
+                },
+            );
+        }
+
         if let Some(ball_state) = self.ball.get_last_value().wrap_err("ball state")?.flatten() {
             painter.ball(ball_state.position, 0.05, Color32::WHITE);
         }
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use color_eyre::Result;
+use eframe::epaint::{Color32, Stroke};
+
+use coordinate_systems::Field;
+use types::{field_dimensions::FieldDimensions, localization::Particle};
+
+use crate::{
+    nao::Nao, panels::map::layer::Layer, twix_painter::TwixPainter, value_buffer::BufferHandle,
+};
+
+pub struct LocalizationParticles {
+    particles: BufferHandle<Option<Vec<Particle>>>,
+}
+
+impl Layer<Field> for LocalizationParticles {
+    const NAME: &'static str = "Localization Particles";
+
+    fn new(nao: Arc<Nao>) -> Self {
+        let particles = nao.subscribe_value("Control.additional_outputs.localization.particles");
+        Self { particles }
+    }
+
+    fn paint(
+        &self,
+        painter: &TwixPainter<Field>,
+        _field_dimensions: &FieldDimensions,
+    ) -> Result<()> {
+        let Some(particles) = self.particles.get_last_value()?.flatten() else {
+            return Ok(());
+        };
+        let maximum_weight = particles
+            .iter()
+            .map(|particle| particle.weight)
+            .fold(f32::EPSILON, f32::max);
+        for particle in particles {
+            let alpha = (particle.weight / maximum_weight * 255.0) as u8;
+            painter.pose(
+                particle.pose,
+                0.03,
+                0.06,
+                Color32::from_rgba_unmultiplied(255, 0, 255, alpha),
+                Stroke::new(0.005, Color32::BLACK),
+            );
+        }
+        Ok(())
+    }
+}
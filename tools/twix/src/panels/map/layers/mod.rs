@@ -9,6 +9,7 @@ mod image_segments;
 mod kick_decisions;
 mod line_correspondences;
 mod lines;
+mod localization_particles;
 mod obstacle_filter;
 mod obstacles;
 mod path;
@@ -29,6 +30,7 @@ pub use image_segments::ImageSegments;
 pub use kick_decisions::KickDecisions;
 pub use line_correspondences::LineCorrespondences;
 pub use lines::Lines;
+pub use localization_particles::LocalizationParticles;
 pub use obstacle_filter::ObstacleFilter;
 pub use obstacles::Obstacles;
 pub use path::Path;
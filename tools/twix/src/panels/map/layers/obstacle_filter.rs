@@ -4,7 +4,6 @@ use color_eyre::Result;
 use eframe::epaint::{Color32, Stroke};
 
 use coordinate_systems::Ground;
-use linear_algebra::Point2;
 use types::{field_dimensions::FieldDimensions, obstacle_filter::Hypothesis};
 
 use crate::{
@@ -31,11 +30,18 @@ impl Layer<Ground> for ObstacleFilter {
     ) -> Result<()> {
         if let Some(hypotheses) = self.hypotheses.get_last_value()?.flatten() {
             for hypothesis in hypotheses.iter() {
-                let position = Point2::from(hypothesis.state.mean);
-                let covariance = hypothesis.state.covariance;
+                let position = hypothesis.position();
+                let covariance = hypothesis
+                    .state
+                    .covariance
+                    .fixed_view::<2, 2>(0, 0)
+                    .into_owned();
                 let stroke = Stroke::new(0.01, Color32::BLACK);
                 let fill_color = Color32::from_rgba_unmultiplied(255, 255, 0, 20);
                 painter.covariance(position, covariance, stroke, fill_color);
+
+                let velocity_target = position + hypothesis.velocity();
+                painter.line_segment(position, velocity_target, stroke);
             }
         }
 
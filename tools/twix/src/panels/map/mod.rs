@@ -72,6 +72,7 @@ pub struct MapPanel {
     lines: EnabledLayer<layers::Lines, Ground>,
     ball_search_heatmap: EnabledLayer<layers::BallSearchHeatmap, Field>,
     line_correspondences: EnabledLayer<layers::LineCorrespondences, Field>,
+    localization_particles: EnabledLayer<layers::LocalizationParticles, Field>,
     path_obstacles: EnabledLayer<layers::PathObstacles, Ground>,
     obstacles: EnabledLayer<layers::Obstacles, Ground>,
     path: EnabledLayer<layers::Path, Ground>,
@@ -95,6 +96,7 @@ impl Panel for MapPanel {
         let field = EnabledLayer::new(nao.clone(), value, true);
         let image_segments = EnabledLayer::new(nao.clone(), value, false);
         let line_correspondences = EnabledLayer::new(nao.clone(), value, false);
+        let localization_particles = EnabledLayer::new(nao.clone(), value, false);
         let lines = EnabledLayer::new(nao.clone(), value, true);
         let ball_search_heatmap = EnabledLayer::new(nao.clone(), value, false);
         let path_obstacles = EnabledLayer::new(nao.clone(), value, false);
@@ -124,6 +126,7 @@ impl Panel for MapPanel {
             field,
             image_segments,
             line_correspondences,
+            localization_particles,
             lines,
             ball_search_heatmap,
             path_obstacles,
@@ -149,6 +152,7 @@ impl Panel for MapPanel {
             "field": self.field.save(),
             "image_segments": self.image_segments.save(),
             "line_correspondences": self.line_correspondences.save(),
+            "localization_particles": self.localization_particles.save(),
             "lines": self.lines.save(),
             "ball_search_heatmap": self.obstacle_filter.save(),
             "path_obstacles": self.path_obstacles.save(),
@@ -176,6 +180,7 @@ impl Widget for &mut MapPanel {
                 self.field.checkbox(ui);
                 self.image_segments.checkbox(ui);
                 self.line_correspondences.checkbox(ui);
+                self.localization_particles.checkbox(ui);
                 self.lines.checkbox(ui);
                 self.ball_search_heatmap.checkbox(ui);
                 self.path_obstacles.checkbox(ui);
@@ -250,6 +255,8 @@ impl Widget for &mut MapPanel {
 
         self.line_correspondences
             .generic_paint(&painter, ground_to_field, &field_dimensions);
+        self.localization_particles
+            .generic_paint(&painter, ground_to_field, &field_dimensions);
         self.lines
             .generic_paint(&painter, ground_to_field, &field_dimensions);
 
@@ -1,5 +1,6 @@
 mod ball_candidates;
 mod behavior_simulator;
+mod decision_trace;
 mod enum_plot;
 mod image;
 mod image_color_select;
@@ -15,6 +16,7 @@ mod vision_tuner;
 
 pub use ball_candidates::BallCandidatePanel;
 pub use behavior_simulator::BehaviorSimulatorPanel;
+pub use decision_trace::DecisionTracePanel;
 pub use enum_plot::EnumPlotPanel;
 pub use image::ImagePanel;
 pub use image_color_select::ImageColorSelectPanel;
@@ -22,7 +22,7 @@ impl Panel for TextPanel {
             _ => String::new(),
         };
         let buffer = if !path.is_empty() {
-            Some(nao.subscribe_json(path.clone()))
+            Some(nao.subscribe_json_deltas(path.clone()))
         } else {
             None
         };
@@ -47,7 +47,7 @@ impl Widget for &mut TextPanel {
                     PathFilter::Readable,
                 ));
                 if edit_response.changed() {
-                    self.buffer = Some(self.nao.subscribe_json(self.path.clone()));
+                    self.buffer = Some(self.nao.subscribe_json_deltas(self.path.clone()));
                 }
                 if let Some(buffer) = &self.buffer {
                     if let Ok(Some(timestamp)) = buffer.get_last_timestamp() {